@@ -1,9 +1,10 @@
-use bytecode::{validate_eof_inner, Eof};
+use bytecode::{opcode::MAINNET_OPCODES, validate_eof_with_opcodes, Eof, OpCodeTable};
 use clap::Parser;
 use revm::{
-    bytecode::eof::{self, validate_raw_eof_inner, CodeType, EofError},
+    bytecode::eof::{self, validate_raw_eof_with_opcodes, CodeType, EofError},
     primitives::{hex, Bytes},
 };
+use seismic_revm::instructions::instruction_provider::SEISMIC_OPCODES;
 use std::io;
 
 /// `bytecode` subcommand.
@@ -15,6 +16,9 @@ pub struct Cmd {
     /// Is EOF code in RUNTIME mode.
     #[arg(long)]
     eof_runtime: bool,
+    /// Use the Seismic opcode set, with CLOAD and CSTORE.
+    #[arg(long)]
+    seismic: bool,
     /// Bytecode in hex format string.
     ///
     /// - If bytes start with 0xFE it will be interpreted as a EOF.
@@ -45,6 +49,11 @@ impl Cmd {
         } else {
             None
         };
+        let opcodes: &OpCodeTable = if self.seismic {
+            &SEISMIC_OPCODES
+        } else {
+            &MAINNET_OPCODES
+        };
 
         if let Some(input_bytes) = &self.bytes {
             let Some(bytes) = trim_decode(input_bytes) else {
@@ -55,7 +64,7 @@ impl Cmd {
                 match Eof::decode(bytes) {
                     Ok(eof) => {
                         println!("Decoding: {:#?}", eof);
-                        match validate_eof_inner(&eof, container_kind) {
+                        match validate_eof_with_opcodes(&eof, container_kind, opcodes) {
                             Ok(_) => {
                                 println!("Validation: OK");
                             }
@@ -70,7 +79,7 @@ impl Cmd {
                     }
                 }
             } else {
-                eof::printer::print(&bytes)
+                eof::printer::print_with_opcodes(&bytes, opcodes)
             }
             return;
         }
@@ -86,7 +95,7 @@ impl Cmd {
             let Some(bytes) = trim_decode(&input) else {
                 return;
             };
-            match validate_raw_eof_inner(bytes, container_kind) {
+            match validate_raw_eof_with_opcodes(bytes, container_kind, opcodes) {
                 Ok(eof) => {
                     println!(
                         "OK {}/{}/{}",
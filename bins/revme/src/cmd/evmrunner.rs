@@ -1,12 +1,23 @@
-use clap::Parser;
-use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+use crate::prestate::{Prestate, PrestateError};
+use clap::{Parser, ValueEnum};
+use database::{
+    CacheDB, EmptyDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET, BENCH_TARGET_BALANCE,
+};
 use inspector::{inspectors::TracerEip3155, InspectEvm};
 use revm::{
     bytecode::{Bytecode, BytecodeDecodeError},
-    primitives::{hex, TxKind},
-    Context, Database, ExecuteEvm, MainBuilder, MainContext,
+    context::result::ExecutionResult,
+    primitives::{hardfork::SpecId, hex, Bytes, TxKind, B256},
+    state::{AccountInfo, EvmState},
+    Context, Database, DatabaseCommit, ExecuteEvm, MainBuilder, MainContext,
+};
+use schnorrkel::{ExpansionMode, Keypair, MiniSecretKey};
+use seismic_revm::{
+    chain::key_source::MockKeySource, transaction::abstraction::RngMode, DefaultSeismic,
+    SeismicBuilder, SeismicChain, SeismicHaltReason, SeismicSpecId,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::{borrow::Cow, fs};
 use std::{io::Error as IoError, time::Instant};
 
@@ -18,6 +29,14 @@ pub enum Errors {
     InvalidBytecode,
     #[error("Invalid input")]
     InvalidInput,
+    #[error("Invalid spec: {0}")]
+    InvalidSpec(String),
+    #[error("Invalid tx hash")]
+    InvalidTxHash,
+    #[error("Invalid VRF key, expected a hex-encoded 32 byte mini secret key or 96 byte keypair")]
+    InvalidVrfKey,
+    #[error(transparent)]
+    Prestate(#[from] PrestateError),
     #[error("EVM Error")]
     EVMError,
     #[error(transparent)]
@@ -26,6 +45,25 @@ pub enum Errors {
     BytecodeDecodeError(#[from] BytecodeDecodeError),
 }
 
+/// Kernel mode of the Seismic RNG precompile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum RngModeArg {
+    /// Unique entropy per call, as used by `eth_call`.
+    Simulation,
+    /// Deterministic entropy, as used for transaction execution.
+    #[default]
+    Execution,
+}
+
+impl From<RngModeArg> for RngMode {
+    fn from(mode: RngModeArg) -> Self {
+        match mode {
+            RngModeArg::Simulation => RngMode::Simulation,
+            RngModeArg::Execution => RngMode::Execution,
+        }
+    }
+}
+
 /// Evm runner command allows running arbitrary evm bytecode
 ///
 /// Bytecode can be provided from cli or from file with `--path` option.
@@ -51,6 +89,33 @@ pub struct Cmd {
     /// Whether to print the trace
     #[arg(long)]
     trace: bool,
+    /// Run with the Seismic EVM, enabling CLOAD/CSTORE and the Seismic precompiles
+    #[arg(long)]
+    seismic: bool,
+    /// Hardfork to run with, e.g. `Prague`, or `Mercury` in Seismic mode
+    ///
+    /// Defaults to `Mercury` with either EVM. Pass `Venus` to run on the latest Seismic spec.
+    #[arg(long)]
+    spec: Option<String>,
+    /// Path to a geth-style `alloc` JSON file with the accounts to load before execution
+    ///
+    /// Storage values are either a hex value or `{ "value": .., "isPrivate": true }`.
+    #[arg(long)]
+    prestate: Option<PathBuf>,
+    /// Path to write the accounts to after execution, in the `--prestate` format
+    #[arg(long)]
+    poststate: Option<PathBuf>,
+    /// RNG mode of the transaction
+    #[arg(long, value_enum, default_value_t, requires = "seismic")]
+    rng_mode: RngModeArg,
+    /// Hex-encoded transaction hash, used for domain separation in the RNG
+    #[arg(long, requires = "seismic")]
+    tx_hash: Option<String>,
+    /// Path to a file containing the hex-encoded root VRF key of the RNG
+    ///
+    /// Defaults to the insecure sample key.
+    #[arg(long, requires = "seismic")]
+    vrf_key: Option<PathBuf>,
 }
 
 impl Cmd {
@@ -72,39 +137,41 @@ impl Cmd {
         let input = hex::decode(self.input.trim())
             .map_err(|_| Errors::InvalidInput)?
             .into();
+        let bytecode = Bytecode::new_raw_checked(bytecode.into())?;
+
+        if self.seismic {
+            return self.run_seismic(bytecode, input);
+        }
 
-        let mut db = BenchmarkDB::new_bytecode(Bytecode::new_raw_checked(bytecode.into())?);
+        let spec = match &self.spec {
+            Some(spec) => spec
+                .parse::<SpecId>()
+                .map_err(|_| Errors::InvalidSpec(spec.clone()))?,
+            None => SpecId::default(),
+        };
+
+        let mut db = self.prepare_database(bytecode)?;
 
         let nonce = db
             .basic(BENCH_CALLER)
             .unwrap()
             .map_or(0, |account| account.nonce);
 
-        // BenchmarkDB is dummy state that implements Database trait.
-        // The bytecode is deployed at zero address.
         let mut evm = Context::mainnet()
-            .with_db(db)
+            .with_db(db.clone())
             .modify_tx_chained(|tx| {
                 tx.caller = BENCH_CALLER;
                 tx.kind = TxKind::Call(BENCH_TARGET);
                 tx.data = input;
                 tx.nonce = nonce;
             })
+            .modify_cfg_chained(|cfg| cfg.spec = spec)
             .build_mainnet_with_inspector(TracerEip3155::new(Box::new(std::io::stdout())));
 
         if self.bench {
-            let mut criterion = criterion::Criterion::default()
-                .warm_up_time(std::time::Duration::from_millis(300))
-                .measurement_time(std::time::Duration::from_secs(2))
-                .without_plots();
-            let mut criterion_group = criterion.benchmark_group("revme");
-            criterion_group.bench_function("evm", |b| {
-                b.iter(|| {
-                    let _ = evm.replay().unwrap();
-                })
+            bench(|| {
+                let _ = evm.replay().unwrap();
             });
-            criterion_group.finish();
-
             return Ok(());
         }
 
@@ -121,8 +188,146 @@ impl Cmd {
         if self.state {
             println!("State: {:#?}", out.state);
         }
+        self.write_poststate(db, out.state)?;
+
+        println!("Elapsed: {:?}", time);
+        Ok(())
+    }
+
+    /// Runs the bytecode with the Seismic EVM.
+    fn run_seismic(&self, bytecode: Bytecode, input: Bytes) -> Result<(), Errors> {
+        let spec = match &self.spec {
+            Some(spec) => spec
+                .parse::<SeismicSpecId>()
+                .map_err(|_| Errors::InvalidSpec(spec.clone()))?,
+            None => SeismicSpecId::default(),
+        };
+        let tx_hash = match &self.tx_hash {
+            Some(tx_hash) => tx_hash
+                .trim()
+                .parse::<B256>()
+                .map_err(|_| Errors::InvalidTxHash)?,
+            None => B256::ZERO,
+        };
+        let chain = match &self.vrf_key {
+            Some(path) => SeismicChain::new(read_vrf_key(path)?),
+            None => SeismicChain::default(),
+        }
+        // The runner executes local bytecode, contract keys come from the mock master key.
+        .with_key_source(Arc::new(MockKeySource::default()));
+
+        let mut db = self.prepare_database(bytecode)?;
+
+        let nonce = db
+            .basic(BENCH_CALLER)
+            .unwrap()
+            .map_or(0, |account| account.nonce);
+
+        let mut evm = Context::seismic()
+            .with_db(db.clone())
+            .with_chain(chain)
+            .modify_tx_chained(|tx| {
+                tx.base.caller = BENCH_CALLER;
+                tx.base.kind = TxKind::Call(BENCH_TARGET);
+                tx.base.data = input;
+                tx.base.nonce = nonce;
+                tx.tx_hash = tx_hash;
+                tx.rng_mode = self.rng_mode.into();
+            })
+            .modify_cfg_chained(|cfg| cfg.spec = spec)
+            .build_seismic_with_inspector(TracerEip3155::new(Box::new(std::io::stdout())));
+
+        if self.bench {
+            bench(|| {
+                let _ = evm.replay().unwrap();
+            });
+            return Ok(());
+        }
+
+        let time = Instant::now();
+        let out = if self.trace {
+            evm.inspect_replay().map_err(|_| Errors::EVMError)?
+        } else {
+            evm.replay().map_err(|_| Errors::EVMError)?
+        };
+        let time = time.elapsed();
+
+        print_seismic_result(&out.result);
+        if self.state {
+            println!("State: {:#?}", out.state);
+        }
+        self.write_poststate(db, out.state)?;
 
         println!("Elapsed: {:?}", time);
         Ok(())
     }
+
+    /// Deploys the bytecode at [`BENCH_TARGET`] and funds [`BENCH_CALLER`], then loads the
+    /// `--prestate` accounts, which can override both.
+    fn prepare_database(&self, bytecode: Bytecode) -> Result<CacheDB<EmptyDB>, Errors> {
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(
+            BENCH_TARGET,
+            AccountInfo::from_bytecode(bytecode)
+                .with_nonce(1)
+                .with_balance(BENCH_TARGET_BALANCE),
+        );
+        db.insert_account_info(
+            BENCH_CALLER,
+            AccountInfo::from_balance(BENCH_CALLER_BALANCE),
+        );
+        if let Some(path) = &self.prestate {
+            if !path.exists() {
+                return Err(Errors::PathNotExists);
+            }
+            Prestate::from_file(path)?.insert_into(&mut db)?;
+        }
+        Ok(db)
+    }
+
+    /// Commits the execution state and writes the accounts to `--poststate`, if set.
+    fn write_poststate(&self, mut db: CacheDB<EmptyDB>, state: EvmState) -> Result<(), Errors> {
+        if let Some(path) = &self.poststate {
+            db.commit(state);
+            Prestate::from_cache(&db).write_to_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Benchmarks `f` with criterion.
+fn bench(mut f: impl FnMut()) {
+    let mut criterion = criterion::Criterion::default()
+        .warm_up_time(std::time::Duration::from_millis(300))
+        .measurement_time(std::time::Duration::from_secs(2))
+        .without_plots();
+    let mut criterion_group = criterion.benchmark_group("revme");
+    criterion_group.bench_function("evm", |b| b.iter(&mut f));
+    criterion_group.finish();
+}
+
+/// Prints the execution result, naming the Seismic specific halt reasons.
+fn print_seismic_result(result: &ExecutionResult<SeismicHaltReason>) {
+    if let ExecutionResult::Halt { reason, gas_used } = result {
+        match reason {
+            SeismicHaltReason::Base(reason) => println!("Halted: {reason:?}"),
+            reason => println!("Halted: {reason}"),
+        }
+        println!("Gas used: {gas_used}");
+    }
+    println!("Result: {:#?}", result);
+}
+
+/// Reads a hex-encoded root VRF key.
+///
+/// Accepts a 32 byte mini secret key, expanded in Ed25519 mode, or a 96 byte keypair.
+fn read_vrf_key(path: &Path) -> Result<Keypair, Errors> {
+    let key = hex::decode(fs::read_to_string(path)?.trim()).map_err(|_| Errors::InvalidVrfKey)?;
+    match key.len() {
+        32 => MiniSecretKey::from_bytes(&key)
+            .map(|key| key.expand_to_keypair(ExpansionMode::Ed25519))
+            .map_err(|_| Errors::InvalidVrfKey),
+        96 => Keypair::from_bytes(&key).map_err(|_| Errors::InvalidVrfKey),
+        _ => Err(Errors::InvalidVrfKey),
+    }
 }
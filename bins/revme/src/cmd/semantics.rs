@@ -1,3 +1,4 @@
+use crate::prestate::Prestate;
 use evm_handler::{EvmConfig, EvmExecutor};
 use revm::{
     database::{CacheDB, EmptyDB},
@@ -7,31 +8,39 @@ use revm::{
 use log::{info, LevelFilter};
 use rayon::prelude::*;
 use state::AccountInfo;
-use std::path::PathBuf;
-use std::time::Instant;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use clap::{ArgAction, Parser};
 
+mod abi;
+mod artifacts;
+use artifacts::Compiler;
 mod errors;
 pub use errors::Errors;
 mod semantic_tests;
 use semantic_tests::SemanticTests;
 mod compiler_evm_versions;
 mod evm_handler;
+mod gas;
+use gas::rewrite_expectations;
 mod parser;
+mod report;
+use report::{FileReport, Outcome, Report};
 mod test_cases;
 mod utils;
 use utils::find_test_files;
 
 /// EVM runner command that allows running Solidity semantic tests.
-/// If a path is provided, it will process that file or recursively process all `.sol` files in that directory.
-/// If no path is provided, it defaults to the Solidity semantic tests directory.
+/// It processes the given file, or recursively processes all `.sol` files in the given directory.
 #[derive(Parser, Debug)]
 pub struct Cmd {
-    /// Path to a Solidity file or directory containing Solidity files. If no file is provided,
-    /// it will default to the Solidity semantic tests directory.
-    #[clap(long)]
-    path: Option<PathBuf>,
+    /// Path to a Solidity test file or to a directory of them, such as
+    /// `seismic-solidity/test/libsolidity/semanticTests`.
+    #[clap(long, alias = "tests-dir")]
+    path: PathBuf,
 
     /// Print the trace.
     #[clap(long)]
@@ -48,6 +57,45 @@ pub struct Cmd {
     /// Will not return on failure.
     #[clap(long, alias = "no-fail-fast")]
     keep_going: bool,
+
+    /// Path to a geth-style `alloc` JSON file with accounts to load before each test file.
+    #[clap(long)]
+    prestate: Option<PathBuf>,
+
+    /// Fail tests whose gas differs from their `gas legacy:` or `gas ir:` expectations,
+    /// instead of only reporting the delta.
+    #[clap(long)]
+    enforce_gas: bool,
+
+    /// Rewrite the gas expectations of the test files in place with the gas actually used.
+    #[clap(long, conflicts_with = "enforce_gas")]
+    update_gas: bool,
+
+    /// Path to the `solc` binary used to compile test sources missing from the artifacts.
+    #[clap(long, default_value = "/usr/local/bin/solc")]
+    solc: PathBuf,
+
+    /// Directory of pre-built artifact bundles, named `<keccak256 of the sources>.json`.
+    /// Sources found there are not compiled.
+    #[clap(long)]
+    artifacts: Option<PathBuf>,
+
+    /// Directory to cache compiled artifacts in, using the `--artifacts` layout.
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Write a JSON report with the outcome of every test file and test case.
+    #[clap(long)]
+    report_json: Option<PathBuf>,
+
+    /// Write a JUnit XML report with one test suite per test file.
+    #[clap(long)]
+    junit: Option<PathBuf>,
+
+    /// Directory to write the post-state of each test file to in the `--prestate` format, at
+    /// the path of the test relative to `--path` with a `.json` extension.
+    #[clap(long)]
+    poststate: Option<PathBuf>,
 }
 
 impl Cmd {
@@ -55,26 +103,63 @@ impl Cmd {
         self.setup_logging();
         let start_time = Instant::now();
         let test_files = self.find_test_files()?;
+        let compiler = Compiler::new(
+            self.solc.clone(),
+            self.artifacts.clone(),
+            self.cache_dir.clone(),
+        )?;
 
-        if self.single_thread {
-            info!("Running in single-threaded mode");
-
-            for test_file in test_files {
-                self.process_test_file(test_file)?;
+        // Without `--keep-going`, files that start after a failure are reported as not run.
+        let aborted = AtomicBool::new(false);
+        let run_file = |test_file: &PathBuf| {
+            if aborted.load(Ordering::Relaxed) {
+                return (FileReport::not_run(&test_file.to_string_lossy()), Ok(()));
+            }
+            let (report, result) = self.process_test_file(test_file.clone(), &compiler);
+            if result.is_err() && !self.keep_going {
+                aborted.store(true, Ordering::Relaxed);
             }
+            (report, result)
+        };
+
+        let outcomes: Vec<_> = if self.single_thread {
+            info!("Running in single-threaded mode");
+            test_files.iter().map(run_file).collect()
         } else {
             info!("Running in multi-threaded mode");
+            test_files.par_iter().map(run_file).collect()
+        };
 
-            // Use parallel iterator
-            test_files
-                .par_iter()
-                .try_for_each(|test_file| self.process_test_file(test_file.clone()))?;
+        let mut reports = Vec::with_capacity(outcomes.len());
+        let mut result = Ok(());
+        for (report, file_result) in outcomes {
+            reports.push(report);
+            if result.is_ok() {
+                result = file_result;
+            }
         }
 
         let duration = start_time.elapsed();
         info!("Execution time: {:?}", duration);
 
-        Ok(())
+        let report = Report::new(reports, duration);
+        info!(
+            "Test files: {} passed, {} failed, {} skipped; test cases: {} passed, {} failed, {} skipped",
+            report.files.passed,
+            report.files.failed,
+            report.files.skipped,
+            report.test_cases.passed,
+            report.test_cases.failed,
+            report.test_cases.skipped
+        );
+        if let Some(path) = &self.report_json {
+            report.write_json(path)?;
+        }
+        if let Some(path) = &self.junit {
+            report.write_junit(path)?;
+        }
+
+        result
     }
 
     fn setup_logging(&self) {
@@ -89,59 +174,110 @@ impl Cmd {
     }
 
     fn find_test_files(&self) -> Result<Vec<PathBuf>, Errors> {
-        if let Some(ref path) = self.path {
-            if path.is_file() {
-                Ok(vec![path.clone()])
-            } else if path.is_dir() {
-                find_test_files(path)
-            } else {
-                Err(Errors::PathNotExists)
-            }
+        if self.path.is_file() {
+            Ok(vec![self.path.clone()])
+        } else if self.path.is_dir() {
+            find_test_files(&self.path)
         } else {
-            let current_dir = std::env::current_dir()?;
-            let parent_dir = current_dir
-                .parent()
-                .ok_or(Errors::PathNotExists)?
-                .parent()
-                .ok_or(Errors::PathNotExists)?
-                .parent()
-                .ok_or(Errors::PathNotExists)?;
-            let semantic_tests_path =
-                parent_dir.join("seismic-solidity/test/libsolidity/semanticTests/");
-            find_test_files(&semantic_tests_path)
+            Err(Errors::TestPathNotFound(self.path.clone()))
         }
     }
 
-    fn process_test_file(&self, test_file: PathBuf) -> Result<(), Errors> {
+    /// Runs a test file, returning its report and whether it failed.
+    fn process_test_file(
+        &self,
+        test_file: PathBuf,
+        compiler: &Compiler,
+    ) -> (FileReport, Result<(), Errors>) {
+        let start_time = Instant::now();
+        let mut report = FileReport::new(&test_file.to_string_lossy());
+        let result = self.run_test_file(test_file, compiler, &mut report);
+        report.elapsed_secs = start_time.elapsed().as_secs_f64();
+
+        let result = match result {
+            Err(Errors::UnhandledTestFormat) => {
+                report.set_outcome(Outcome::Skipped, &Errors::UnhandledTestFormat);
+                Ok(())
+            }
+            Err(err) => {
+                report.set_outcome(Outcome::Failed, &err);
+                Err(err)
+            }
+            Ok(()) => Ok(()),
+        };
+        (report, result)
+    }
+
+    fn run_test_file(
+        &self,
+        test_file: PathBuf,
+        compiler: &Compiler,
+        report: &mut FileReport,
+    ) -> Result<(), Errors> {
         info!("test_file: {:?}", test_file);
         let test_file_path = test_file.to_str().ok_or(Errors::InvalidTestFormat)?;
 
-        match SemanticTests::new(test_file_path) {
+        match SemanticTests::new(test_file_path, compiler) {
             Ok(semantic_tests) => {
                 let evm_version = semantic_tests.contract_infos[0].evm_version;
                 let evm_config = EvmConfig::new(evm_version);
                 let db = self.prepare_database(&evm_config)?;
 
-                let mut evm_executor = EvmExecutor::new(db, evm_config.clone(), evm_version);
+                let mut evm_executor =
+                    EvmExecutor::new(db, evm_config.clone(), evm_version, semantic_tests.gas_mode)
+                        .with_contracts(semantic_tests.contract_infos.clone());
 
-                for test_case in &semantic_tests.test_cases {
-                    let result = evm_executor.run_test_case(test_case, self.trace, test_file_path);
-                    match result {
-                        Ok(_) => {}
-                        Err(e) => {
-                            if !self.keep_going {
-                                return Err(e);
+                for (index, test_case) in semantic_tests.test_cases.iter().enumerate() {
+                    let start_time = Instant::now();
+                    let gas_checks = evm_executor.gas_checks.len();
+                    let mut result =
+                        evm_executor.run_test_case(test_case, self.trace, test_file_path);
+                    if result.is_ok()
+                        && self.enforce_gas
+                        && evm_executor.gas_checks[gas_checks..]
+                            .iter()
+                            .any(|check| !check.is_match())
+                    {
+                        result = Err(Errors::GasMismatch);
+                    }
+                    report.add_test_case(
+                        test_case.name(index),
+                        Some(&result),
+                        start_time.elapsed(),
+                    );
+                    if let Err(e) = result {
+                        if !self.keep_going {
+                            for (index, test_case) in
+                                semantic_tests.test_cases.iter().enumerate().skip(index + 1)
+                            {
+                                report.add_test_case(test_case.name(index), None, Duration::ZERO);
                             }
+                            return Err(e);
                         }
-                    };
+                    }
                     evm_executor.config.block_number =
                         evm_executor.config.block_number.wrapping_add(1);
 
                     evm_executor.config.timestamp = evm_executor.config.timestamp.wrapping_add(15);
                 }
-            }
-            Err(Errors::UnhandledTestFormat) => {
-                return Ok(());
+
+                let gas_mismatches = evm_executor
+                    .gas_checks
+                    .iter()
+                    .filter(|check| !check.is_match())
+                    .count();
+                if gas_mismatches > 0 && self.update_gas {
+                    info!("Updating {} gas expectations", gas_mismatches);
+                    rewrite_expectations(test_file_path, &evm_executor.gas_checks)?;
+                }
+
+                if let Some(dir) = &self.poststate {
+                    let path = poststate_path(dir, &self.path, &test_file)?;
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    Prestate::from_cache(evm_executor.db()).write_to_file(&path)?;
+                }
             }
             Err(e) => {
                 return Err(e);
@@ -159,6 +295,46 @@ impl Cmd {
             code_hash: Default::default(),
         };
         db.insert_account_info(config.caller, account_info);
+        if let Some(path) = &self.prestate {
+            if !path.exists() {
+                return Err(Errors::PathNotExists);
+            }
+            Prestate::from_file(path)?.insert_into(&mut db)?;
+        }
         Ok(db)
     }
 }
+
+/// Returns where the post-state of `test_file` goes: its path relative to the `--path` root,
+/// under `dir`, with a `.json` extension, so tests of the same name in different directories
+/// do not overwrite each other.
+fn poststate_path(dir: &Path, root: &Path, test_file: &Path) -> Result<PathBuf, Errors> {
+    let relative = match test_file.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative,
+        // `--path` is the test file itself.
+        _ => Path::new(test_file.file_name().ok_or(Errors::InvalidTestFormat)?),
+    };
+    Ok(dir.join(relative).with_extension("json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poststate_mirrors_test_path() {
+        let dir = Path::new("out");
+        assert_eq!(
+            poststate_path(dir, Path::new("tests"), Path::new("tests/a/f.sol")).unwrap(),
+            Path::new("out/a/f.json")
+        );
+        assert_eq!(
+            poststate_path(dir, Path::new("tests"), Path::new("tests/b/f.sol")).unwrap(),
+            Path::new("out/b/f.json")
+        );
+        assert_eq!(
+            poststate_path(dir, Path::new("tests/a/f.sol"), Path::new("tests/a/f.sol")).unwrap(),
+            Path::new("out/f.json")
+        );
+    }
+}
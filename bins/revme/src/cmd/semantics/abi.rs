@@ -0,0 +1,424 @@
+//! ABI types of semantic test values, on top of the `alloy-dyn-abi` codec.
+//!
+//! Types are read from isoltest call signatures and from the ABI of compiled contracts. The
+//! Seismic shielded types `suint<N>`, `sint<N>`, `sbool` and `saddress` are encoded as their
+//! public counterparts, but keep their name in signatures so selectors match the compiler.
+//!
+//! Values use the isoltest syntax: numbers, `true`/`false`, `"strings"`, `hex"..."`,
+//! `left(...)`/`right(...)`, arrays `[a, b]` and tuples `(a, b)`. A value must fit its type,
+//! e.g. `300` is not a `uint8`.
+use std::{fmt, sync::LazyLock};
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use regex::{Captures, Regex};
+use revm::primitives::{hex, keccak256, Address, FixedBytes, B256, I256, U256};
+
+use super::{
+    parser::Parser,
+    utils::{parse_string_with_escapes, split_top_level},
+    Errors,
+};
+
+/// Elementary type names of a type, e.g. `uint` and `bytes` in `(uint,bytes)[2]`.
+static TYPE_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_]\w*").expect("invalid regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AbiType {
+    /// Canonical name, shielded types keep their `s` prefix.
+    name: String,
+    /// Public type the values are encoded as.
+    ty: DynSolType,
+}
+
+impl AbiType {
+    pub(crate) fn parse(ty: &str) -> Result<Self, Errors> {
+        let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+        let name = TYPE_NAME
+            .replace_all(&ty, |caps: &Captures<'_>| {
+                canonical_name(&caps[0]).to_string()
+            })
+            .into_owned();
+        let public = TYPE_NAME.replace_all(&name, |caps: &Captures<'_>| {
+            public_name(&caps[0]).to_string()
+        });
+        let ty = DynSolType::parse(&public).map_err(|_| Errors::InvalidFunctionSignature)?;
+        Ok(Self { name, ty })
+    }
+
+    /// Returns the type of an ABI JSON parameter, expanding tuple components.
+    pub(crate) fn from_json(param: &serde_json::Value) -> Result<Self, Errors> {
+        Self::parse(&json_type_name(param).ok_or(Errors::InvalidArtifacts)?)
+    }
+
+    /// Parses a value of the type written in isoltest syntax.
+    pub(crate) fn parse_value(&self, value: &str) -> Result<DynSolValue, Errors> {
+        parse_value(&self.ty, value)
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Function name and parameter types of a call, e.g. `f(uint,(bytes,suint)[])`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FunctionSignature {
+    pub name: String,
+    pub inputs: Vec<AbiType>,
+}
+
+impl FunctionSignature {
+    pub(crate) fn parse(signature: &str) -> Result<Self, Errors> {
+        let start = signature
+            .find('(')
+            .ok_or(Errors::InvalidFunctionSignature)?;
+        let inner = signature[start + 1..]
+            .trim_end()
+            .strip_suffix(')')
+            .ok_or(Errors::InvalidFunctionSignature)?;
+        let inputs = split_top_level(inner)
+            .iter()
+            .map(|ty| AbiType::parse(ty))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            name: signature[..start].trim().to_string(),
+            inputs,
+        })
+    }
+
+    /// Returns the selector of the canonical signature.
+    pub(crate) fn selector(&self) -> [u8; 4] {
+        let hash = keccak256(self.to_string().as_bytes());
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+}
+
+impl fmt::Display for FunctionSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inputs: Vec<_> = self.inputs.iter().map(AbiType::to_string).collect();
+        write!(f, "{}({})", self.name, inputs.join(","))
+    }
+}
+
+/// Encodes values as the parameters of a call or the return data of a function.
+pub(crate) fn encode_params(values: Vec<DynSolValue>) -> Vec<u8> {
+    DynSolValue::Tuple(values).abi_encode_params()
+}
+
+/// Decodes the parameters or return data of the given types.
+pub(crate) fn decode_params(types: &[AbiType], data: &[u8]) -> Result<Vec<DynSolValue>, Errors> {
+    let tuple = DynSolType::Tuple(types.iter().map(|ty| ty.ty.clone()).collect());
+    match tuple.abi_decode_params(data) {
+        Ok(DynSolValue::Tuple(values)) => Ok(values),
+        _ => Err(Errors::InvalidTestOutput),
+    }
+}
+
+/// Formats values as a comma separated isoltest list.
+pub(crate) fn format_params(values: &[DynSolValue]) -> String {
+    let values: Vec<_> = values.iter().map(format_value).collect();
+    values.join(", ")
+}
+
+fn parse_value(ty: &DynSolType, value: &str) -> Result<DynSolValue, Errors> {
+    let value = value.trim();
+    let mismatch = || Errors::ValueTypeMismatch(value.to_string(), ty.sol_type_name().into_owned());
+    match ty {
+        DynSolType::Array(elem) => parse_array(elem, value).map(DynSolValue::Array),
+        DynSolType::FixedArray(elem, size) => {
+            let items = parse_array(elem, value)?;
+            if items.len() != *size {
+                return Err(Errors::InvalidArgumentCount);
+            }
+            Ok(DynSolValue::FixedArray(items))
+        }
+        DynSolType::Tuple(types) => {
+            let inner = value
+                .strip_prefix('(')
+                .and_then(|v| v.strip_suffix(')'))
+                .ok_or_else(mismatch)?;
+            let items = split_top_level(inner);
+            if items.len() != types.len() {
+                return Err(Errors::InvalidArgumentCount);
+            }
+            types
+                .iter()
+                .zip(&items)
+                .map(|(ty, item)| parse_value(ty, item))
+                .collect::<Result<_, _>>()
+                .map(DynSolValue::Tuple)
+        }
+        DynSolType::Bytes => parse_bytes(value)
+            .map(DynSolValue::Bytes)
+            .ok_or_else(mismatch),
+        DynSolType::String => parse_bytes(value)
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .map(DynSolValue::String)
+            .ok_or_else(mismatch),
+        DynSolType::Uint(bits) => {
+            if value.starts_with('-') {
+                return Err(mismatch());
+            }
+            let value = U256::from_be_bytes(Parser::parse_word(value)?.0);
+            if *bits < 256 && value >> *bits != U256::ZERO {
+                return Err(mismatch());
+            }
+            Ok(DynSolValue::Uint(value, *bits))
+        }
+        DynSolType::Int(bits) => {
+            let value = I256::from_raw(U256::from_be_bytes(Parser::parse_word(value)?.0));
+            // Fits if all the bits above the sign bit are copies of it.
+            let high = value.asr(*bits - 1);
+            if high != I256::ZERO && high != I256::MINUS_ONE {
+                return Err(mismatch());
+            }
+            Ok(DynSolValue::Int(value, *bits))
+        }
+        DynSolType::Bool => match Parser::parse_word(value)? {
+            word if word == B256::ZERO => Ok(DynSolValue::Bool(false)),
+            word if word == B256::with_last_byte(1) => Ok(DynSolValue::Bool(true)),
+            _ => Err(mismatch()),
+        },
+        DynSolType::Address => {
+            let word = Parser::parse_word(value)?;
+            if word[..12] != [0; 12] {
+                return Err(mismatch());
+            }
+            Ok(DynSolValue::Address(Address::from_word(word)))
+        }
+        DynSolType::FixedBytes(size) => {
+            let word = Parser::parse_word(value)?;
+            if word[*size..].iter().any(|b| *b != 0) {
+                return Err(mismatch());
+            }
+            Ok(DynSolValue::FixedBytes(word, *size))
+        }
+        DynSolType::Function => {
+            let word = Parser::parse_word(value)?;
+            if word[24..] != [0; 8] {
+                return Err(mismatch());
+            }
+            Ok(DynSolValue::Function(FixedBytes::from_slice(&word[..24])))
+        }
+        // Struct types of the `eip712` feature are never parsed from a signature.
+        #[allow(unreachable_patterns)]
+        _ => Err(Errors::InvalidFunctionSignature),
+    }
+}
+
+/// Parses the elements of an array value, `[a, b]`.
+fn parse_array(elem: &DynSolType, value: &str) -> Result<Vec<DynSolValue>, Errors> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| {
+            Errors::ValueTypeMismatch(value.to_string(), format!("{}[]", elem.sol_type_name()))
+        })?;
+    split_top_level(inner)
+        .iter()
+        .map(|item| parse_value(elem, item))
+        .collect()
+}
+
+/// Parses the content of a `bytes` or `string` value, `hex"..."` or `"..."`.
+fn parse_bytes(value: &str) -> Option<Vec<u8>> {
+    if let Some(inner) = value
+        .strip_prefix("hex\"")
+        .and_then(|v| v.strip_suffix('"'))
+    {
+        hex::decode(inner).ok()
+    } else {
+        let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+        parse_string_with_escapes(inner).ok()
+    }
+}
+
+/// Formats a value in isoltest syntax.
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Address(address) => address.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("left(0x{})", hex::encode(&word[..*size])),
+        DynSolValue::Function(function) => hex::encode_prefixed(function),
+        DynSolValue::String(string) => format!("\"{}\"", string.escape_default()),
+        DynSolValue::Bytes(bytes) => format!("hex\"{}\"", hex::encode(bytes)),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => {
+            format!("[{}]", format_params(items))
+        }
+        DynSolValue::Tuple(items) => format!("({})", format_params(items)),
+        // Struct values of the `eip712` feature are never decoded from a signature.
+        #[allow(unreachable_patterns)]
+        value => hex::encode_prefixed(value.abi_encode()),
+    }
+}
+
+/// Returns the canonical name of an elementary type, e.g. `uint256` for `uint`.
+fn canonical_name(name: &str) -> &str {
+    match name {
+        "uint" => "uint256",
+        "int" => "int256",
+        "suint" => "suint256",
+        "sint" => "sint256",
+        // `tuple(a,b)` is written `(a,b)`.
+        "tuple" => "",
+        name => name,
+    }
+}
+
+/// Returns the public type a shielded elementary type is encoded as.
+fn public_name(name: &str) -> &str {
+    match name.strip_prefix('s') {
+        Some(public @ ("bool" | "address")) => public,
+        Some(public) if public.starts_with("uint") || public.starts_with("int") => public,
+        _ => name,
+    }
+}
+
+fn json_type_name(param: &serde_json::Value) -> Option<String> {
+    let ty = param.get("type")?.as_str()?;
+    match ty.strip_prefix("tuple") {
+        Some(suffix) => {
+            let components = param
+                .get("components")?
+                .as_array()?
+                .iter()
+                .map(json_type_name)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("({}){}", components.join(","), suffix))
+        }
+        None => Some(ty.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolValue;
+    use revm::primitives::Bytes;
+
+    fn parse(types: &str, values: &[&str]) -> Result<Vec<u8>, Errors> {
+        let types = FunctionSignature::parse(&format!("f({types})"))?.inputs;
+        let values = types
+            .iter()
+            .zip(values)
+            .map(|(ty, value)| ty.parse_value(value))
+            .collect::<Result<_, _>>()?;
+        Ok(encode_params(values))
+    }
+
+    fn encode(types: &str, values: &[&str]) -> Vec<u8> {
+        parse(types, values).unwrap()
+    }
+
+    #[test]
+    fn canonical_signatures() {
+        let signature = FunctionSignature::parse("transfer(address,uint)").unwrap();
+        assert_eq!(signature.to_string(), "transfer(address,uint256)");
+        assert_eq!(signature.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+
+        let signature =
+            FunctionSignature::parse("f(suint, sbool, saddress, (int,string)[2][], bytes3)")
+                .unwrap();
+        assert_eq!(
+            signature.to_string(),
+            "f(suint256,sbool,saddress,(int256,string)[2][],bytes3)"
+        );
+        assert_eq!(
+            FunctionSignature::parse("f(tuple(uint,sint8))")
+                .unwrap()
+                .to_string(),
+            "f((uint256,sint8))"
+        );
+        assert!(FunctionSignature::parse("f(sstring)").is_err());
+        assert!(FunctionSignature::parse("f(uint7)").is_err());
+        assert!(FunctionSignature::parse("()").unwrap().inputs.is_empty());
+    }
+
+    #[test]
+    fn encodes_like_sol_types() {
+        let expected = (
+            U256::from(7),
+            "abc".to_string(),
+            vec![U256::from(1), U256::from(2)],
+            vec![
+                (U256::from(1), Bytes::from_static(&[0xff])),
+                (U256::from(2), Bytes::from_static(b"ab")),
+            ],
+        )
+            .abi_encode_params();
+        assert_eq!(
+            encode(
+                "uint,string,uint[],(uint,bytes)[]",
+                &["7", "\"abc\"", "[1, 2]", "[(1, hex\"ff\"), (2, \"ab\")]"]
+            ),
+            expected
+        );
+
+        let expected = ([U256::from(3), U256::from(4)], vec!["x".to_string()]).abi_encode_params();
+        assert_eq!(encode("uint[2],string[]", &["[3, 4]", "[\"x\"]"]), expected);
+    }
+
+    #[test]
+    fn shielded_types_encode_as_public() {
+        assert_eq!(
+            encode("suint,sbool,saddress,sint8", &["5", "true", "0x01", "-1"]),
+            encode("uint,bool,address,int8", &["5", "true", "0x01", "-1"])
+        );
+    }
+
+    #[test]
+    fn rejects_values_not_fitting_their_type() {
+        assert!(parse("uint8", &["255"]).is_ok());
+        assert!(parse("int8", &["-128"]).is_ok());
+        for (ty, value) in [
+            ("uint8", "300"),
+            ("suint8", "256"),
+            ("int8", "-200"),
+            ("int8", "128"),
+            ("uint", "-1"),
+            ("bool", "2"),
+            ("address", "left(0x01)"),
+            ("bytes2", "0x0102"),
+            ("uint[2]", "[1]"),
+            ("string", "5"),
+            ("(uint,bool)", "(1)"),
+        ] {
+            assert!(parse(ty, &[value]).is_err(), "{value} is not a {ty}");
+        }
+    }
+
+    #[test]
+    fn decodes_and_formats() {
+        let types = FunctionSignature::parse("f(int8,string,(bool,bytes)[],bytes2)")
+            .unwrap()
+            .inputs;
+        let data = encode(
+            "int8,string,(bool,bytes)[],bytes2",
+            &["-1", "\"a\\n\"", "[(true, hex\"0102\")]", "left(0xabcd)"],
+        );
+        let values = decode_params(&types, &data).unwrap();
+        assert_eq!(encode_params(values.clone()), data);
+        assert_eq!(
+            format_params(&values),
+            "-1, \"a\\n\", [(true, hex\"0102\")], left(0xabcd)"
+        );
+        assert!(decode_params(&types, &data[..64]).is_err());
+    }
+
+    #[test]
+    fn json_tuple_types() {
+        let param = serde_json::json!({
+            "type": "tuple[]",
+            "components": [{ "type": "suint256" }, { "type": "tuple", "components": [{ "type": "bytes" }] }]
+        });
+        assert_eq!(
+            AbiType::from_json(&param).unwrap().to_string(),
+            "(suint256,(bytes))[]"
+        );
+    }
+}
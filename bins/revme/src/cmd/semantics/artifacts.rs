@@ -0,0 +1,480 @@
+//! Compiled contracts of semantic tests.
+//!
+//! Artifacts are looked up in bundles named after the keccak256 hash of the sources of a test, so
+//! the suite can run without `solc`. Only the part before `// ----` is hashed and compiled, so
+//! editing the expectations of a test keeps its bundle. A bundle holds the contracts of one source for every compile
+//! profile it was built with:
+//!
+//! ```json
+//! {
+//!   "cancun/via-ir": [
+//!     { "source": "test.sol", "name": "C", "abi": [...], "bytecode": "6080...", "runtimeBytecode": "6080..." }
+//!   ]
+//! }
+//! ```
+//!
+//! Contracts are kept in source order. Bytecode is hex without prefix and may hold library link
+//! placeholders `__$<hash>$__`, resolved through `linkReferences` (hash to fully qualified library
+//! name). The cache directory uses the same layout in a `v<schema>-solc-<version>` subdirectory,
+//! so bundles written by another `solc` or before a change of the bundle format are never reused,
+//! and a warm cache subdirectory can be shipped as an artifact directory.
+//!
+//! Sources are written to a temporary directory before compiling: a single source test becomes
+//! one unit named after the test file, and tests made of several `==== Source: <name> ====` units
+//! are compiled together, each unit written to its own file so that imports between them resolve.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+use log::{debug, error};
+use revm::primitives::{hex, keccak256, B256};
+use serde::{Deserialize, Serialize};
+
+use super::{compiler_evm_versions::EVMVersion, Errors};
+
+/// Version of the bundle format, part of the cache key.
+///
+/// Bump it whenever [`ContractArtifact`] changes meaning: version 2 added link references and
+/// version 3 source unit names, version 4 keyed bundles by the compiled sources only.
+const ARTIFACT_SCHEMA_VERSION: u32 = 4;
+
+/// Contract compiled for one profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContractArtifact {
+    /// Source unit the contract is defined in.
+    #[serde(default)]
+    pub source: String,
+    pub name: String,
+    #[serde(default)]
+    pub abi: serde_json::Value,
+    pub bytecode: String,
+    #[serde(default)]
+    pub runtime_bytecode: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub link_references: BTreeMap<String, String>,
+}
+
+/// Contracts of one source, keyed by [`CompileProfile::key`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct ArtifactBundle(pub BTreeMap<String, Vec<ContractArtifact>>);
+
+impl ArtifactBundle {
+    fn load(path: &Path) -> Result<Option<Self>, Errors> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map(Some).map_err(|err| {
+            error!("Invalid artifact bundle {:?}: {}", path, err);
+            Errors::InvalidArtifacts
+        })
+    }
+
+    fn store(&self, path: &Path) -> Result<(), Errors> {
+        let content = serde_json::to_string_pretty(self).map_err(|_| Errors::InvalidArtifacts)?;
+        // Write then rename, so parallel runs never read a partial bundle.
+        let tmp = path.with_extension(format!("json.{}", std::process::id()));
+        fs::write(&tmp, content)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Compiler settings a test source is built with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CompileProfile {
+    pub evm_version: Option<EVMVersion>,
+    pub via_ir: bool,
+    pub eof: bool,
+}
+
+impl CompileProfile {
+    /// Returns the bundle key of the profile, e.g. `default`, `cancun` or `osaka/via-ir/eof`.
+    pub(crate) fn key(&self) -> String {
+        let mut key = self
+            .evm_version
+            .map_or_else(|| "default".to_string(), |v| v.to_string());
+        // via-IR is implied by EOF, see `Compiler::run_solc`.
+        if self.via_ir || self.eof {
+            key.push_str("/via-ir");
+        }
+        if self.eof {
+            key.push_str("/eof");
+        }
+        key
+    }
+}
+
+/// Resolves the artifacts of test sources from bundles, the cache or `solc`.
+#[derive(Debug, Clone)]
+pub(crate) struct Compiler {
+    solc: PathBuf,
+    artifacts: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    /// Version reported by `solc`, `None` if it cannot be run.
+    solc_version: OnceLock<Option<String>>,
+}
+
+impl Compiler {
+    pub(crate) fn new(
+        solc: PathBuf,
+        artifacts: Option<PathBuf>,
+        cache_dir: Option<PathBuf>,
+    ) -> Result<Self, Errors> {
+        if let Some(dir) = &artifacts {
+            if !dir.is_dir() {
+                return Err(Errors::PathNotExists);
+            }
+        }
+        if let Some(dir) = &cache_dir {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(Self {
+            solc,
+            artifacts,
+            cache_dir,
+            solc_version: OnceLock::new(),
+        })
+    }
+
+    /// Returns the cache directory of the current bundle format and `solc`.
+    ///
+    /// `None` without a cache directory, or if `solc` cannot report its version.
+    fn versioned_cache_dir(&self) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        let version = self
+            .solc_version
+            .get_or_init(|| {
+                let output = Command::new(&self.solc).arg("--version").output().ok()?;
+                parse_solc_version(&String::from_utf8_lossy(&output.stdout))
+            })
+            .as_ref()?;
+        Some(dir.join(format!("v{ARTIFACT_SCHEMA_VERSION}-solc-{version}")))
+    }
+
+    /// Returns the contracts of the source in source order.
+    ///
+    /// Artifact bundles are searched first, then the cache. On a miss the source is compiled
+    /// and, if a cache directory is set, the result is added to it.
+    pub(crate) fn compile(
+        &self,
+        path: &str,
+        profile: CompileProfile,
+    ) -> Result<Vec<ContractArtifact>, Errors> {
+        let units = source_units(path, &fs::read_to_string(path)?);
+        let hash = sources_hash(&units);
+        let bundle_name = format!("{}.json", hex::encode(hash));
+        let key = profile.key();
+        let cache_dir = self.versioned_cache_dir();
+
+        for dir in self.artifacts.iter().chain(&cache_dir) {
+            if let Some(mut bundle) = ArtifactBundle::load(&dir.join(&bundle_name))? {
+                if let Some(contracts) = bundle.0.remove(&key) {
+                    debug!("Using artifacts of {:?} from {:?}", path, dir);
+                    return Ok(contracts);
+                }
+            }
+        }
+
+        let contracts = parse_solc_output(&self.run_solc(path, &units, hash, profile)?);
+
+        if let Some(dir) = &cache_dir {
+            fs::create_dir_all(dir)?;
+            let bundle_path = dir.join(&bundle_name);
+            let mut bundle = ArtifactBundle::load(&bundle_path)?.unwrap_or_default();
+            bundle.0.insert(key, contracts.clone());
+            bundle.store(&bundle_path)?;
+        }
+        Ok(contracts)
+    }
+
+    fn run_solc(
+        &self,
+        path: &str,
+        units: &[(String, String)],
+        hash: B256,
+        profile: CompileProfile,
+    ) -> Result<String, Errors> {
+        let mut solc = Command::new(&self.solc);
+
+        solc.arg("--bin").arg("--bin-runtime").arg("--abi");
+
+        let sources_dir = std::env::temp_dir().join(format!(
+            "revme-semantics-{}-{}",
+            std::process::id(),
+            hex::encode(&hash[..8])
+        ));
+        for (name, source) in units {
+            let file = sources_dir.join(name);
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&file, source)?;
+            solc.arg(file);
+        }
+        solc.arg("--base-path").arg(&sources_dir);
+
+        if let Some(v) = profile.evm_version {
+            solc.arg("--evm-version").arg(v.to_string());
+        }
+
+        // via‑IR is required for EOF; keep explicit flag for legacy tests
+        if profile.via_ir || profile.eof {
+            solc.arg("--via-ir");
+        }
+
+        if profile.eof {
+            solc.arg("--experimental-eof-version").arg("1");
+        }
+        // ─── invoke ───────────────────────────────────────────────────────────────
+        let output = solc.output();
+        let _ = fs::remove_dir_all(sources_dir);
+        let output = output.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Errors::CompilerNotFound
+            } else {
+                error!("Compilation failed for file: {:?}", path);
+                Errors::CompilationFailed
+            }
+        })?;
+
+        if !output.status.success() {
+            error!(
+                "Compilation failed for file: {:?}, output: {:?}",
+                path, output
+            );
+            return Err(Errors::CompilationFailed);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Parses the version out of `solc --version`, keeping only characters safe in a path.
+fn parse_solc_version(output: &str) -> Option<String> {
+    let version = output
+        .lines()
+        .find_map(|line| line.strip_prefix("Version:"))?
+        .trim();
+    let version = version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    (!version.is_empty()).then_some(version)
+}
+
+/// Returns the source units of a test as compiled by `solc`, without its expectations.
+///
+/// A single source test is one unit named after the test file.
+fn source_units(path: &str, content: &str) -> Vec<(String, String)> {
+    let section = content.split("// ----").next().unwrap_or_default();
+    let units = split_sources(section);
+    if !units.is_empty() {
+        return units;
+    }
+    let name = Path::new(path).file_name().map_or_else(
+        || "test.sol".into(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    vec![(name, section.to_string())]
+}
+
+/// Returns the bundle hash of source units, covering both their names and contents.
+fn sources_hash(units: &[(String, String)]) -> B256 {
+    let mut preimage = Vec::new();
+    for (name, source) in units {
+        for part in [name, source] {
+            preimage.extend_from_slice(&(part.len() as u64).to_be_bytes());
+            preimage.extend_from_slice(part.as_bytes());
+        }
+    }
+    keccak256(preimage)
+}
+
+/// Splits a test made of `==== Source: <name> ====` units into its sources.
+///
+/// Returns an empty list for single source tests.
+pub(crate) fn split_sources(content: &str) -> Vec<(String, String)> {
+    const MARKER: &str = "==== Source:";
+    let mut units: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        if let Some(name) = line.trim().strip_prefix(MARKER) {
+            let name = name
+                .trim()
+                .trim_end_matches("====")
+                .trim()
+                .trim_matches('"');
+            units.push((name.to_string(), String::new()));
+        } else if let Some((_, source)) = units.last_mut() {
+            source.push_str(line);
+            source.push('\n');
+        }
+    }
+    units
+}
+
+/// Parses the text output of `solc --bin --bin-runtime --abi`.
+///
+/// Contracts without a `Binary:` section are skipped. Link references are read from the
+/// `// $<hash>$ -> <source>:<library>` lines following the binaries.
+fn parse_solc_output(output: &str) -> Vec<ContractArtifact> {
+    let mut contracts = Vec::new();
+
+    for section in output.split("======= ").skip(1) {
+        let mut lines = section.lines();
+        let contract_line = lines.next().unwrap_or("");
+
+        let (source, name) = contract_line
+            .trim_end_matches(" =======")
+            .rsplit_once(':')
+            .unwrap_or(("", "Unknown"));
+
+        let lines: Vec<&str> = lines.collect();
+        let line_after = |header: &str| {
+            lines
+                .iter()
+                .position(|line| line.trim() == header)
+                .map(|index| lines.get(index + 1).copied().unwrap_or("").trim())
+        };
+
+        let Some(bytecode) = line_after("Binary:") else {
+            continue;
+        };
+        let runtime_bytecode = line_after("Binary of the runtime part:").unwrap_or("");
+        let abi = line_after("Contract JSON ABI")
+            .and_then(|abi| serde_json::from_str(abi).ok())
+            .unwrap_or_default();
+        let link_references = lines
+            .iter()
+            .filter_map(|line| {
+                let (hash, library) = line.trim().strip_prefix("// $")?.split_once("$ -> ")?;
+                Some((hash.to_string(), library.trim().to_string()))
+            })
+            .collect();
+
+        contracts.push(ContractArtifact {
+            source: source.to_string(),
+            name: name.to_string(),
+            abi,
+            bytecode: bytecode.to_string(),
+            runtime_bytecode: runtime_bytecode.to_string(),
+            link_references,
+        });
+    }
+    contracts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_solc_sections_in_order() {
+        let output = "\n======= test.sol:A =======\nBinary:\n6001\nBinary of the runtime part:\n6002\nContract JSON ABI\n[{\"type\":\"fallback\"}]\n\n======= test.sol:B =======\nBinary:\n6003\nBinary of the runtime part:\n\nContract JSON ABI\n[]\n";
+        let contracts = parse_solc_output(output);
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(contracts[0].source, "test.sol");
+        assert_eq!(contracts[0].name, "A");
+        assert_eq!(contracts[0].bytecode, "6001");
+        assert_eq!(contracts[0].runtime_bytecode, "6002");
+        assert_eq!(contracts[0].abi[0]["type"], "fallback");
+        assert_eq!(contracts[1].name, "B");
+        assert_eq!(contracts[1].runtime_bytecode, "");
+    }
+
+    #[test]
+    fn parses_link_references() {
+        let output = "======= test.sol:C =======\nBinary:\n73__$8aa64f937099b65a4febc243a5ae69c1e2$__00\n// $8aa64f937099b65a4febc243a5ae69c1e2$ -> test.sol:L\nBinary of the runtime part:\n00\n";
+        let contracts = parse_solc_output(output);
+        assert_eq!(
+            contracts[0]
+                .link_references
+                .get("8aa64f937099b65a4febc243a5ae69c1e2"),
+            Some(&"test.sol:L".to_string())
+        );
+    }
+
+    #[test]
+    fn splits_source_units() {
+        let content = "==== Source: a.sol ====\ncontract A {}\n==== Source: \"b\" ====\nimport \"a.sol\";\ncontract B is A {}\n// ----\n// f() -> 1\n";
+        let units = split_sources(content);
+        assert_eq!(units.len(), 2);
+        assert_eq!(
+            units[0],
+            ("a.sol".to_string(), "contract A {}\n".to_string())
+        );
+        assert_eq!(units[1].0, "b");
+        assert!(units[1].1.starts_with("import \"a.sol\";\n"));
+        assert!(split_sources("contract A {}").is_empty());
+    }
+
+    #[test]
+    fn source_units_exclude_expectations() {
+        let units = source_units("tests/a/test.sol", "contract C {}\n// ----\n// f() -> 1\n");
+        assert_eq!(
+            units,
+            vec![("test.sol".to_string(), "contract C {}\n".to_string())]
+        );
+        let multi = source_units(
+            "tests/a/multi.sol",
+            "==== Source: a.sol ====\ncontract A {}\n// ----\n// f() -> 1\n",
+        );
+        assert_eq!(
+            multi,
+            vec![("a.sol".to_string(), "contract A {}\n".to_string())]
+        );
+        assert_eq!(
+            sources_hash(&units),
+            sources_hash(&source_units(
+                "other/test.sol",
+                "contract C {}\n// ----\n// f() -> 2\n"
+            ))
+        );
+        assert_ne!(sources_hash(&units), sources_hash(&multi));
+    }
+
+    #[test]
+    fn parses_solc_version() {
+        let output = "solc, the solidity compiler commandline interface\nVersion: 0.8.28-develop.2025.1.1+commit.abc123.Linux.g++\n";
+        assert_eq!(
+            parse_solc_version(output).as_deref(),
+            Some("0.8.28-develop.2025.1.1+commit.abc123.Linux.g++")
+        );
+        assert_eq!(
+            parse_solc_version("Version: 0.8/x y"),
+            Some("0.8_x_y".into())
+        );
+        assert_eq!(parse_solc_version("error"), None);
+    }
+
+    #[test]
+    fn profile_keys() {
+        let profile = CompileProfile {
+            evm_version: Some(EVMVersion::Cancun),
+            via_ir: false,
+            eof: false,
+        };
+        assert_eq!(profile.key(), "cancun");
+        assert_eq!(
+            CompileProfile {
+                evm_version: None,
+                via_ir: false,
+                eof: true
+            }
+            .key(),
+            "default/via-ir/eof"
+        );
+    }
+}
@@ -1,22 +1,31 @@
-use std::io::Error as IoError;
+use crate::prestate::PrestateError;
+use std::{io::Error as IoError, path::PathBuf};
 #[derive(Debug, thiserror::Error)]
 pub enum Errors {
     #[error("The specified path does not exist")]
     PathNotExists,
+    #[error("Test path {} does not exist", .0.display())]
+    TestPathNotFound(PathBuf),
     #[error("Invalid bytecode")]
     InvalidBytecode,
     #[error("Invalid input")]
     InvalidInput,
+    #[error("Output Mismatch")]
+    OutputMismatch,
     #[error("Log Mismatch")]
     LogMismatch,
     #[error("Balance Mismatch")]
     BalanceMismatch,
     #[error("Storage Mismatch")]
     StorageMismatch,
+    #[error("Gas Mismatch")]
+    GasMismatch,
     #[error("EVM Error")]
     EVMError,
     #[error(transparent)]
     Io(#[from] IoError),
+    #[error(transparent)]
+    Prestate(#[from] PrestateError),
     #[error("Invalid Test Format")]
     InvalidTestFormat,
     #[error("Unhandled Test Format: === Source:")]
@@ -27,10 +36,18 @@ pub enum Errors {
     InvalidTestOutput,
     #[error("Invalid Argument Format")]
     InvalidArgumentFormat,
+    #[error("Value {0} is not a {1}")]
+    ValueTypeMismatch(String, String),
     #[error("Invalid Argument Count given Function Signature")]
     InvalidArgumentCount,
     #[error("Compilation Failed")]
     CompilationFailed,
     #[error("Compiler Not Found, Download Solc")]
     CompilerNotFound,
+    #[error("Invalid Artifact Bundle")]
+    InvalidArtifacts,
+    #[error("Unlinked Library")]
+    UnlinkedLibrary,
+    #[error("Library Dependency Cycle")]
+    LibraryCycle,
 }
@@ -1,16 +1,21 @@
 use context::result::{ExecutionResult, Output};
-use log::{debug, error};
+use log::{debug, error, warn};
 use primitives::hex::FromHex;
 use revm::{
     database::{CacheDB, EmptyDB},
     inspector::inspectors::TracerEip3155,
-    primitives::{Address, Bytes, FixedBytes, Log, TxKind, U256},
+    primitives::{Address, Bytes, FixedBytes, HashMap, Log, TxKind, U256},
     Context, DatabaseCommit, DatabaseRef, ExecuteEvm, InspectEvm, MainBuilder, MainContext,
 };
 use seismic_revm::{DefaultSeismic, SeismicBuilder};
 use std::str::FromStr;
 
-use crate::cmd::semantics::{test_cases::TestStep, utils::verify_emitted_events};
+use crate::cmd::semantics::{
+    gas::{ExpectedGas, GasCheck},
+    semantic_tests::ContractInfo,
+    test_cases::TestStep,
+    utils::verify_emitted_events,
+};
 
 use super::{
     compiler_evm_versions::EVMVersion,
@@ -100,25 +105,48 @@ pub(crate) struct EvmExecutor {
     db: CacheDB<EmptyDB>,
     pub config: EvmConfig,
     evm_version: EVMVersion,
-    libraries: Vec<Address>,
+    /// Contracts of the test file, libraries are deployed from them on demand.
+    contracts: Vec<ContractInfo>,
+    /// Deployed libraries by `<source>:<library>` name.
+    libraries: HashMap<String, Address>,
+    gas_mode: &'static str,
+    /// Gas comparisons of the steps run so far.
+    pub gas_checks: Vec<GasCheck>,
 }
 
 impl EvmExecutor {
-    pub(crate) fn new(db: CacheDB<EmptyDB>, config: EvmConfig, evm_version: EVMVersion) -> Self {
+    pub(crate) fn new(
+        db: CacheDB<EmptyDB>,
+        config: EvmConfig,
+        evm_version: EVMVersion,
+        gas_mode: &'static str,
+    ) -> Self {
         Self {
             db,
             config,
             evm_version,
-            libraries: Vec::new(),
+            contracts: Vec::new(),
+            libraries: HashMap::default(),
+            gas_mode,
+            gas_checks: Vec::new(),
         }
     }
 
+    pub(crate) fn with_contracts(mut self, contracts: Vec<ContractInfo>) -> Self {
+        self.contracts = contracts;
+        self
+    }
+
+    pub(crate) fn db(&self) -> &CacheDB<EmptyDB> {
+        &self.db
+    }
+
     pub(crate) fn deploy_contract(
         &mut self,
         deploy_data: Bytes,
         trace: bool,
         value: U256,
-    ) -> Result<(Address, Vec<Log>), Errors> {
+    ) -> Result<(Address, Vec<Log>, u64), Errors> {
         let nonce = self
             .db
             .basic_ref(self.config.caller)
@@ -195,6 +223,7 @@ impl EvmExecutor {
             mainnet_to_seismic(raw)
         };
 
+        let gas_used = deploy_out.result.gas_used();
         let (contract_address, logs) = match deploy_out.clone().result {
             ExecutionResult::Success { output, logs, .. } => match output {
                 Output::Create(_, Some(addr)) => (addr, logs),
@@ -221,7 +250,7 @@ impl EvmExecutor {
         };
 
         self.db.commit(deploy_out.state);
-        Ok((contract_address, logs))
+        Ok((contract_address, logs, gas_used))
     }
 
     pub(crate) fn execute_function_call(
@@ -232,7 +261,7 @@ impl EvmExecutor {
         trace: bool,
         test_file: &str,
         value: U256,
-    ) -> Result<Vec<Log>, Errors> {
+    ) -> Result<(Vec<Log>, u64), Errors> {
         let nonce = self
             .db
             .basic_ref(self.config.caller)
@@ -387,12 +416,21 @@ impl EvmExecutor {
             mainnet_to_seismic(raw)
         };
 
+        let gas_used = out.result.gas_used();
         let logs = match out.clone().result {
             ExecutionResult::Success { output, logs, .. } => {
                 if expected_outputs.is_success() {
                     match output {
                         Output::Call(out) => {
-                            assert_eq!(out, expected_outputs.output);
+                            if out != expected_outputs.output {
+                                error!(
+                                    "Output mismatch: expected {}, got {}, for file: {:?}",
+                                    expected_outputs.describe(&expected_outputs.output),
+                                    expected_outputs.describe(&out),
+                                    test_file
+                                );
+                                return Err(Errors::OutputMismatch);
+                            }
                         }
                         _ => return Err(Errors::EVMError),
                     }
@@ -408,20 +446,22 @@ impl EvmExecutor {
             }
             ExecutionResult::Revert { output, .. } => {
                 if !expected_outputs.is_success() {
-                    return Ok(vec![]);
+                    return Ok((vec![], gas_used));
                 } else {
                     error!(
                         "Reverted with output: {:?} for file {:?}",
                         output.to_string(),
                         test_file
                     );
-                    assert_eq!(output, expected_outputs.output);
+                    if output != expected_outputs.output {
+                        return Err(Errors::OutputMismatch);
+                    }
                     vec![]
                 }
             }
             ExecutionResult::Halt { reason, .. } => {
                 if !expected_outputs.is_success() {
-                    return Ok(vec![]);
+                    return Ok((vec![], gas_used));
                 } else {
                     error!("Execution halted: {:?} for file {:?}", reason, test_file);
                     return Err(Errors::EVMError);
@@ -430,7 +470,35 @@ impl EvmExecutor {
         };
 
         self.db.commit(out.state);
-        Ok(logs)
+        Ok((logs, gas_used))
+    }
+
+    /// Compares the gas used by a step against its expectations, logging mismatches.
+    fn check_gas(&mut self, expected_gas: &ExpectedGas, gas_used: u64, test_file: &str) {
+        let Some(check) = expected_gas.check(self.gas_mode, gas_used) else {
+            return;
+        };
+        if !check.is_match() {
+            match check.delta() {
+                Some(delta) => warn!(
+                    "Gas mismatch at {}:{}: expected gas {} {}, got {} ({:+})",
+                    test_file,
+                    check.line.map_or(0, |line| line + 1),
+                    check.mode,
+                    check.expected.unwrap_or_default(),
+                    check.actual,
+                    delta
+                ),
+                None => warn!(
+                    "Missing gas {} expectation at {}:{}, got {}",
+                    check.mode,
+                    test_file,
+                    check.insert_after + 1,
+                    check.actual
+                ),
+            }
+        }
+        self.gas_checks.push(check);
     }
 
     pub(crate) fn copy_contract_to_env(&mut self, contract_address: Address) {
@@ -454,6 +522,48 @@ impl EvmExecutor {
         }
     }
 
+    /// Deploys the libraries the contract links against that are not deployed yet,
+    /// dependencies first.
+    ///
+    /// `linking` holds the libraries being linked, to detect dependency cycles.
+    fn link_libraries(
+        &mut self,
+        contract: &ContractInfo,
+        trace: bool,
+        linking: &mut Vec<String>,
+    ) -> Result<(), Errors> {
+        for library in contract.linked_libraries() {
+            if self.libraries.contains_key(library) {
+                continue;
+            }
+            if linking.iter().any(|name| name == library) {
+                error!("Library dependency cycle: {:?} -> {}", linking, library);
+                return Err(Errors::LibraryCycle);
+            }
+            let Some(info) = self
+                .contracts
+                .iter()
+                .find(|info| info.qualified_name() == library)
+                .cloned()
+            else {
+                error!(
+                    "Library {} of {} not found",
+                    library, contract.contract_name
+                );
+                return Err(Errors::UnlinkedLibrary);
+            };
+
+            linking.push(library.to_string());
+            self.link_libraries(&info, trace, linking)?;
+            linking.pop();
+
+            let code = info.get_deployable_code(&self.libraries)?;
+            let (address, _, _) = self.deploy_contract(code, trace, U256::ZERO)?;
+            self.libraries.insert(library.to_string(), address);
+        }
+        Ok(())
+    }
+
     pub(crate) fn run_test_case(
         &mut self,
         test_case: &TestCase,
@@ -467,31 +577,19 @@ impl EvmExecutor {
                     contract,
                     value,
                     expected_events,
+                    expected_gas,
                 } => {
+                    self.link_libraries(contract, trace, &mut Vec::new())?;
+                    let code = contract.get_deployable_code(&self.libraries)?;
                     if contract.is_library {
-                        let (address, _) = self.deploy_contract(
-                            contract.clone().get_deployable_code(None),
-                            trace,
-                            *value,
-                        )?;
-                        self.libraries.push(address);
+                        let (address, _, gas_used) = self.deploy_contract(code, trace, *value)?;
+                        self.check_gas(expected_gas, gas_used, test_file);
+                        self.libraries.insert(contract.qualified_name(), address);
                     } else {
-                        let (contract_address, logs) = if !self.libraries.is_empty() {
-                            self.deploy_contract(
-                                contract
-                                    .clone()
-                                    .get_deployable_code(Some(*self.libraries.first().unwrap())),
-                                trace,
-                                *value,
-                            )?
-                        } else {
-                            self.deploy_contract(
-                                contract.clone().get_deployable_code(None),
-                                trace,
-                                *value,
-                            )?
-                        };
+                        let (contract_address, logs, gas_used) =
+                            self.deploy_contract(code, trace, *value)?;
                         verify_emitted_events(expected_events, &logs)?;
+                        self.check_gas(expected_gas, gas_used, test_file);
                         self.copy_contract_to_env(contract_address);
                     }
                 }
@@ -501,8 +599,9 @@ impl EvmExecutor {
                     expected_outputs,
                     value,
                     expected_events,
+                    expected_gas,
                 } => {
-                    let logs = self.execute_function_call(
+                    let (logs, gas_used) = self.execute_function_call(
                         function_name,
                         input_data,
                         expected_outputs,
@@ -511,6 +610,7 @@ impl EvmExecutor {
                         *value,
                     )?;
                     verify_emitted_events(expected_events, &logs)?;
+                    self.check_gas(expected_gas, gas_used, test_file);
                 }
                 TestStep::CheckStorageEmpty { expected_empty } => {
                     verify_storage_empty(
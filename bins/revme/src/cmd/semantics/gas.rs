@@ -0,0 +1,208 @@
+//! Gas expectations of semantic tests.
+//!
+//! isoltest records the gas of a call or deployment on lines following it:
+//!
+//! ```text
+//! // f() -> 1
+//! // gas irOptimized: 102363
+//! // gas legacy: 104211
+//! // gas legacy code: 37200
+//! ```
+//!
+//! The `code` lines hold the code deposit cost of a deployment, which is part of the
+//! transaction gas. Only the modes matching how the runner compiles are compared: `ir` or
+//! `legacy`, since the optimizer is never enabled.
+use std::fs;
+
+use super::Errors;
+
+/// Returns the isoltest gas mode of unoptimized sources.
+pub(crate) fn gas_mode(via_ir: bool) -> &'static str {
+    if via_ir {
+        "ir"
+    } else {
+        "legacy"
+    }
+}
+
+/// One `gas <mode>[ code]: <gas>` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GasExpectation {
+    pub mode: String,
+    pub code: bool,
+    pub gas: u64,
+    /// Zero-based line of the test file.
+    pub line: usize,
+}
+
+impl GasExpectation {
+    /// Parses an expectation line with the comment marker already stripped.
+    pub(crate) fn parse(line: &str, line_number: usize) -> Option<Self> {
+        let (key, gas) = line.strip_prefix("gas ")?.split_once(':')?;
+        let gas = gas.trim().parse().ok()?;
+        let (mode, code) = match key.trim().strip_suffix(" code") {
+            Some(mode) => (mode, true),
+            None => (key.trim(), false),
+        };
+        Some(Self {
+            mode: mode.to_string(),
+            code,
+            gas,
+            line: line_number,
+        })
+    }
+}
+
+/// Gas expectations of one transaction step.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExpectedGas {
+    /// Last line belonging to the step, new expectations are inserted after it.
+    pub last_line: usize,
+    pub expectations: Vec<GasExpectation>,
+}
+
+impl ExpectedGas {
+    pub(crate) fn new(line: usize) -> Self {
+        Self {
+            last_line: line,
+            expectations: Vec::new(),
+        }
+    }
+
+    /// Compares the gas used by the step against the expectations of `mode`.
+    ///
+    /// Returns `None` if the step has no gas expectations at all.
+    pub(crate) fn check(&self, mode: &str, gas_used: u64) -> Option<GasCheck> {
+        if self.expectations.is_empty() {
+            return None;
+        }
+        let find = |code: bool| {
+            self.expectations
+                .iter()
+                .find(|expectation| expectation.mode == mode && expectation.code == code)
+        };
+        let base = find(false);
+        let code = find(true).map_or(0, |expectation| expectation.gas);
+        Some(GasCheck {
+            mode: mode.to_string(),
+            expected: base.map(|expectation| expectation.gas + code),
+            actual: gas_used,
+            code,
+            line: base.map(|expectation| expectation.line),
+            insert_after: self.last_line,
+        })
+    }
+}
+
+/// Result of comparing the gas of one step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GasCheck {
+    pub mode: String,
+    /// Expected transaction gas, including the code deposit.
+    pub expected: Option<u64>,
+    pub actual: u64,
+    /// Expected code deposit, kept as is when rewriting.
+    pub code: u64,
+    /// Line of the expectation, `None` if the mode has none.
+    pub line: Option<usize>,
+    pub insert_after: usize,
+}
+
+impl GasCheck {
+    pub(crate) fn is_match(&self) -> bool {
+        self.expected == Some(self.actual)
+    }
+
+    /// Returns the gas used minus the expected gas.
+    pub(crate) fn delta(&self) -> Option<i128> {
+        self.expected
+            .map(|expected| self.actual as i128 - expected as i128)
+    }
+
+    fn expectation_line(&self) -> String {
+        format!(
+            "// gas {}: {}",
+            self.mode,
+            self.actual.saturating_sub(self.code)
+        )
+    }
+}
+
+/// Rewrites the gas expectations of a test file with the gas actually used.
+///
+/// Existing lines of the mode are updated in place, steps lacking the mode get a new line
+/// after their last one.
+pub(crate) fn rewrite_expectations(path: &str, checks: &[GasCheck]) -> Result<(), Errors> {
+    let content = fs::read_to_string(path)?;
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    let mut inserts = Vec::new();
+    for check in checks.iter().filter(|check| !check.is_match()) {
+        match check.line {
+            Some(line) => {
+                let Some(old) = lines.get_mut(line) else {
+                    return Err(Errors::InvalidTestFormat);
+                };
+                let indent = &old[..old.len() - old.trim_start().len()];
+                *old = format!("{indent}{}", check.expectation_line());
+            }
+            None => inserts.push((check.insert_after, check.expectation_line())),
+        }
+    }
+    // Insert bottom up so earlier line numbers stay valid.
+    inserts.sort_by(|a, b| b.0.cmp(&a.0));
+    for (after, line) in inserts {
+        if after >= lines.len() {
+            return Err(Errors::InvalidTestFormat);
+        }
+        lines.insert(after + 1, line);
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gas_lines() {
+        assert_eq!(
+            GasExpectation::parse("gas legacy: 104211", 3),
+            Some(GasExpectation {
+                mode: "legacy".to_string(),
+                code: false,
+                gas: 104211,
+                line: 3
+            })
+        );
+        let code = GasExpectation::parse("gas irOptimized code: 37200", 4).unwrap();
+        assert_eq!(code.mode, "irOptimized");
+        assert!(code.code);
+        assert_eq!(GasExpectation::parse("gasleft() -> 1", 5), None);
+    }
+
+    #[test]
+    fn check_includes_code_deposit() {
+        let mut expected = ExpectedGas::new(1);
+        expected.expectations = vec![
+            GasExpectation::parse("gas legacy: 100", 2).unwrap(),
+            GasExpectation::parse("gas legacy code: 50", 3).unwrap(),
+            GasExpectation::parse("gas ir: 90", 4).unwrap(),
+        ];
+        expected.last_line = 4;
+
+        let check = expected.check("legacy", 160).unwrap();
+        assert_eq!(check.expected, Some(150));
+        assert_eq!(check.delta(), Some(10));
+        assert_eq!(check.line, Some(2));
+        assert_eq!(check.expectation_line(), "// gas legacy: 110");
+
+        assert!(expected.check("ir", 90).unwrap().is_match());
+        assert_eq!(expected.check("eof", 90).unwrap().line, None);
+        assert_eq!(ExpectedGas::new(1).check("legacy", 1), None);
+    }
+}
@@ -1,219 +1,139 @@
 use std::str::FromStr;
 
 use super::{
+    abi::{encode_params, AbiType},
     errors::Errors,
-    utils::{count_used_bytes_right, parse_string_with_escapes},
+    utils::parse_string_with_escapes,
 };
-use hex::FromHex;
-use log::error;
-use revm::primitives::{hex, keccak256, Bytes, FixedBytes, I256, U256};
+use revm::primitives::{hex, Bytes, B256, I256, U256};
 
 pub struct Parser {}
 
 impl Parser {
-    pub(crate) fn parse_function_signature(
-        signature: &str,
-    ) -> Result<(Vec<u8>, Vec<String>), Errors> {
-        if let Some(start_idx) = signature.find('(') {
-            if let Some(end_idx) = signature.rfind(')') {
-                let function_name = &signature[..start_idx];
-                let params_str = &signature[start_idx + 1..end_idx];
-                let parameter_types = if params_str.is_empty() {
-                    Vec::new()
-                } else {
-                    params_str
-                        .split(',')
-                        .map(|s| s.trim().to_string())
-                        .collect()
-                };
-
-                // Map 'uint' and 'int' to 'uint256' and 'int256' in the parameter types
-                let mapped_parameter_types: Vec<String> = parameter_types
-                    .iter()
-                    .map(|param| match param.as_str() {
-                        "uint" => "uint256".to_string(),
-                        "int" => "int256".to_string(),
-                        _ => param.clone(),
-                    })
-                    .collect();
+    /// Encodes the arguments of a call, or the expected return data of a function, for the
+    /// given parameter types.
+    ///
+    /// Values matching the types one to one are ABI encoded, so dynamic values may be written
+    /// as `"abc"`, `[1, 2]` or `(1, hex"ff")`, and a value that does not fit its type is an
+    /// error. Lines with another number of values spell out the raw words of the encoding, as
+    /// isoltest allows, e.g. `f(string): 0x20, 3, "abc"`.
+    pub(crate) fn encode_values(types: &[AbiType], values: &[&str]) -> Result<Bytes, Errors> {
+        if values.len() == types.len() {
+            let values = types
+                .iter()
+                .zip(values)
+                .map(|(ty, value)| ty.parse_value(value))
+                .collect::<Result<_, _>>()?;
+            return Ok(encode_params(values).into());
+        }
+
+        let mut encoded = Vec::new();
+        for value in values {
+            encoded.extend(Self::parse_raw(value)?);
+        }
+        Ok(encoded.into())
+    }
 
-                // Reconstruct the signature with mapped parameter types
-                let new_signature =
-                    format!("{}({})", function_name, mapped_parameter_types.join(","));
-                // Compute the function selector using the modified signature
-                Ok((
-                    keccak256(new_signature.as_bytes()).0[0..4].to_vec(),
-                    mapped_parameter_types,
-                ))
+    /// Parses a raw isoltest literal: `hex"..."` and `"..."` into their bytes, padded to words
+    /// for strings, and any other literal into a word.
+    pub(crate) fn parse_raw(arg: &str) -> Result<Vec<u8>, Errors> {
+        let arg = arg.trim();
+        if let Some(inner) = arg.strip_prefix("hex\"").and_then(|a| a.strip_suffix('"')) {
+            return hex::decode(inner).map_err(|_| Errors::InvalidArgumentFormat);
+        }
+        if let Some(inner) = arg.strip_prefix('"').and_then(|a| a.strip_suffix('"')) {
+            let mut bytes = parse_string_with_escapes(inner)?;
+            if bytes.is_empty() {
+                // An empty string stands for its encoding, an offset and a zero length.
+                bytes.extend(U256::from(32).to_be_bytes::<32>());
+                bytes.extend([0; 32]);
             } else {
-                Err(Errors::InvalidFunctionSignature)
+                bytes.resize(bytes.len().div_ceil(32) * 32, 0);
             }
-        } else {
-            Err(Errors::InvalidFunctionSignature)
+            return Ok(bytes);
         }
+        Self::parse_word(arg).map(|word| word.to_vec())
     }
 
-    pub(crate) fn parse_arg(arg: &str) -> Result<Bytes, Errors> {
+    /// Parses a scalar literal into a word.
+    ///
+    /// Numbers are decimal or `0x` hex, negative numbers are two's complement. `left(x)` aligns
+    /// the significant bytes of `x` to the left of the word, `right(x)` is `x`.
+    pub(crate) fn parse_word(arg: &str) -> Result<B256, Errors> {
         let arg = arg.trim();
-
-        if let Some(bytes) = Self::parse_left(arg)? {
-            return Ok(bytes);
-        }
-
-        if let Some(bytes) = Self::parse_right(arg)? {
-            return Ok(bytes);
-        }
-
-        if let Some(bytes) = Self::parse_bool(arg) {
-            return Ok(bytes);
-        }
-
-        if let Some(bytes) = Self::parse_signed_int(arg) {
-            return Ok(bytes);
-        }
-
-        if let Some(bytes) = Self::parse_unsigned_int(arg) {
-            return Ok(bytes);
-        }
-
-        if let Some(bytes) = Self::parse_hex(arg) {
-            return Ok(bytes);
-        }
-
-        if let Some(bytes) = Self::parse_string(arg) {
-            return Ok(bytes);
+        if let Some(inner) = arg.strip_prefix("left(").and_then(|a| a.strip_suffix(')')) {
+            let bytes = match inner.trim().strip_prefix("0x") {
+                Some(digits) => hex::decode(digits).map_err(|_| Errors::InvalidArgumentFormat)?,
+                None => {
+                    let word = Self::parse_word(inner)?;
+                    let start = word.iter().position(|b| *b != 0).unwrap_or(32);
+                    word[start..].to_vec()
+                }
+            };
+            if bytes.len() > 32 {
+                return Err(Errors::InvalidArgumentFormat);
+            }
+            return Ok(B256::right_padding_from(&bytes));
         }
-
-        if let Some(bytes) = Self::parse_raw_hex(arg) {
-            return Ok(bytes);
+        if let Some(inner) = arg.strip_prefix("right(").and_then(|a| a.strip_suffix(')')) {
+            return Self::parse_word(inner);
         }
-
-        Err(Errors::InvalidArgumentFormat)
-    }
-
-    pub(crate) fn parse_bool(arg: &str) -> Option<Bytes> {
         match arg {
-            "true" => Some(Bytes::from({
-                let mut buf = [0u8; 32];
-                buf[31] = 1;
-                buf.to_vec()
-            })),
-            "false" => Some(Bytes::from({
-                let mut buf = [0u8; 32];
-                buf[31] = 0;
-                buf.to_vec()
-            })),
-            _ => None,
+            "true" => return Ok(B256::with_last_byte(1)),
+            "false" => return Ok(B256::ZERO),
+            _ => {}
         }
-    }
-
-    pub(crate) fn parse_signed_int(arg: &str) -> Option<Bytes> {
         if arg.starts_with('-') {
-            I256::from_str(arg).ok().map(|num| {
-                let num_bytes = num.to_be_bytes::<32>();
-                Bytes::from(num_bytes.to_vec())
-            })
-        } else {
-            None
-        }
-    }
-
-    pub(crate) fn parse_unsigned_int(arg: &str) -> Option<Bytes> {
-        U256::from_str(arg).ok().map(|num| {
-            let num_bytes = num.to_be_bytes::<32>();
-            Bytes::from(num_bytes.to_vec())
-        })
-    }
-
-    pub(crate) fn parse_raw_hex(arg: &str) -> Option<Bytes> {
-        if arg.starts_with("0x") {
-            let hex_str = arg.trim_start_matches("0x");
-            let hex_str = if hex_str.len() % 2 != 0 {
-                // Odd length, prepend a '0'
-                format!("0{}", hex_str)
-            } else {
-                hex_str.to_string()
-            };
-
-            hex::decode(&hex_str)
-                .ok()
-                .as_deref()
-                .map(FixedBytes::<32>::left_padding_from)
-                .map(Bytes::from)
-        } else {
-            None
-        }
-    }
-
-    pub(crate) fn parse_hex(arg: &str) -> Option<Bytes> {
-        if arg.starts_with("hex\"") {
-            let inner = &arg[4..arg.len() - 1];
-            match hex::decode(inner) {
-                Ok(decoded) => Some(Bytes::from(decoded)),
-                Err(e) => {
-                    error!("Failed to decode hex: {:?}", e);
-                    None
-                }
-            }
-        } else {
-            None
-        }
-    }
-
-    pub(crate) fn parse_string(arg: &str) -> Option<Bytes> {
-        if arg.starts_with('"') && arg.ends_with('"') {
-            let inner = &arg[1..arg.len() - 1];
-            match parse_string_with_escapes(inner) {
-                Ok(string_bytes) => {
-                    let mut encoded = Vec::new();
-                    if string_bytes.is_empty() {
-                        encoded.extend_from_slice(U256::from(32).to_be_bytes::<32>().as_ref());
-                        encoded.extend_from_slice(U256::ZERO.to_be_bytes::<32>().as_ref());
-                    } else {
-                        let mut data_bytes = string_bytes.to_vec();
-                        let padding_length = (32 - (data_bytes.len() % 32)) % 32;
-                        data_bytes.extend(vec![0u8; padding_length]);
-                        encoded.extend_from_slice(&data_bytes);
-                    }
-                    Some(Bytes::from(encoded))
-                }
-                Err(_) => None,
-            }
-        } else {
-            None
+            return I256::from_str(arg)
+                .map(|value| B256::from(value.to_be_bytes::<32>()))
+                .map_err(|_| Errors::InvalidArgumentFormat);
         }
+        U256::from_str(arg)
+            .map(B256::from)
+            .map_err(|_| Errors::InvalidArgumentFormat)
     }
+}
 
-    pub(crate) fn parse_left(arg: &str) -> Result<Option<Bytes>, Errors> {
-        if arg.starts_with("left(") && arg.ends_with(')') {
-            let inner = &arg[5..arg.len() - 1];
-            if inner.starts_with("0x") {
-                let parsed_output = Bytes::from_hex(inner).unwrap();
-                let padded_output: Bytes =
-                    FixedBytes::<32>::right_padding_from(parsed_output.as_ref()).into();
-                return Ok(Some(padded_output));
-            }
-            let inner_bytes = Self::parse_arg(inner)?;
-            let used_length = count_used_bytes_right(&inner_bytes);
-            if used_length == 0 {
-                return Ok(Some(Bytes::from(vec![0u8; 32])));
-            }
-            let first_non_zero = 32 - used_length;
-            let used_bytes = &inner_bytes[first_non_zero..];
-            let mut output = vec![0u8; 32];
-            output[..used_bytes.len()].copy_from_slice(used_bytes);
-            Ok(Some(Bytes::from(output)))
-        } else {
-            Ok(None)
-        }
+#[cfg(test)]
+mod tests {
+    use super::super::abi::FunctionSignature;
+    use super::*;
+
+    #[test]
+    fn parses_words() {
+        assert_eq!(Parser::parse_word("1").unwrap(), B256::with_last_byte(1));
+        assert_eq!(
+            Parser::parse_word("0x0102").unwrap(),
+            B256::left_padding_from(&[1, 2])
+        );
+        assert_eq!(Parser::parse_word("-1").unwrap(), B256::repeat_byte(0xff));
+        assert_eq!(
+            Parser::parse_word("left(0x0102)").unwrap(),
+            B256::right_padding_from(&[1, 2])
+        );
+        assert_eq!(
+            Parser::parse_word("left(258)").unwrap(),
+            B256::right_padding_from(&[1, 2])
+        );
+        assert_eq!(
+            Parser::parse_word("right(true)").unwrap(),
+            B256::with_last_byte(1)
+        );
+        assert!(Parser::parse_word(&format!("left(0x{})", "00".repeat(33))).is_err());
+        assert!(Parser::parse_word("abc").is_err());
     }
 
-    pub(crate) fn parse_right(arg: &str) -> Result<Option<Bytes>, Errors> {
-        if arg.starts_with("right(") && arg.ends_with(')') {
-            let inner = &arg[6..arg.len() - 1];
-            Self::parse_arg(inner).map(Some)
-        } else {
-            Ok(None)
-        }
+    #[test]
+    fn type_errors_are_not_taken_as_raw_words() {
+        let types = FunctionSignature::parse("f(uint8,string)").unwrap().inputs;
+        assert!(matches!(
+            Parser::encode_values(&types, &["300", "\"a\""]),
+            Err(Errors::ValueTypeMismatch(..))
+        ));
+
+        // Isoltest spelling out the words of `f(string)`.
+        let types = FunctionSignature::parse("f(string)").unwrap().inputs;
+        let raw = Parser::encode_values(&types, &["0x20", "3", "\"abc\""]).unwrap();
+        assert_eq!(raw, Parser::encode_values(&types, &["\"abc\""]).unwrap());
     }
 }
@@ -0,0 +1,316 @@
+//! Machine-readable results of a semantic test run, as JSON or JUnit XML.
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+use serde::Serialize;
+
+use super::Errors;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Outcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Pass, fail and skip counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct Counts {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+impl Counts {
+    fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Failed => self.failed += 1,
+            Outcome::Skipped => self.skipped += 1,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.passed + self.failed + self.skipped
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct TestCaseReport {
+    pub name: String,
+    pub outcome: Outcome,
+    /// Failure or skip reason.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub elapsed_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct FileReport {
+    pub path: String,
+    pub outcome: Outcome,
+    /// Reason the file failed or was skipped before running its test cases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub counts: Counts,
+    pub elapsed_secs: f64,
+    pub test_cases: Vec<TestCaseReport>,
+}
+
+impl FileReport {
+    pub(crate) fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            outcome: Outcome::Passed,
+            reason: None,
+            counts: Counts::default(),
+            elapsed_secs: 0.0,
+            test_cases: Vec::new(),
+        }
+    }
+
+    /// Report of a file that was not run because a previous file failed.
+    pub(crate) fn not_run(path: &str) -> Self {
+        Self {
+            outcome: Outcome::Skipped,
+            reason: Some("Previous test file failed".to_string()),
+            ..Self::new(path)
+        }
+    }
+
+    /// Marks the whole file as failed or skipped without running its test cases.
+    pub(crate) fn set_outcome(&mut self, outcome: Outcome, reason: &Errors) {
+        self.outcome = outcome;
+        self.reason = Some(reason.to_string());
+    }
+
+    pub(crate) fn add_test_case(
+        &mut self,
+        name: String,
+        result: Option<&Result<(), Errors>>,
+        elapsed: Duration,
+    ) {
+        let (outcome, reason) = match result {
+            Some(Ok(())) => (Outcome::Passed, None),
+            Some(Err(err)) => (Outcome::Failed, Some(err.to_string())),
+            None => (
+                Outcome::Skipped,
+                Some("Previous test case failed".to_string()),
+            ),
+        };
+        if outcome == Outcome::Failed {
+            self.outcome = Outcome::Failed;
+        }
+        self.counts.add(outcome);
+        self.test_cases.push(TestCaseReport {
+            name,
+            outcome,
+            reason,
+            elapsed_secs: elapsed.as_secs_f64(),
+        });
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct Report {
+    /// Outcomes of the test files.
+    pub files: Counts,
+    /// Outcomes of the test cases of all files.
+    pub test_cases: Counts,
+    pub elapsed_secs: f64,
+    pub results: Vec<FileReport>,
+}
+
+impl Report {
+    pub(crate) fn new(mut results: Vec<FileReport>, elapsed: Duration) -> Self {
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut files = Counts::default();
+        let mut test_cases = Counts::default();
+        for result in &results {
+            files.add(result.outcome);
+            test_cases.passed += result.counts.passed;
+            test_cases.failed += result.counts.failed;
+            test_cases.skipped += result.counts.skipped;
+        }
+        Self {
+            files,
+            test_cases,
+            elapsed_secs: elapsed.as_secs_f64(),
+            results,
+        }
+    }
+
+    pub(crate) fn write_json(&self, path: &Path) -> Result<(), Errors> {
+        let json = serde_json::to_string_pretty(self).map_err(|_| Errors::InvalidTestOutput)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Writes the report as JUnit XML, one `testsuite` per file.
+    ///
+    /// Files that failed or were skipped as a whole get a single test case describing why.
+    pub(crate) fn write_junit(&self, path: &Path) -> Result<(), Errors> {
+        fs::write(path, self.to_junit())?;
+        Ok(())
+    }
+
+    fn to_junit(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let mut totals = Counts::default();
+        let mut suites = String::new();
+        for file in &self.results {
+            let mut counts = file.counts;
+            let mut cases = String::new();
+            if file.test_cases.is_empty() && file.outcome != Outcome::Passed {
+                counts.add(file.outcome);
+                write_testcase(
+                    &mut cases,
+                    &file.path,
+                    "file",
+                    file.outcome,
+                    file.reason.as_deref(),
+                    file.elapsed_secs,
+                );
+            }
+            for case in &file.test_cases {
+                write_testcase(
+                    &mut cases,
+                    &file.path,
+                    &case.name,
+                    case.outcome,
+                    case.reason.as_deref(),
+                    case.elapsed_secs,
+                );
+            }
+            totals.passed += counts.passed;
+            totals.failed += counts.failed;
+            totals.skipped += counts.skipped;
+            let _ = writeln!(
+                suites,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+                escape(&file.path),
+                counts.total(),
+                counts.failed,
+                counts.skipped,
+                file.elapsed_secs
+            );
+            suites.push_str(&cases);
+            suites.push_str("  </testsuite>\n");
+        }
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"revme semantics\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            totals.total(),
+            totals.failed,
+            totals.skipped,
+            self.elapsed_secs
+        );
+        xml.push_str(&suites);
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn write_testcase(
+    xml: &mut String,
+    classname: &str,
+    name: &str,
+    outcome: Outcome,
+    reason: Option<&str>,
+    elapsed_secs: f64,
+) {
+    let _ = write!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.6}\"",
+        escape(name),
+        escape(classname),
+        elapsed_secs
+    );
+    let tag = match outcome {
+        Outcome::Passed => {
+            xml.push_str("/>\n");
+            return;
+        }
+        Outcome::Failed => "failure",
+        Outcome::Skipped => "skipped",
+    };
+    let _ = writeln!(
+        xml,
+        ">\n      <{} message=\"{}\"/>\n    </testcase>",
+        tag,
+        escape(reason.unwrap_or_default())
+    );
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_counts_and_escaping() {
+        let mut passed = FileReport::new("a.sol");
+        passed.add_test_case("0: f()".to_string(), Some(&Ok(())), Duration::ZERO);
+        passed.add_test_case(
+            "1: g(uint256)".to_string(),
+            Some(&Err(Errors::LogMismatch)),
+            Duration::ZERO,
+        );
+        passed.add_test_case("2: \"h\"".to_string(), None, Duration::ZERO);
+        let mut skipped = FileReport::new("b<c>.sol");
+        skipped.set_outcome(Outcome::Skipped, &Errors::UnhandledTestFormat);
+
+        let report = Report::new(vec![skipped, passed], Duration::from_secs(1));
+        assert_eq!(
+            report.files,
+            Counts {
+                passed: 0,
+                failed: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(
+            report.test_cases,
+            Counts {
+                passed: 1,
+                failed: 1,
+                skipped: 1
+            }
+        );
+
+        let xml = report.to_junit();
+        assert!(xml.contains(
+            "<testsuites name=\"revme semantics\" tests=\"4\" failures=\"1\" skipped=\"2\""
+        ));
+        assert!(xml.contains("<failure message=\"Log Mismatch\"/>"));
+        assert!(xml.contains("name=\"2: &quot;h&quot;\""));
+        assert!(xml.contains("<testsuite name=\"b&lt;c&gt;.sol\" tests=\"1\""));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["results"][0]["path"], "a.sol");
+        assert_eq!(json["results"][0]["test_cases"][1]["outcome"], "failed");
+    }
+
+    #[test]
+    fn files_not_run_are_skipped() {
+        let report = Report::new(vec![FileReport::not_run("d.sol")], Duration::ZERO);
+        assert_eq!(report.files.skipped, 1);
+        assert!(report
+            .to_junit()
+            .contains("<skipped message=\"Previous test file failed\"/>"));
+    }
+}
@@ -1,19 +1,27 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::process::Command;
+use std::sync::LazyLock;
 
 use log::error;
-use regex::Regex;
-use revm::primitives::{hex, Address, Bytes};
+use regex::{Captures, Regex};
+use revm::primitives::{hex, Address, Bytes, HashMap};
 
 use super::{
+    abi::{AbiType, FunctionSignature},
+    artifacts::{CompileProfile, Compiler},
     compiler_evm_versions::EVMVersion,
+    gas::gas_mode,
     test_cases::TestCase,
     utils::{extract_compile_via_yul, extract_functions_from_source, needs_eof},
     Errors,
 };
 
+/// Library link placeholder `__$<hash>$__` of unlinked bytecode.
+static LINK_PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"__\$([0-9a-fA-F]{34})\$__").expect("invalid regex"));
+
 const SKIP_KEYWORD: [&str; 3] = [
-    "==== Source:",
+    "==== ExternalSource:",
     "allowNonExistingFunctions: true",
     "revertStrings: debug",
 ];
@@ -21,11 +29,17 @@ const SKIP_KEYWORD: [&str; 3] = [
 #[derive(Debug, Clone)]
 pub struct ContractInfo {
     pub contract_name: String,
+    /// Source unit the contract is defined in.
+    pub source: String,
     pub evm_version: EVMVersion,
     compile_binary: String,
     pub functions: Vec<String>,
     pub is_library: bool,
     deploy_args: Vec<u8>,
+    /// Link placeholder hashes of the bytecode, mapped to the `<source>:<library>` name.
+    link_references: BTreeMap<String, String>,
+    /// Return types of the functions of the ABI, keyed by canonical signature.
+    function_outputs: HashMap<String, Vec<AbiType>>,
 }
 
 impl ContractInfo {
@@ -38,11 +52,14 @@ impl ContractInfo {
     ) -> Self {
         Self {
             contract_name,
+            source: String::new(),
             evm_version,
             compile_binary,
             functions: Vec::new(),
             is_library,
             deploy_args: vec![],
+            link_references: BTreeMap::new(),
+            function_outputs: HashMap::default(),
         }
     }
 
@@ -66,38 +83,95 @@ impl ContractInfo {
         self.deploy_args = buffer;
     }
 
+    /// Returns the return types of a function, if the ABI of the contract has it.
+    pub(crate) fn output_types(&self, signature: &FunctionSignature) -> Option<&[AbiType]> {
+        self.function_outputs
+            .get(&signature.to_string())
+            .map(Vec::as_slice)
+    }
+
+    /// Reads the return types of the functions from an ABI, entries with unsupported types
+    /// are skipped.
+    fn set_abi(&mut self, abi: &serde_json::Value) {
+        let functions = abi
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry["type"] == "function");
+        for function in functions {
+            let types = |key: &str| -> Option<Vec<AbiType>> {
+                function[key]
+                    .as_array()?
+                    .iter()
+                    .map(|param| AbiType::from_json(param).ok())
+                    .collect()
+            };
+            let (Some(name), Some(inputs), Some(outputs)) =
+                (function["name"].as_str(), types("inputs"), types("outputs"))
+            else {
+                continue;
+            };
+            let signature = FunctionSignature {
+                name: name.to_string(),
+                inputs,
+            };
+            self.function_outputs.insert(signature.to_string(), outputs);
+        }
+    }
+
     pub fn set_is_library(&mut self, is_library: bool) {
         self.is_library = is_library;
     }
 
-    pub fn get_deployable_code(&self, address: Option<Address>) -> Bytes {
-        let mut code_str = self.compile_binary.clone();
+    /// Returns the fully qualified `<source>:<contract>` name.
+    pub fn qualified_name(&self) -> String {
+        format!("{}:{}", self.source, self.contract_name)
+    }
 
-        if let Some(addr) = address {
-            let mut addr_str = addr.to_string(); // e.g. "0x123abc..."
+    /// Returns the qualified names of the libraries the bytecode must be linked against.
+    pub fn linked_libraries(&self) -> impl Iterator<Item = &str> {
+        self.link_references.values().map(String::as_str)
+    }
 
-            if addr_str.starts_with("0x") {
-                addr_str = addr_str.trim_start_matches("0x").to_string();
+    /// Returns the creation code with the library placeholders replaced by the deployed
+    /// library addresses, followed by the constructor arguments.
+    pub fn get_deployable_code(
+        &self,
+        libraries: &HashMap<String, Address>,
+    ) -> Result<Bytes, Errors> {
+        let mut unlinked = None;
+        let code_str = LINK_PLACEHOLDER.replace_all(&self.compile_binary, |caps: &Captures<'_>| {
+            match self
+                .link_references
+                .get(&caps[1])
+                .and_then(|library| libraries.get(library))
+            {
+                Some(address) => hex::encode(address),
+                None => {
+                    unlinked = Some(caps[0].to_string());
+                    caps[0].to_string()
+                }
             }
-
-            let re = Regex::new(r"__\$[0-9a-fA-F]+\$__").expect("invalid regex");
-            code_str = re.replace_all(&code_str, &addr_str).to_string();
+        });
+        if let Some(placeholder) = unlinked {
+            error!(
+                "No deployed library for placeholder {} of {}",
+                placeholder, self.contract_name
+            );
+            return Err(Errors::UnlinkedLibrary);
         }
 
-        let mut code_bytes = match hex::decode(&code_str) {
-            Ok(bytes) => bytes,
-            Err(decode_error) => {
-                error!(
-                    "Failed to decode bytecode string: {}, error: {:?}",
-                    code_str, decode_error
-                );
-                return Bytes::new();
-            }
-        };
+        let mut code_bytes = hex::decode(code_str.as_ref()).map_err(|decode_error| {
+            error!(
+                "Failed to decode bytecode string: {}, error: {:?}",
+                code_str, decode_error
+            );
+            Errors::InvalidBytecode
+        })?;
 
         code_bytes.extend_from_slice(&self.deploy_args);
 
-        Bytes::from(code_bytes)
+        Ok(Bytes::from(code_bytes))
     }
 }
 
@@ -105,19 +179,20 @@ impl ContractInfo {
 pub struct SemanticTests {
     pub test_cases: Vec<TestCase>,
     pub contract_infos: Vec<ContractInfo>,
+    /// isoltest gas mode the sources are compiled in.
+    pub gas_mode: &'static str,
 }
 
 impl SemanticTests {
-    pub fn new(path: &str) -> Result<Self, Errors> {
+    pub fn new(path: &str, compiler: &Compiler) -> Result<Self, Errors> {
         let content = fs::read_to_string(path)?;
         let parts: Vec<&str> = content.split("// ----").collect();
         if parts.len() != 2 {
             return Err(Errors::InvalidTestFormat);
         }
 
-        // Early exit if the content contains `==== Source:` We do not handle this yet nor
-        // nonExistingFunctions nor Libraries that generate some slightly different Bytecode with
-        // the unhandled "_"
+        // Early exit if the content contains `==== ExternalSource:` We do not handle this yet nor
+        // nonExistingFunctions
         if SKIP_KEYWORD
             .iter()
             .any(|&keyword| content.contains(keyword))
@@ -125,113 +200,54 @@ impl SemanticTests {
             return Err(Errors::UnhandledTestFormat);
         }
         let expectations = parts[1].to_string();
+        let expectations_line = parts[0].matches('\n').count();
 
         let evm_version = EVMVersion::extract(&content);
         let via_ir = extract_compile_via_yul(&content);
         let eof_mode = needs_eof(&content);
 
-        let mut contract_infos =
-            Self::get_contract_infos(path, evm_version, via_ir, eof_mode, false)?;
+        let profile = CompileProfile {
+            evm_version,
+            via_ir,
+            eof: eof_mode,
+        };
+        let mut contract_infos = Self::get_contract_infos(path, compiler, profile)?;
 
-        let test_cases = TestCase::from_expectations(expectations, &mut contract_infos[..])?;
+        let test_cases =
+            TestCase::from_expectations(expectations, expectations_line, &mut contract_infos[..])?;
         Ok(SemanticTests {
             test_cases,
             contract_infos,
+            gas_mode: gas_mode(via_ir || eof_mode),
         })
     }
 
-    fn compile_solidity(
-        path: &str,
-        evm_version: Option<EVMVersion>,
-        via_ir: bool,
-        eof_mode: bool,
-        runtime: bool,
-    ) -> Result<String, Errors> {
-        let mut solc = Command::new("/usr/local/bin/solc");
-
-        solc.arg(if runtime { "--bin-runtime" } else { "--bin" })
-            .arg(path);
-
-        if let Some(v) = evm_version {
-            solc.arg("--evm-version").arg(v.to_string());
-        }
-
-        // via‑IR is required for EOF; keep explicit flag for legacy tests
-        if via_ir || eof_mode {
-            solc.arg("--via-ir");
-        }
-
-        if eof_mode {
-            solc.arg("--experimental-eof-version").arg("1");
-        }
-        // ─── invoke ───────────────────────────────────────────────────────────────
-        let output = solc.output().map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                Errors::CompilerNotFound
-            } else {
-                error!("Compilation failed for file: {:?}", path);
-                Errors::CompilationFailed
-            }
-        })?;
-
-        if !output.status.success() {
-            error!(
-                "Compilation failed for file: {:?}, output: {:?}",
-                path, output
-            );
-            return Err(Errors::CompilationFailed);
-        }
-
-        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
-    }
-
     fn get_contract_infos(
         path: &str,
-        evm_version: Option<EVMVersion>,
-        via_ir: bool,
-        eof_mode: bool,
-        runtime: bool,
+        compiler: &Compiler,
+        profile: CompileProfile,
     ) -> Result<Vec<ContractInfo>, Errors> {
-        let stdout_output = Self::compile_solidity(path, evm_version, via_ir, eof_mode, runtime)?;
+        let artifacts = compiler.compile(path, profile)?;
 
-        let revm_version = evm_version.unwrap_or(EVMVersion::Mercury);
+        let revm_version = profile.evm_version.unwrap_or(EVMVersion::Mercury);
 
         let mut contract_infos = Vec::new();
 
-        let contract_sections = stdout_output.split("======= ").skip(1);
-
-        for section in contract_sections {
-            let mut lines = section.lines();
-            let contract_line = lines.next().unwrap_or("");
-
-            let contract_name = contract_line
-                .split(':')
-                .nth(1)
-                .unwrap_or("Unknown")
-                .trim_end_matches(" =======")
-                .to_string();
-
-            let rest_of_section = lines.collect::<Vec<&str>>().join("\n");
-            if let Some(index) = rest_of_section.find("Binary:") {
-                let after_binary = &rest_of_section[index + "Binary:".len()..];
-                let bytecode_line = after_binary.lines().nth(1).unwrap_or("");
-
-                let mut contract_info = ContractInfo::new(
-                    contract_name.clone(),
-                    bytecode_line.to_string(),
-                    revm_version,
-                    false,
-                );
-
-                let contract_functions_map = extract_functions_from_source(path)?;
-                if let Some(functions) = contract_functions_map.get(&contract_name) {
-                    for function in functions {
-                        contract_info.add_function(function.clone());
-                    }
+        let contract_functions_map = extract_functions_from_source(path)?;
+        for artifact in artifacts {
+            let mut contract_info =
+                ContractInfo::new(artifact.name, artifact.bytecode, revm_version, false);
+            contract_info.source = artifact.source;
+            contract_info.link_references = artifact.link_references;
+            contract_info.set_abi(&artifact.abi);
+
+            if let Some(functions) = contract_functions_map.get(&contract_info.contract_name) {
+                for function in functions {
+                    contract_info.add_function(function.clone());
                 }
-
-                contract_infos.push(contract_info);
             }
+
+            contract_infos.push(contract_info);
         }
         //reversing as in the more down a function is seen the more likely it is the one we want to
         //call
@@ -239,3 +255,43 @@ impl SemanticTests {
         Ok(contract_infos)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_library_placeholders() {
+        let hash = "8aa64f937099b65a4febc243a5ae69c1e2";
+        let mut contract = ContractInfo::new(
+            "C".to_string(),
+            format!("73__${hash}$__00"),
+            EVMVersion::Mercury,
+            false,
+        );
+        contract
+            .link_references
+            .insert(hash.to_string(), "a.sol:L".to_string());
+        assert_eq!(contract.linked_libraries().collect::<Vec<_>>(), ["a.sol:L"]);
+
+        assert!(matches!(
+            contract.get_deployable_code(&HashMap::default()),
+            Err(Errors::UnlinkedLibrary)
+        ));
+
+        let address = Address::repeat_byte(0x11);
+        // A same-named library of another source does not link.
+        let other = HashMap::from_iter([("b.sol:L".to_string(), address)]);
+        assert!(matches!(
+            contract.get_deployable_code(&other),
+            Err(Errors::UnlinkedLibrary)
+        ));
+
+        let libraries = HashMap::from_iter([("a.sol:L".to_string(), address)]);
+        contract.add_deploy_args(vec![Bytes::from_static(&[0xff])]);
+        let code = contract.get_deployable_code(&libraries).unwrap();
+        assert_eq!(code[0], 0x73);
+        assert_eq!(&code[1..21], address.as_slice());
+        assert_eq!(&code[21..], &[0x00, 0xff]);
+    }
+}
@@ -1,9 +1,18 @@
 use std::str::FromStr;
 
-use super::{errors::Errors, parser::Parser, semantic_tests::ContractInfo, utils::bytes_to_fixed};
+use super::{
+    abi::{decode_params, format_params, AbiType, FunctionSignature},
+    errors::Errors,
+    gas::{ExpectedGas, GasExpectation},
+    parser::Parser,
+    semantic_tests::ContractInfo,
+    utils::split_top_level,
+};
 use log::info;
-use revm::primitives::{keccak256, Address, Bytes, FixedBytes, HashMap, LogData, U256};
+use revm::primitives::{hex, keccak256, Address, Bytes, FixedBytes, HashMap, LogData, U256};
 
+/// Calls whose outputs depend on the gas left, such as `gasleft()`, are skipped. Gas expectation
+/// lines are parsed before this check.
 const SKIP_KEYWORD: [&str; 1] = ["gas"];
 
 #[derive(Debug, Clone, PartialEq)]
@@ -22,12 +31,27 @@ impl Default for ExecutionResult {
 pub(crate) struct ExpectedOutputs {
     state: ExecutionResult,
     pub output: Bytes,
+    /// Return types of the called function, if known from the ABI.
+    types: Option<Vec<AbiType>>,
 }
 
 impl ExpectedOutputs {
     pub(crate) fn is_success(&self) -> bool {
         self.state == ExecutionResult::Success
     }
+
+    /// Formats return data as isoltest values if it decodes as the return types, as hex
+    /// otherwise.
+    pub(crate) fn describe(&self, output: &[u8]) -> String {
+        self.types
+            .as_deref()
+            .filter(|_| self.is_success())
+            .and_then(|types| {
+                let values = decode_params(types, output).ok()?;
+                Some(format_params(&values))
+            })
+            .unwrap_or_else(|| hex::encode_prefixed(output))
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -41,6 +65,7 @@ pub(crate) enum TestStep {
         contract: ContractInfo,
         value: U256,
         expected_events: Vec<LogData>,
+        expected_gas: ExpectedGas,
     },
     CallFunction {
         function_name: String,
@@ -48,6 +73,7 @@ pub(crate) enum TestStep {
         expected_outputs: ExpectedOutputs,
         value: U256,
         expected_events: Vec<LogData>,
+        expected_gas: ExpectedGas,
     },
     CheckStorageEmpty {
         expected_empty: bool,
@@ -57,9 +83,33 @@ pub(crate) enum TestStep {
     },
 }
 
+impl TestStep {
+    fn expected_gas_mut(&mut self) -> Option<&mut ExpectedGas> {
+        match self {
+            Self::Deploy { expected_gas, .. } | Self::CallFunction { expected_gas, .. } => {
+                Some(expected_gas)
+            }
+            _ => None,
+        }
+    }
+}
+
 impl TestCase {
+    /// Returns a name for reports, made of the index of the test case and its first call.
+    pub(crate) fn name(&self, index: usize) -> String {
+        let call = self.steps.iter().find_map(|step| match step {
+            TestStep::CallFunction { function_name, .. } => Some(function_name.as_str()),
+            TestStep::Deploy { contract, .. } if !contract.is_library => Some("constructor"),
+            _ => None,
+        });
+        format!("#{} {}", index, call.unwrap_or("checks"))
+    }
+
+    /// Parses the expectations of a test, `first_line` being the line of the test file they
+    /// start on.
     pub(crate) fn from_expectations(
         expectations: String,
+        first_line: usize,
         contract_infos: &mut [ContractInfo],
     ) -> Result<Vec<Self>, Errors> {
         let mut test_cases = Vec::new();
@@ -67,42 +117,67 @@ impl TestCase {
 
         let mut first_contract_deployed = false;
 
-        for line in expectations
+        for (line_number, line) in expectations
             .lines()
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
+            .enumerate()
+            .map(|(index, line)| (first_line + index, line.trim()))
+            .filter(|(_, l)| !l.is_empty())
         {
             let line = Self::strip_comments(line);
 
-            if line.starts_with("library:") {
-                let lib_name = line.trim_start_matches("library:").trim();
+            if line.starts_with("gas ") {
+                if let Some(expectation) = GasExpectation::parse(&line, line_number) {
+                    if let Some(expected_gas) =
+                        steps.last_mut().and_then(TestStep::expected_gas_mut)
+                    {
+                        expected_gas.last_line = line_number;
+                        expected_gas.expectations.push(expectation);
+                    }
+                    continue;
+                }
+            }
 
-                if let Some(lib_info) = contract_infos
-                    .iter_mut()
-                    .find(|c| c.contract_name == lib_name)
-                {
+            if line.starts_with("library:") {
+                // Libraries of multi-source tests are given as `"<source>":<library>`.
+                let library = line.trim_start_matches("library:").trim();
+                let (lib_source, lib_name) = match library.rsplit_once(':') {
+                    Some((source, name)) => (Some(source.trim().trim_matches('"')), name.trim()),
+                    None => (None, library),
+                };
+
+                if let Some(lib_info) = contract_infos.iter_mut().find(|c| {
+                    c.contract_name == lib_name
+                        && lib_source.is_none_or(|source| c.source == source)
+                }) {
                     lib_info.set_is_library(true);
                     steps.push(TestStep::Deploy {
                         contract: lib_info.clone(),
                         value: U256::ZERO,
                         expected_events: vec![],
+                        expected_gas: ExpectedGas::new(line_number),
                     });
                 }
                 continue;
             }
 
             if line.contains("~ emit") {
-                let event_bytes = Self::parse_event(&line);
+                let event_bytes = Self::parse_event(&line)?;
                 if let Some(TestStep::CallFunction {
-                    expected_events, ..
+                    expected_events,
+                    expected_gas,
+                    ..
                 }) = steps.last_mut()
                 {
                     expected_events.push(event_bytes);
+                    expected_gas.last_line = line_number;
                 } else if let Some(TestStep::Deploy {
-                    expected_events, ..
+                    expected_events,
+                    expected_gas,
+                    ..
                 }) = steps.last_mut()
                 {
                     expected_events.push(event_bytes);
+                    expected_gas.last_line = line_number;
                 } else {
                     return Err(Errors::InvalidInput);
                 }
@@ -160,22 +235,16 @@ impl TestCase {
             }
 
             let (function_signature, value, inputs) = Self::parse_call_part(call_part)?;
-            let expected_outputs = Self::parse_outputs(expected_output_part)?;
-            let (function_selector, _) = Parser::parse_function_signature(&function_signature)?;
+            let signature = FunctionSignature::parse(&function_signature)?;
             let is_constructor = function_signature.starts_with("constructor(");
 
-            let mut args_encoded = Vec::new();
-            for arg_str in &inputs {
-                args_encoded.push(Parser::parse_arg(arg_str)?);
-            }
+            let args_encoded = Parser::encode_values(&signature.inputs, &inputs)?;
 
             let mut input_data = Vec::new();
             if !is_constructor && function_signature != "()" {
-                input_data.extend_from_slice(&function_selector);
-            }
-            for arg in &args_encoded {
-                input_data.extend_from_slice(arg);
+                input_data.extend_from_slice(&signature.selector());
             }
+            input_data.extend_from_slice(&args_encoded);
 
             let mut matching_contract = contract_infos.iter().find(|contract| {
                 if function_signature == "()" {
@@ -191,9 +260,11 @@ impl TestCase {
             }
 
             if let Some(contract) = matching_contract {
+                let expected_outputs =
+                    Self::parse_outputs(expected_output_part, contract.output_types(&signature))?;
                 let mut contract_copy = contract.clone();
                 if is_constructor {
-                    contract_copy.add_deploy_args(args_encoded.clone());
+                    contract_copy.add_deploy_args(vec![args_encoded]);
                     input_data.clear();
                 }
 
@@ -202,6 +273,7 @@ impl TestCase {
                         contract: contract_copy,
                         value: value.unwrap_or_default(),
                         expected_events: vec![],
+                        expected_gas: ExpectedGas::new(line_number),
                     });
                     first_contract_deployed = true;
                     continue;
@@ -212,6 +284,7 @@ impl TestCase {
                             contract: contract_copy,
                             value: U256::ZERO,
                             expected_events: vec![],
+                            expected_gas: ExpectedGas::default(),
                         },
                     );
                     first_contract_deployed = true;
@@ -222,6 +295,7 @@ impl TestCase {
                     expected_outputs,
                     value: value.unwrap_or_default(),
                     expected_events: vec![],
+                    expected_gas: ExpectedGas::new(line_number),
                 });
             } else {
                 info!(
@@ -238,7 +312,7 @@ impl TestCase {
         Ok(test_cases)
     }
 
-    fn parse_event(call_part: &str) -> LogData {
+    fn parse_event(call_part: &str) -> Result<LogData, Errors> {
         // Remove the "~ emit" prefix and trim whitespace.
         let event_str = call_part.trim().trim_start_matches("~ emit").trim();
         // Split at the first colon to separate signature and arguments.
@@ -272,18 +346,16 @@ impl TestCase {
                     if arg.starts_with('#') {
                         // Indexed parameter: remove '#' and parse hex.
                         let hex_str = arg.trim_start_matches('#').trim();
-                        let parsed = Parser::parse_arg(hex_str).unwrap();
-                        topics.push(bytes_to_fixed(parsed));
+                        topics.push(Parser::parse_word(hex_str)?);
                     } else {
                         // Non-indexed parameter: parse hex and append to data.
-                        let parsed = Parser::parse_arg(arg).unwrap();
-                        data.extend(parsed);
+                        data.extend(Parser::parse_raw(arg)?);
                     }
                 }
             }
         }
 
-        LogData::new(topics, data.into()).unwrap()
+        LogData::new(topics, data.into()).ok_or(Errors::InvalidInput)
     }
 
     fn parse_balance(line: &str) -> Result<(Address, U256), Errors> {
@@ -337,7 +409,7 @@ impl TestCase {
         }
     }
 
-    fn parse_call_part(call_part: &str) -> Result<(String, Option<U256>, Vec<String>), Errors> {
+    fn parse_call_part(call_part: &str) -> Result<(String, Option<U256>, Vec<&str>), Errors> {
         // Find the function signature by matching parentheses
         let mut paren_count = 0;
         let mut sig_end_idx = None;
@@ -386,17 +458,7 @@ impl TestCase {
             }
         }
 
-        // Parse inputs
-        let inputs = if !inputs_str.is_empty() {
-            inputs_str
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .collect()
-        } else {
-            Vec::new()
-        };
-
-        Ok((function_signature, value, inputs))
+        Ok((function_signature, value, split_top_level(inputs_str)))
     }
 
     fn parse_value(value_str: &str) -> Result<U256, Errors> {
@@ -414,40 +476,34 @@ impl TestCase {
         Ok(amount * multiplier)
     }
 
-    fn parse_outputs(outputs_str: &str) -> Result<ExpectedOutputs, Errors> {
+    /// Parses the expected outputs, encoded for the return types of the function if known.
+    fn parse_outputs(
+        outputs_str: &str,
+        types: Option<&[AbiType]>,
+    ) -> Result<ExpectedOutputs, Errors> {
         if outputs_str.is_empty() {
             Ok(ExpectedOutputs::default())
         } else if outputs_str.contains("FAILURE") {
-            let outputs_list: Vec<&str> = outputs_str
-                .split(',')
-                .map(|s| s.trim())
+            // Revert data is not typed by the function, only raw words are supported.
+            let outputs_list: Vec<&str> = split_top_level(outputs_str)
+                .into_iter()
                 .filter(|s| !s.is_empty() && !s.contains("FAILURE"))
                 .collect();
 
-            let mut output = vec![];
-            for output_arg in outputs_list {
-                let output_encoded = Parser::parse_arg(output_arg)?;
-                output.extend_from_slice(output_encoded.as_ref());
-            }
-
             Ok(ExpectedOutputs {
                 state: ExecutionResult::Failure,
-                output: output.into(),
+                output: Parser::encode_values(&[], &outputs_list)?,
+                types: None,
             })
         } else {
-            let outputs_list: Vec<&str> = outputs_str
-                .split(',')
-                .map(|s| s.trim())
+            let outputs_list: Vec<&str> = split_top_level(outputs_str)
+                .into_iter()
                 .filter(|s| !s.is_empty())
                 .collect();
-            let mut output = Vec::new();
-            for output_arg in outputs_list {
-                let output_encoded = Parser::parse_arg(output_arg)?;
-                output.extend_from_slice(output_encoded.as_ref());
-            }
             Ok(ExpectedOutputs {
                 state: ExecutionResult::Success,
-                output: output.into(),
+                output: Parser::encode_values(types.unwrap_or_default(), &outputs_list)?,
+                types: types.map(<[AbiType]>::to_vec),
             })
         }
     }
@@ -2,7 +2,7 @@ use context::result::{ExecutionResult, HaltReason, ResultAndState};
 use log::error;
 use primitives::HashMap;
 use revm::database::{CacheDB, EmptyDB};
-use revm::primitives::{Address, Bytes, FixedBytes, Log, LogData, U256};
+use revm::primitives::{Address, Log, LogData, U256};
 use seismic_revm::SeismicHaltReason;
 
 use crate::cmd::semantics::Errors;
@@ -203,14 +203,6 @@ pub(crate) fn extract_functions_from_source(
     Ok(contract_functions)
 }
 
-pub(crate) fn count_used_bytes_right(bytes: &[u8]) -> usize {
-    let mut start = 0;
-    while start < bytes.len() && bytes[start] == 0 {
-        start += 1;
-    }
-    bytes.len() - start
-}
-
 pub(crate) fn parse_string_with_escapes(s: &str) -> Result<Vec<u8>, Errors> {
     let mut bytes = Vec::new();
     let mut chars = s.chars().peekable();
@@ -274,6 +266,43 @@ pub(crate) fn parse_string_with_escapes(s: &str) -> Result<Vec<u8>, Errors> {
     Ok(bytes)
 }
 
+/// Splits a comma separated list, ignoring commas nested in brackets, parentheses or strings.
+///
+/// Items are trimmed and an empty list yields no items.
+pub(crate) fn split_top_level(list: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = list[start..].trim();
+    if !last.is_empty() || !items.is_empty() {
+        items.push(last);
+    }
+    items
+}
+
 /// Verifies that the emitted logs (ignoring address and topics) match the expected events.
 pub(crate) fn verify_emitted_events(
     expected_events: &[LogData],
@@ -353,14 +382,6 @@ pub(crate) fn verify_storage_empty(
     Ok(())
 }
 
-// Helper function to convert Bytes into FixedBytes<32>
-pub(crate) fn bytes_to_fixed(bytes: Bytes) -> FixedBytes<32> {
-    let slice = bytes.as_ref();
-    let mut fixed = [0u8; 32];
-    fixed.copy_from_slice(slice);
-    fixed.into()
-}
-
 pub fn mainnet_to_seismic(raw: ResultAndState<HaltReason>) -> ResultAndState<SeismicHaltReason> {
     let ResultAndState { result, state } = raw;
     let result = match result {
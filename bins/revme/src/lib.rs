@@ -1,2 +1,3 @@
 pub mod cmd;
 pub mod dir_utils;
+pub mod prestate;
@@ -0,0 +1,242 @@
+//! Geth-style `alloc` files holding the accounts of a run.
+//!
+//! A prestate is loaded into a [`CacheDB`] before execution and the post-state can be written
+//! back in the same format, so runs can be chained and diffed. Storage values are either a plain
+//! hex value or `{ "value": .., "isPrivate": true }` for private slots.
+use database::{AccountState, CacheDB, DatabaseRef};
+use revm::{
+    bytecode::{Bytecode, BytecodeDecodeError},
+    primitives::{alloy_primitives::U64, Address, Bytes, FlaggedStorage, U256},
+    state::AccountInfo,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io::Error as IoError, path::Path};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PrestateError {
+    #[error("Invalid prestate: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid code of account {address}: {error}")]
+    Code {
+        address: Address,
+        error: BytecodeDecodeError,
+    },
+    #[error("Failed to load account {0}")]
+    Database(Address),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Accounts of an `alloc` file, sorted by address.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Prestate(pub BTreeMap<Address, PrestateAccount>);
+
+/// Account of an `alloc` file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrestateAccount {
+    pub balance: U256,
+    pub nonce: U64,
+    #[serde(skip_serializing_if = "Bytes::is_empty")]
+    pub code: Bytes,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<U256, PrestateSlot>,
+}
+
+/// Storage value of an `alloc` file, public unless flagged otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateSlot {
+    Public(U256),
+    #[serde(rename_all = "camelCase")]
+    Flagged {
+        value: U256,
+        #[serde(default)]
+        is_private: bool,
+    },
+}
+
+impl From<PrestateSlot> for FlaggedStorage {
+    fn from(slot: PrestateSlot) -> Self {
+        match slot {
+            PrestateSlot::Public(value) => FlaggedStorage::new_from_value(value),
+            PrestateSlot::Flagged { value, is_private } => FlaggedStorage::new(value, is_private),
+        }
+    }
+}
+
+impl From<FlaggedStorage> for PrestateSlot {
+    fn from(slot: FlaggedStorage) -> Self {
+        if slot.is_private {
+            PrestateSlot::Flagged {
+                value: slot.value,
+                is_private: true,
+            }
+        } else {
+            PrestateSlot::Public(slot.value)
+        }
+    }
+}
+
+impl Prestate {
+    /// Reads an `alloc` file.
+    pub fn from_file(path: &Path) -> Result<Self, PrestateError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the accounts as a pretty printed `alloc` file.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), PrestateError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Collects the accounts held in the cache of the database.
+    ///
+    /// Accounts the cache knows as not existing and empty storage slots are left out. Accounts
+    /// that were never loaded into the cache are not part of the result.
+    pub fn from_cache<ExtDB>(db: &CacheDB<ExtDB>) -> Self {
+        let accounts = db
+            .cache
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.account_state != AccountState::NotExisting)
+            .map(|(address, account)| {
+                let code = account
+                    .info
+                    .code
+                    .as_ref()
+                    .or_else(|| db.cache.contracts.get(&account.info.code_hash))
+                    .map(Bytecode::original_bytes)
+                    .unwrap_or_default();
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| !slot.value.is_zero())
+                    .map(|(key, slot)| (*key, (*slot).into()))
+                    .collect();
+                let account = PrestateAccount {
+                    balance: account.info.balance,
+                    nonce: U64::from(account.info.nonce),
+                    code,
+                    storage,
+                };
+                (*address, account)
+            })
+            .collect();
+        Self(accounts)
+    }
+
+    /// Inserts the accounts into the database, replacing their info and storage.
+    pub fn insert_into<ExtDB: DatabaseRef>(
+        self,
+        db: &mut CacheDB<ExtDB>,
+    ) -> Result<(), PrestateError> {
+        for (address, account) in self.0 {
+            let mut info =
+                AccountInfo::from_balance(account.balance).with_nonce(account.nonce.to());
+            if !account.code.is_empty() {
+                let code = Bytecode::new_raw_checked(account.code)
+                    .map_err(|error| PrestateError::Code { address, error })?;
+                info = info.with_code(code);
+            }
+            db.insert_account_info(address, info);
+            db.replace_account_storage(
+                address,
+                account
+                    .storage
+                    .into_iter()
+                    .map(|(key, slot)| (key, slot.into()))
+                    .collect(),
+            )
+            .map_err(|_| PrestateError::Database(address))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::{DatabaseCommit, EmptyDB};
+    use revm::{
+        primitives::HashMap,
+        state::{Account, AccountStatus, EvmStorageSlot},
+    };
+
+    #[test]
+    fn alloc_roundtrip_with_private_slots() {
+        let alloc = r#"{
+            "0x0000000000000000000000000000000000000001": {
+                "balance": "0x10",
+                "nonce": "0x2",
+                "code": "0x6001600055",
+                "storage": {
+                    "0x1": "0x11",
+                    "0x2": { "value": "0x22", "isPrivate": true },
+                    "0x3": { "value": "0x33" }
+                }
+            },
+            "0x0000000000000000000000000000000000000002": { "balance": "0x5" }
+        }"#;
+        let prestate: Prestate = serde_json::from_str(alloc).unwrap();
+        let first = Address::with_last_byte(1);
+        let second = Address::with_last_byte(2);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        prestate.clone().insert_into(&mut db).unwrap();
+        assert_eq!(db.basic_ref(first).unwrap().unwrap().nonce, 2);
+        assert_eq!(
+            db.storage_ref(first, U256::from(2)).unwrap(),
+            FlaggedStorage::new(U256::from(0x22), true)
+        );
+        assert_eq!(
+            db.storage_ref(first, U256::from(3)).unwrap(),
+            FlaggedStorage::new_from_value(U256::from(0x33))
+        );
+
+        // Public slots are written back as plain values.
+        let mut expected = prestate.clone();
+        expected
+            .0
+            .get_mut(&first)
+            .unwrap()
+            .storage
+            .insert(U256::from(3), PrestateSlot::Public(U256::from(0x33)));
+        let dumped = Prestate::from_cache(&db);
+        assert_eq!(dumped, expected);
+        let json = serde_json::to_string(&dumped).unwrap();
+        assert_eq!(serde_json::from_str::<Prestate>(&json).unwrap(), expected);
+
+        // Post-state reflects committed changes, zeroed slots are dropped.
+        let mut account = Account::from(db.basic_ref(second).unwrap().unwrap());
+        account.status = AccountStatus::Touched;
+        account.storage = HashMap::from_iter([(
+            U256::from(1),
+            EvmStorageSlot::new_changed(
+                FlaggedStorage::ZERO,
+                FlaggedStorage::new(U256::from(7), true),
+            ),
+        )]);
+        let mut cleared = Account::from(db.basic_ref(first).unwrap().unwrap());
+        cleared.status = AccountStatus::Touched;
+        cleared.storage = HashMap::from_iter([(
+            U256::from(1),
+            EvmStorageSlot::new_changed(
+                FlaggedStorage::new_from_value(U256::from(0x11)),
+                FlaggedStorage::ZERO,
+            ),
+        )]);
+        db.commit(HashMap::from_iter([(second, account), (first, cleared)]));
+
+        let dumped = Prestate::from_cache(&db);
+        assert!(!dumped.0[&first].storage.contains_key(&U256::from(1)));
+        assert_eq!(
+            dumped.0[&second].storage[&U256::from(1)],
+            PrestateSlot::Flagged {
+                value: U256::from(7),
+                is_private: true
+            }
+        );
+    }
+}
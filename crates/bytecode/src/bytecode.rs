@@ -212,6 +212,15 @@ impl Bytecode {
     pub fn iter_opcodes(&self) -> crate::BytecodeIterator<'_> {
         crate::BytecodeIterator::new(self)
     }
+
+    /// Returns an iterator over the opcodes, using the opcode table of a chain variant.
+    #[inline]
+    pub fn iter_opcodes_with<'a>(
+        &'a self,
+        opcodes: &'a crate::opcode::OpCodeTable,
+    ) -> crate::BytecodeIterator<'a> {
+        crate::BytecodeIterator::with_opcodes(self, opcodes)
+    }
 }
 
 #[cfg(test)]
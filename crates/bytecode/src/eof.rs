@@ -20,6 +20,7 @@ pub use header::{
 };
 pub use verification::*;
 
+use crate::opcode::OpCodeTable;
 use core::cmp::min;
 use primitives::{b256, bytes, Bytes, B256};
 use std::{fmt, vec, vec::Vec};
@@ -87,6 +88,11 @@ impl Eof {
         validate_eof_inner(self, Some(mode))
     }
 
+    /// Validates the EOF container against the opcode table of a chain variant.
+    pub fn validate_with_opcodes(&self, opcodes: &OpCodeTable) -> Result<(), EofError> {
+        validate_eof_with_opcodes(self, Some(CodeType::Initcode), opcodes)
+    }
+
     /// Returns len of the header and body in bytes.
     pub fn size(&self) -> usize {
         self.header.size() + self.header.body_size()
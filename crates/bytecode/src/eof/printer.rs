@@ -5,18 +5,23 @@
 //! It is enabled by the `std` feature.
 #![cfg(feature = "std")]
 
+use crate::opcode::{OpCodeTable, MAINNET_OPCODES};
+
 /// Pretty print the EOF bytecode in human readable format.
 pub fn print(code: &[u8]) {
-    use crate::{opcode::*, utils::read_i16};
+    print_with_opcodes(code, &MAINNET_OPCODES)
+}
+
+/// Pretty print the EOF bytecode in human readable format, naming opcodes from the given table.
+pub fn print_with_opcodes(code: &[u8], opcodes: &OpCodeTable) {
+    use crate::{opcode::RJUMPV, utils::read_i16};
     use primitives::hex;
 
     // We can check validity and jump destinations in one pass.
     let mut i = 0;
     while i < code.len() {
         let op = code[i];
-        let opcode = &OPCODE_INFO[op as usize];
-
-        let Some(opcode) = opcode else {
+        let Some(opcode) = opcodes.get(op) else {
             println!("Unknown opcode: 0x{:02X}", op);
             i += 1;
             continue;
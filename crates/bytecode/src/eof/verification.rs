@@ -2,7 +2,7 @@
 
 use crate::{
     eof::{CodeInfo, Eof, EofDecodeError},
-    opcode::{self, OPCODE_INFO},
+    opcode::{self, OpCodeTable, MAINNET_OPCODES},
     utils::{read_i16, read_u16},
 };
 use primitives::{
@@ -32,6 +32,22 @@ pub fn validate_raw_eof_inner(
     Ok(eof)
 }
 
+/// Decodes `raw` into an [`Eof`] container and validates it against the given opcode table.
+///
+/// Used by chain variants that define their own opcodes.
+pub fn validate_raw_eof_with_opcodes(
+    raw: Bytes,
+    first_code_type: Option<CodeType>,
+    opcodes: &OpCodeTable,
+) -> Result<Eof, EofError> {
+    if raw.len() > MAX_INITCODE_SIZE {
+        return Err(EofError::Decode(EofDecodeError::InvalidEOFSize));
+    }
+    let eof = Eof::decode(raw)?;
+    validate_eof_with_opcodes(&eof, first_code_type, opcodes)?;
+    Ok(eof)
+}
+
 /// Fully validates an [`Eof`] container.
 ///
 /// Only place where validation happen is in Creating Transaction.
@@ -47,12 +63,23 @@ pub fn validate_eof(eof: &Eof) -> Result<(), EofError> {
 /// in verification process.
 #[inline]
 pub fn validate_eof_inner(eof: &Eof, first_code_type: Option<CodeType>) -> Result<(), EofError> {
+    validate_eof_with_opcodes(eof, first_code_type, &MAINNET_OPCODES)
+}
+
+/// Fully validates an [`Eof`] container against the given opcode table.
+///
+/// Same as [`validate_eof_inner`], used by chain variants that define their own opcodes.
+pub fn validate_eof_with_opcodes(
+    eof: &Eof,
+    first_code_type: Option<CodeType>,
+    opcodes: &OpCodeTable,
+) -> Result<(), EofError> {
     // Data needs to be filled in the first container.
     if !eof.body.is_data_filled {
         return Err(EofError::Validation(EofValidationError::DataNotFilled));
     }
     if eof.body.container_section.is_empty() {
-        validate_eof_codes(eof, first_code_type)?;
+        validate_eof_codes_with_opcodes(eof, first_code_type, opcodes)?;
         return Ok(());
     }
 
@@ -61,7 +88,7 @@ pub fn validate_eof_inner(eof: &Eof, first_code_type: Option<CodeType>) -> Resul
 
     while let Some((eof, code_type)) = stack.pop() {
         // Validate the current container.
-        let tracker_containers = validate_eof_codes(&eof, code_type)?;
+        let tracker_containers = validate_eof_codes_with_opcodes(&eof, code_type, opcodes)?;
         // Decode subcontainers and push them to the stack.
         for (container, code_type) in eof
             .body
@@ -83,6 +110,18 @@ pub fn validate_eof_inner(eof: &Eof, first_code_type: Option<CodeType>) -> Resul
 pub fn validate_eof_codes(
     eof: &Eof,
     this_code_type: Option<CodeType>,
+) -> Result<Vec<CodeType>, EofValidationError> {
+    validate_eof_codes_with_opcodes(eof, this_code_type, &MAINNET_OPCODES)
+}
+
+/// Validates an [`Eof`] structure against the given opcode table, without recursing into
+/// containers.
+///
+/// Returns a list of all sub containers that are accessed.
+pub fn validate_eof_codes_with_opcodes(
+    eof: &Eof,
+    this_code_type: Option<CodeType>,
+    opcodes: &OpCodeTable,
 ) -> Result<Vec<CodeType>, EofValidationError> {
     if eof.body.code_section.len() != eof.body.code_info.len() {
         return Err(EofValidationError::InvalidCodeInfo);
@@ -110,13 +149,14 @@ pub fn validate_eof_codes(
     while let Some(index) = tracker.processing_stack.pop() {
         // Assume `index` is correct.
         let code = eof.body.code(index).unwrap();
-        validate_eof_code(
+        validate_eof_code_with_opcodes(
             &code,
             eof.header.data_size as usize,
             index,
             eof.body.container_section.len(),
             &eof.body.code_info,
             &mut tracker,
+            opcodes,
         )?;
     }
 
@@ -419,6 +459,27 @@ pub fn validate_eof_code(
     num_of_containers: usize,
     types: &[CodeInfo],
     tracker: &mut AccessTracker,
+) -> Result<(), EofValidationError> {
+    validate_eof_code_with_opcodes(
+        code,
+        data_size,
+        this_types_index,
+        num_of_containers,
+        types,
+        tracker,
+        &MAINNET_OPCODES,
+    )
+}
+
+/// Same as [`validate_eof_code`], with opcodes taken from the given table.
+pub fn validate_eof_code_with_opcodes(
+    code: &[u8],
+    data_size: usize,
+    this_types_index: usize,
+    num_of_containers: usize,
+    types: &[CodeInfo],
+    tracker: &mut AccessTracker,
+    opcodes: &OpCodeTable,
 ) -> Result<(), EofValidationError> {
     let this_types = &types[this_types_index];
 
@@ -471,9 +532,7 @@ pub fn validate_eof_code(
     // We can check validity and jump destinations in one pass.
     while i < code.len() {
         let op = code[i];
-        let opcode = &OPCODE_INFO[op as usize];
-
-        let Some(opcode) = opcode else {
+        let Some(opcode) = opcodes.get(op) else {
             // Err unknown opcode.
             return Err(EofValidationError::UnknownOpcode);
         };
@@ -825,6 +884,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn custom_opcode_table() {
+        use crate::opcode::{stack_io, OpCodeInfo};
+
+        const TABLE: OpCodeTable =
+            OpCodeTable::MAINNET.with_opcode(0xB0, stack_io(OpCodeInfo::new("CUSTOM"), 1, 1));
+        // PUSH0, 0xB0, POP, STOP
+        let raw: Bytes = hex!("ef00010100040200010004ff000100008000015fb0500000").into();
+
+        assert_eq!(
+            validate_raw_eof_inner(raw.clone(), Some(CodeType::Runtime)),
+            Err(EofError::Validation(EofValidationError::UnknownOpcode))
+        );
+        assert!(
+            validate_raw_eof_with_opcodes(raw.clone(), Some(CodeType::Runtime), &TABLE).is_ok()
+        );
+
+        const DISABLED: OpCodeTable = OpCodeTable::MAINNET.with_opcode(
+            0xB0,
+            opcode::not_eof(stack_io(OpCodeInfo::new("CUSTOM"), 1, 1)),
+        );
+        assert_eq!(
+            validate_raw_eof_with_opcodes(raw, Some(CodeType::Runtime), &DISABLED),
+            Err(EofError::Validation(EofValidationError::OpcodeDisabled))
+        );
+    }
+
     #[test]
     fn size_limit() {
         let eof = validate_raw_eof_inner(
@@ -1,4 +1,7 @@
-use crate::{opcode, Bytecode, OpCode};
+use crate::{
+    opcode::{self, OpCodeInfo, OpCodeTable, MAINNET_OPCODES},
+    Bytecode, OpCode,
+};
 
 /// Iterator over opcodes in a bytecode, skipping immediates.
 ///
@@ -10,12 +13,22 @@ pub struct BytecodeIterator<'a> {
     start: *const u8,
     /// Iterator over the bytecode bytes.
     bytes: core::slice::Iter<'a, u8>,
+    /// Opcode table used to find the immediate size of each opcode.
+    opcodes: &'a OpCodeTable,
 }
 
 impl<'a> BytecodeIterator<'a> {
     /// Creates a new iterator from a bytecode reference.
     #[inline]
     pub fn new(bytecode: &'a Bytecode) -> Self {
+        Self::with_opcodes(bytecode, &MAINNET_OPCODES)
+    }
+
+    /// Creates a new iterator from a bytecode reference, using the given opcode table.
+    ///
+    /// Used to iterate over bytecode of chain variants that define their own opcodes.
+    #[inline]
+    pub fn with_opcodes(bytecode: &'a Bytecode, opcodes: &'a OpCodeTable) -> Self {
         let bytes = match bytecode {
             Bytecode::LegacyAnalyzed(_) | Bytecode::Eof(_) => &bytecode.bytecode()[..],
             Bytecode::Eip7702(_) => &[],
@@ -23,6 +36,7 @@ impl<'a> BytecodeIterator<'a> {
         Self {
             start: bytes.as_ptr(),
             bytes: bytes.iter(),
+            opcodes,
         }
     }
 
@@ -54,7 +68,9 @@ impl<'a> BytecodeIterator<'a> {
     #[inline]
     fn skip_immediate(&mut self, opcode: u8) {
         // Get base immediate size from opcode info
-        let mut immediate_size = opcode::OPCODE_INFO[opcode as usize]
+        let mut immediate_size = self
+            .opcodes
+            .get(opcode)
             .map(|info| info.immediate_size() as usize)
             .unwrap_or_default();
 
@@ -87,6 +103,13 @@ impl<'a> BytecodeIterator<'a> {
     pub fn peek_opcode(&self) -> Option<OpCode> {
         self.peek().and_then(OpCode::new)
     }
+
+    /// Returns the info of the current opcode from the iterator's opcode table, without
+    /// advancing the iterator.
+    #[inline]
+    pub fn peek_info(&self) -> Option<OpCodeInfo> {
+        self.peek().and_then(|opcode| self.opcodes.get(opcode))
+    }
 }
 
 impl Iterator for BytecodeIterator<'_> {
@@ -301,4 +324,23 @@ mod tests {
         let opcodes: Vec<u8> = bytecode.iter_opcodes().collect();
         assert_eq!(opcodes, vec![opcode::STOP]);
     }
+
+    #[test]
+    fn test_custom_opcode_table() {
+        // Opcode 0xB0 with a one byte immediate, followed by STOP.
+        const TABLE: OpCodeTable = OpCodeTable::MAINNET
+            .with_opcode(0xB0, opcode::immediate_size(OpCodeInfo::new("CUSTOM"), 1));
+        let bytecode_data = vec![0xB0, opcode::ADD, opcode::STOP];
+        let raw_bytecode = LegacyRawBytecode(Bytes::from(bytecode_data));
+        let bytecode = Bytecode::LegacyAnalyzed(raw_bytecode.into_analyzed());
+
+        let iter = BytecodeIterator::with_opcodes(&bytecode, &TABLE);
+        assert_eq!(iter.peek_info().map(|info| info.name()), Some("CUSTOM"));
+        let opcodes: Vec<u8> = iter.collect();
+        assert_eq!(opcodes, vec![0xB0, opcode::STOP]);
+
+        // The mainnet table does not know 0xB0 and does not skip the immediate.
+        let opcodes: Vec<u8> = bytecode.iter_opcodes().collect();
+        assert_eq!(opcodes, vec![0xB0, opcode::ADD, opcode::STOP]);
+    }
 }
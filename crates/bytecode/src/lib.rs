@@ -29,11 +29,13 @@ pub use bytecode::Bytecode;
 pub use decode_errors::BytecodeDecodeError;
 pub use eof::{
     verification::{
-        validate_eof, validate_eof_code, validate_eof_codes, validate_eof_inner, validate_raw_eof,
-        validate_raw_eof_inner, CodeType, EofValidationError,
+        validate_eof, validate_eof_code, validate_eof_code_with_opcodes, validate_eof_codes,
+        validate_eof_codes_with_opcodes, validate_eof_inner, validate_eof_with_opcodes,
+        validate_raw_eof, validate_raw_eof_inner, validate_raw_eof_with_opcodes, CodeType,
+        EofValidationError,
     },
     Eof, EOF_MAGIC, EOF_MAGIC_BYTES, EOF_MAGIC_HASH,
 };
 pub use iter::BytecodeIterator;
 pub use legacy::{JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
-pub use opcode::OpCode;
+pub use opcode::{OpCode, OpCodeTable};
@@ -343,6 +343,70 @@ pub const fn stack_io(mut op: OpCodeInfo, inputs: u8, outputs: u8) -> OpCodeInfo
 /// Alias for the [`JUMPDEST`] opcode
 pub const NOP: u8 = JUMPDEST;
 
+/// Opcode info table, maps each opcode byte to its info.
+///
+/// [`OpCodeTable::MAINNET`] holds the opcodes declared in this module. Chain variants that define
+/// their own opcodes extend it with [`OpCodeTable::with_opcode`] and pass the table to the
+/// bytecode iterator, the EOF printer and the EOF validation.
+///
+/// [`OpCode`] only knows the mainnet opcodes, variant opcodes are only reachable through the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpCodeTable([Option<OpCodeInfo>; 256]);
+
+/// Mainnet opcode table.
+pub static MAINNET_OPCODES: OpCodeTable = OpCodeTable::MAINNET;
+
+impl Default for OpCodeTable {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+impl OpCodeTable {
+    /// Table of the opcodes declared in this module, same as [`OPCODE_INFO`].
+    pub const MAINNET: Self = Self(MAINNET_OPCODE_INFO);
+
+    /// Returns the table with `opcode` assigned to `info`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opcode is already assigned.
+    pub const fn with_opcode(mut self, opcode: u8, info: OpCodeInfo) -> Self {
+        assert!(
+            self.0[opcode as usize].is_none(),
+            "opcode is already assigned"
+        );
+        self.0[opcode as usize] = Some(info);
+        self
+    }
+
+    /// Returns the info of the opcode, `None` if it is not assigned.
+    #[inline]
+    pub const fn get(&self, opcode: u8) -> Option<OpCodeInfo> {
+        self.0[opcode as usize]
+    }
+
+    /// Returns the name of the opcode, `None` if it is not assigned.
+    #[inline]
+    pub const fn name(&self, opcode: u8) -> Option<&'static str> {
+        match self.0[opcode as usize] {
+            Some(info) => Some(info.name()),
+            None => None,
+        }
+    }
+
+    /// Returns the opcode with the given name.
+    pub fn opcode_by_name(&self, name: &str) -> Option<u8> {
+        (0..=u8::MAX).find(|opcode| self.name(*opcode) == Some(name))
+    }
+
+    /// Returns the table as an array indexed by opcode.
+    #[inline]
+    pub const fn as_array(&self) -> &[Option<OpCodeInfo>; 256] {
+        &self.0
+    }
+}
+
 /// Created all opcodes constants and two maps:
 ///  * `OPCODE_INFO` maps opcode number to the opcode info
 ///  * `NAME_TO_OPCODE` that maps opcode name to the opcode number.
@@ -359,7 +423,10 @@ macro_rules! opcodes {
         )*}
 
         /// Maps each opcode to its info.
-        pub static OPCODE_INFO: [Option<OpCodeInfo>; 256] = {
+        pub static OPCODE_INFO: [Option<OpCodeInfo>; 256] = MAINNET_OPCODE_INFO;
+
+        /// Maps each opcode to its info, usable in constant contexts.
+        const MAINNET_OPCODE_INFO: [Option<OpCodeInfo>; 256] = {
             let mut map = [None; 256];
             let mut prev: u8 = 0;
             $(
@@ -800,6 +867,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_opcode_table() {
+        assert_eq!(OpCodeTable::MAINNET.as_array(), &OPCODE_INFO);
+        assert_eq!(OpCodeTable::MAINNET.get(0xB0), None);
+
+        const TABLE: OpCodeTable = OpCodeTable::MAINNET
+            .with_opcode(0xB0, stack_io(OpCodeInfo::new("CUSTOM"), 1, 1))
+            .with_opcode(
+                0xB1,
+                not_eof(immediate_size(OpCodeInfo::new("CUSTOM_IMM"), 2)),
+            );
+        let custom = TABLE.get(0xB0).unwrap();
+        assert_eq!(custom.name(), "CUSTOM");
+        assert_eq!(custom.io_diff(), 0);
+        assert!(TABLE.get(0xB1).unwrap().is_disabled_in_eof());
+        assert_eq!(TABLE.name(0xB1), Some("CUSTOM_IMM"));
+        assert_eq!(TABLE.opcode_by_name("CUSTOM"), Some(0xB0));
+        assert_eq!(TABLE.opcode_by_name("SLOAD"), Some(SLOAD));
+        // Mainnet `OpCode` is unaffected.
+        assert_eq!(OpCode::new(0xB0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "opcode is already assigned")]
+    fn test_opcode_table_reassign() {
+        let _ = OpCodeTable::MAINNET.with_opcode(SLOAD, OpCodeInfo::new("SLOAD2"));
+    }
+
     #[test]
     #[cfg(feature = "parse")]
     fn test_parsing() {
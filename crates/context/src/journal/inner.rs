@@ -700,10 +700,11 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             ));
         }
 
+        // Journal the flag the slot had, so a revert restores its privacy as well.
         self.journal.push(ENTRY::storage_changed(
             address,
             key,
-            FlaggedStorage::new(present.data, is_private),
+            FlaggedStorage::new(present.data, present.is_private),
         ));
         // insert value into present state.
         slot.present_value = FlaggedStorage::new(new, is_private);
@@ -35,8 +35,14 @@ impl LocalContextTr for LocalContext {
 
     fn clear(&mut self) {
         self.initcode_mapping.clear();
+        // Wipes the whole capacity, including memory freed by earlier calls, and sets len to 0.
+        #[cfg(feature = "zeroize_memory")]
+        zeroize::Zeroize::zeroize(&mut *self.shared_memory_buffer.borrow_mut());
         // Sets len to 0 but it will not shrink to drop the capacity.
-        unsafe { self.shared_memory_buffer.borrow_mut().set_len(0) };
+        #[cfg(not(feature = "zeroize_memory"))]
+        unsafe {
+            self.shared_memory_buffer.borrow_mut().set_len(0)
+        };
     }
 
     fn get_validated_initcode(&mut self, hash: B256) -> Option<Bytes> {
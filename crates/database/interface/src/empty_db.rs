@@ -1,4 +1,4 @@
-use crate::{DBErrorMarker, Database, DatabaseRef};
+use crate::{DBErrorMarker, Database, DatabaseIter, DatabaseRef, StorageFilter, StorageRange};
 use core::error::Error;
 use core::{convert::Infallible, fmt, marker::PhantomData};
 use primitives::{keccak256, Address, FlaggedStorage, B256, U256};
@@ -101,6 +101,21 @@ impl<E: DBErrorMarker + Error> DatabaseRef for EmptyDBTyped<E> {
     }
 }
 
+impl<E: DBErrorMarker + Error> DatabaseIter for EmptyDBTyped<E> {
+    type Error = E;
+
+    #[inline]
+    fn storage_range(
+        &self,
+        _address: Address,
+        _start: U256,
+        _limit: usize,
+        _filter: StorageFilter,
+    ) -> Result<StorageRange, Self::Error> {
+        Ok(StorageRange::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
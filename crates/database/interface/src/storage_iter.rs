@@ -0,0 +1,210 @@
+//! Storage range iteration.
+//!
+//! [`DatabaseIter`] lists the storage slots of an account page by page, together with their
+//! privacy flag. It is optional: the EVM only needs point lookups, while tools such as state
+//! diffs and snapshot exporters need to enumerate an account's storage.
+use crate::DBErrorMarker;
+use auto_impl::auto_impl;
+use core::error::Error;
+use primitives::{Address, FlaggedStorage, U256};
+use std::vec::Vec;
+
+/// Number of slots fetched per page by [`DatabaseIter::storage_all`].
+pub const STORAGE_ALL_PAGE_SIZE: usize = 1024;
+
+/// Which storage slots to list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageFilter {
+    /// Public and private slots.
+    #[default]
+    All,
+    /// Public slots only.
+    Public,
+    /// Private slots only.
+    Private,
+}
+
+impl StorageFilter {
+    /// Returns `true` if the slot passes the filter.
+    #[inline]
+    pub fn matches(&self, value: &FlaggedStorage) -> bool {
+        match self {
+            Self::All => true,
+            Self::Public => !value.is_private,
+            Self::Private => value.is_private,
+        }
+    }
+}
+
+/// Page of storage slots of one account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageRange {
+    /// Slots sorted by key.
+    pub slots: Vec<(U256, FlaggedStorage)>,
+    /// Key to start the next page from, `None` if this is the last page.
+    pub next_key: Option<U256>,
+}
+
+impl StorageRange {
+    /// Builds a page out of unordered slots.
+    ///
+    /// Keeps the slots with a key of at least `start` that pass `filter`, skips empty slots,
+    /// and returns the first `limit` of them in key order.
+    pub fn paginate(
+        slots: impl IntoIterator<Item = (U256, FlaggedStorage)>,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+    ) -> Self {
+        let mut slots: Vec<_> = slots
+            .into_iter()
+            .filter(|(key, value)| *key >= start && !value.value.is_zero() && filter.matches(value))
+            .collect();
+        slots.sort_unstable_by_key(|(key, _)| *key);
+
+        let next_key = slots.get(limit).map(|(key, _)| *key);
+        slots.truncate(limit);
+        Self { slots, next_key }
+    }
+
+    /// Builds a page out of `overrides` layered over an underlying storage.
+    ///
+    /// An override replaces the underlying slot of the same key, an empty override deletes it.
+    /// `base` fetches a page of the underlying storage from `start` with the given limit and
+    /// `filter`. It is called once, with `limit` widened by the overrides at or after `start`,
+    /// which is the most the overrides can remove from the page.
+    pub fn overlay<E>(
+        overrides: impl IntoIterator<Item = (U256, FlaggedStorage)>,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+        base: impl FnOnce(U256, usize, StorageFilter) -> Result<Self, E>,
+    ) -> Result<Self, E> {
+        let mut overrides: Vec<_> = overrides
+            .into_iter()
+            .filter(|(key, _)| *key >= start)
+            .collect();
+        overrides.sort_unstable_by_key(|(key, _)| *key);
+
+        let base = base(start, limit.saturating_add(overrides.len()), filter)?;
+        // Overrides past the fetched window belong to a later page.
+        let window = overrides
+            .iter()
+            .take_while(|(key, _)| base.next_key.is_none_or(|next_key| *key < next_key))
+            .count();
+        overrides.truncate(window);
+
+        let mut slots: Vec<_> = base
+            .slots
+            .into_iter()
+            .filter(|(key, _)| {
+                overrides
+                    .binary_search_by_key(key, |(key, _)| *key)
+                    .is_err()
+            })
+            .chain(
+                overrides
+                    .into_iter()
+                    .filter(|(_, value)| !value.value.is_zero() && filter.matches(value)),
+            )
+            .collect();
+        slots.sort_unstable_by_key(|(key, _)| *key);
+
+        let next_key = slots.get(limit).map(|(key, _)| *key).or(base.next_key);
+        slots.truncate(limit);
+        Ok(Self { slots, next_key })
+    }
+}
+
+/// Database that can enumerate the storage of an account.
+///
+/// Empty slots are never listed, whatever their privacy flag.
+#[auto_impl(&, &mut, Box, Rc, Arc)]
+pub trait DatabaseIter {
+    /// The database error type.
+    type Error: DBErrorMarker + Error;
+
+    /// Returns up to `limit` slots of the account, in key order, starting at key `start`.
+    ///
+    /// Pass the returned [`StorageRange::next_key`] as `start` to fetch the next page.
+    fn storage_range(
+        &self,
+        address: Address,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+    ) -> Result<StorageRange, Self::Error>;
+
+    /// Returns all slots of the account, in key order.
+    fn storage_all(
+        &self,
+        address: Address,
+        filter: StorageFilter,
+    ) -> Result<Vec<(U256, FlaggedStorage)>, Self::Error> {
+        let mut slots = Vec::new();
+        let mut start = U256::ZERO;
+        loop {
+            let range = self.storage_range(address, start, STORAGE_ALL_PAGE_SIZE, filter)?;
+            slots.extend(range.slots);
+            match range.next_key {
+                Some(next_key) => start = next_key,
+                None => return Ok(slots),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    fn slot(key: u64, value: u64) -> (U256, FlaggedStorage) {
+        (
+            U256::from(key),
+            FlaggedStorage::new_from_value(U256::from(value)),
+        )
+    }
+
+    #[test]
+    fn overlay_fetches_only_the_page_window() {
+        let base: Vec<_> = (1..=10).map(|key| slot(key, key)).collect();
+        // Deletes slot 2, overrides slot 3, adds slot 11.
+        let overrides = [slot(2, 0), slot(3, 30), slot(11, 11)];
+
+        let mut pages = Vec::new();
+        let mut start = U256::ZERO;
+        loop {
+            let page = StorageRange::overlay(
+                overrides,
+                start,
+                3,
+                StorageFilter::All,
+                |start, limit, filter| {
+                    assert!(limit <= 3 + overrides.len());
+                    Ok::<_, Infallible>(StorageRange::paginate(
+                        base.iter().copied(),
+                        start,
+                        limit,
+                        filter,
+                    ))
+                },
+            )
+            .unwrap();
+            assert!(page.slots.len() <= 3);
+            pages.extend(page.slots);
+            match page.next_key {
+                Some(next_key) => start = next_key,
+                None => break,
+            }
+        }
+
+        let expected: Vec<_> = [1, 3, 4, 5, 6, 7, 8, 9, 10, 11]
+            .into_iter()
+            .map(|key| slot(key, if key == 3 { 30 } else { key }))
+            .collect();
+        assert_eq!(pages, expected);
+    }
+}
@@ -1,5 +1,7 @@
 use core::convert::Infallible;
-use database_interface::{Database, DatabaseCommit, DatabaseRef, EmptyDB};
+use database_interface::{
+    Database, DatabaseCommit, DatabaseIter, DatabaseRef, EmptyDB, StorageFilter, StorageRange,
+};
 use primitives::alloy_primitives::FlaggedStorage;
 use primitives::{address, hash_map::Entry, Address, HashMap, Log, B256, KECCAK_EMPTY, U256};
 use state::{Account, AccountInfo, Bytecode};
@@ -345,6 +347,33 @@ impl<ExtDB: DatabaseRef> DatabaseRef for CacheDB<ExtDB> {
     }
 }
 
+impl<ExtDB: DatabaseIter> DatabaseIter for CacheDB<ExtDB> {
+    type Error = ExtDB::Error;
+
+    fn storage_range(
+        &self,
+        address: Address,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+    ) -> Result<StorageRange, Self::Error> {
+        let Some(account) = self.cache.accounts.get(&address) else {
+            return self.db.storage_range(address, start, limit, filter);
+        };
+        let storage = account.storage.iter().map(|(key, value)| (*key, *value));
+        // Cached slots override the underlying database unless its storage is gone.
+        if matches!(
+            account.account_state,
+            AccountState::StorageCleared | AccountState::NotExisting
+        ) {
+            return Ok(StorageRange::paginate(storage, start, limit, filter));
+        }
+        StorageRange::overlay(storage, start, limit, filter, |start, limit, filter| {
+            self.db.storage_range(address, start, limit, filter)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DbAccount {
@@ -620,4 +649,71 @@ mod tests {
         assert_eq!(new_state.storage(account, key0), Ok(FlaggedStorage::ZERO));
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
+
+    #[test]
+    fn test_storage_range() {
+        let account = Address::with_last_byte(42);
+        let mut init_state = CacheDB::new(EmptyDB::default());
+        init_state.insert_account_info(account, AccountInfo::default());
+        for slot in 1..=4u64 {
+            let value = FlaggedStorage::new(U256::from(slot * 10), slot % 2 == 0);
+            init_state
+                .insert_account_storage(account, U256::from(slot), value)
+                .unwrap();
+        }
+
+        let mut new_state = CacheDB::new(init_state);
+        // Overrides slot 1 with a private value and clears slot 3.
+        new_state
+            .insert_account_storage(
+                account,
+                U256::from(1),
+                FlaggedStorage::new(U256::from(11), true),
+            )
+            .unwrap();
+        new_state
+            .insert_account_storage(account, U256::from(3), FlaggedStorage::ZERO)
+            .unwrap();
+
+        let page = new_state
+            .storage_range(account, U256::ZERO, 2, StorageFilter::All)
+            .unwrap();
+        assert_eq!(
+            page.slots,
+            [
+                (U256::from(1), FlaggedStorage::new(U256::from(11), true)),
+                (U256::from(2), FlaggedStorage::new(U256::from(20), true)),
+            ]
+        );
+        assert_eq!(page.next_key, Some(U256::from(4)));
+
+        let page = new_state
+            .storage_range(account, U256::from(4), 2, StorageFilter::All)
+            .unwrap();
+        assert_eq!(page.slots.len(), 1);
+        assert_eq!(page.next_key, None);
+
+        let private = new_state
+            .storage_all(account, StorageFilter::Private)
+            .unwrap();
+        assert_eq!(
+            private.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+            [U256::from(1), U256::from(2), U256::from(4)]
+        );
+        assert!(new_state
+            .storage_all(account, StorageFilter::Public)
+            .unwrap()
+            .is_empty());
+
+        new_state
+            .replace_account_storage(
+                account,
+                [(U256::from(9), FlaggedStorage::from(U256::from(1)))].into(),
+            )
+            .unwrap();
+        assert_eq!(
+            new_state.storage_all(account, StorageFilter::All).unwrap(),
+            [(U256::from(9), FlaggedStorage::from(U256::from(1)))]
+        );
+    }
 }
@@ -10,14 +10,20 @@ mod alloydb;
 pub use database_interface::*;
 
 pub mod in_memory_db;
+pub mod metered;
 pub mod states;
+pub mod trie;
 
 #[cfg(feature = "alloydb")]
 pub use alloydb::{AlloyDB, BlockId, DBTransportError};
 
 pub use in_memory_db::*;
+pub use metered::{DatabaseMetricsReport, MeteredDatabase};
 pub use states::{
-    AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox,
+    AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox, HistoricalState,
     OriginalValuesKnown, PlainAccount, RevertToSlot, State, StateBuilder, StateDBBox,
     StorageWithOriginalValues, TransitionAccount, TransitionState,
 };
+pub use trie::{
+    AccountProof, ProofVerificationError, StateTrie, StorageProof, StorageProofValue, TrieAccount,
+};
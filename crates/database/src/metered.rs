@@ -0,0 +1,832 @@
+//! Database wrapper that records how a database is accessed.
+//!
+//! [`MeteredDatabase`] counts every `basic`, `code_by_hash`, `storage` and `block_hash` call that
+//! goes through it, splits storage reads into public and private ones, and, with `std`, records
+//! a latency histogram per method. It works over [`Database`], [`DatabaseRef`] and, with the
+//! `asyncdb` feature, over the async database traits.
+//!
+//! When the wrapped database is a [`CacheDB`] or a [`State`], use
+//! [`MeteredDatabase::with_cache_stats`] to also count how many calls were served from the cache.
+//! Metrics are kept in atomics, so they can be read while the database is in use, and
+//! [`MeteredDatabase::report`] exports them as a plain [`DatabaseMetricsReport`].
+use crate::{CacheDB, State};
+use core::sync::atomic::{AtomicU64, Ordering};
+use database_interface::{Database, DatabaseCommit, DatabaseRef};
+use primitives::{Address, FlaggedStorage, HashMap, B256, U256};
+use state::{Account, AccountInfo, Bytecode};
+
+/// Number of latency buckets, the last one holds every sample above the largest bound.
+pub const LATENCY_BUCKETS: usize = 17;
+
+/// Upper bound, in nanoseconds, of the latency bucket at `index`.
+///
+/// Bounds double from 1µs up to ~65ms. Returns `None` for the overflow bucket.
+pub const fn latency_bucket_upper_bound_ns(index: usize) -> Option<u64> {
+    if index + 1 >= LATENCY_BUCKETS {
+        None
+    } else {
+        Some(1_000 << index)
+    }
+}
+
+/// Database lookup, used to ask a cache whether it would serve it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DatabaseLookup {
+    /// Account info lookup.
+    Basic(Address),
+    /// Bytecode lookup.
+    CodeByHash(B256),
+    /// Storage slot lookup.
+    Storage(Address, U256),
+    /// Block hash lookup.
+    BlockHash(u64),
+}
+
+/// Database with an in-memory cache in front of another database.
+pub trait CacheProbe {
+    /// Returns `true` if the lookup would be served from the cache, without loading anything.
+    fn is_cached(&self, lookup: &DatabaseLookup) -> bool;
+}
+
+impl<ExtDB> CacheProbe for CacheDB<ExtDB> {
+    fn is_cached(&self, lookup: &DatabaseLookup) -> bool {
+        let cache = &self.cache;
+        match lookup {
+            DatabaseLookup::Basic(address) => cache.accounts.contains_key(address),
+            DatabaseLookup::CodeByHash(code_hash) => cache.contracts.contains_key(code_hash),
+            DatabaseLookup::Storage(address, index) => {
+                cache.accounts.get(address).is_some_and(|account| {
+                    account.account_state.is_storage_cleared()
+                        || account.info().is_none()
+                        || account.storage.contains_key(index)
+                })
+            }
+            DatabaseLookup::BlockHash(number) => {
+                cache.block_hashes.contains_key(&U256::from(*number))
+            }
+        }
+    }
+}
+
+impl<DB> CacheProbe for State<DB> {
+    fn is_cached(&self, lookup: &DatabaseLookup) -> bool {
+        match lookup {
+            DatabaseLookup::Basic(address) => {
+                self.cache.accounts.contains_key(address)
+                    || (self.use_preloaded_bundle && self.bundle_state.account(address).is_some())
+            }
+            DatabaseLookup::CodeByHash(code_hash) => {
+                self.cache.contracts.contains_key(code_hash)
+                    || (self.use_preloaded_bundle
+                        && self.bundle_state.contracts.contains_key(code_hash))
+            }
+            DatabaseLookup::Storage(address, index) => {
+                self.cache.accounts.get(address).is_some_and(|account| {
+                    account.status.is_storage_known()
+                        || account
+                            .account
+                            .as_ref()
+                            .map_or(true, |account| account.storage.contains_key(index))
+                })
+            }
+            DatabaseLookup::BlockHash(number) => self.block_hashes.contains_key(number),
+        }
+    }
+}
+
+/// Latency histogram with exponentially growing buckets.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+    total_ns: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// Records a sample.
+    pub fn record_ns(&self, ns: u64) {
+        let index = (0..LATENCY_BUCKETS)
+            .find(|index| latency_bucket_upper_bound_ns(*index).map_or(true, |bound| ns <= bound))
+            .unwrap_or(LATENCY_BUCKETS - 1);
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.total_ns.fetch_add(ns, Ordering::Relaxed);
+    }
+
+    /// Returns a copy of the histogram.
+    pub fn report(&self) -> LatencyReport {
+        LatencyReport {
+            buckets: core::array::from_fn(|index| self.buckets[index].load(Ordering::Relaxed)),
+            total_ns: self.total_ns.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.buckets
+            .iter()
+            .for_each(|bucket| bucket.store(0, Ordering::Relaxed));
+        self.total_ns.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Metrics of one database method.
+#[derive(Debug, Default)]
+pub struct MethodMetrics {
+    calls: AtomicU64,
+    cache_hits: AtomicU64,
+    errors: AtomicU64,
+    latency: LatencyHistogram,
+}
+
+impl MethodMetrics {
+    fn record<T, E>(&self, cache_hit: bool, result: &Result<T, E>, elapsed_ns: Option<u64>) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if cache_hit {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+        }
+        if result.is_err() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(ns) = elapsed_ns {
+            self.latency.record_ns(ns);
+        }
+    }
+
+    /// Returns a copy of the metrics.
+    ///
+    /// Counters are read one by one while calls may still be recorded, so the copy is not an
+    /// exact snapshot. Hits are read before calls, which are counted first, to keep them below.
+    pub fn report(&self) -> MethodReport {
+        let cache_hits = self.cache_hits.load(Ordering::Relaxed);
+        let errors = self.errors.load(Ordering::Relaxed);
+        let latency = self.latency.report();
+        MethodReport {
+            calls: self.calls.load(Ordering::Relaxed),
+            cache_hits,
+            errors,
+            latency,
+        }
+    }
+
+    fn reset(&self) {
+        self.calls.store(0, Ordering::Relaxed);
+        self.cache_hits.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+        self.latency.reset();
+    }
+}
+
+/// Metrics of all database methods.
+#[derive(Debug, Default)]
+pub struct DatabaseMetrics {
+    /// `basic` calls.
+    pub basic: MethodMetrics,
+    /// `code_by_hash` calls.
+    pub code_by_hash: MethodMetrics,
+    /// `storage` calls.
+    pub storage: MethodMetrics,
+    /// Successful `storage` calls that returned a public slot.
+    pub storage_public: AtomicU64,
+    /// Successful `storage` calls that returned a private slot.
+    pub storage_private: AtomicU64,
+    /// `block_hash` calls.
+    pub block_hash: MethodMetrics,
+}
+
+impl DatabaseMetrics {
+    /// Returns a copy of the metrics.
+    pub fn report(&self) -> DatabaseMetricsReport {
+        DatabaseMetricsReport {
+            basic: self.basic.report(),
+            code_by_hash: self.code_by_hash.report(),
+            storage: self.storage.report(),
+            storage_public: self.storage_public.load(Ordering::Relaxed),
+            storage_private: self.storage_private.load(Ordering::Relaxed),
+            block_hash: self.block_hash.report(),
+        }
+    }
+
+    /// Resets all metrics to zero.
+    pub fn reset(&self) {
+        self.basic.reset();
+        self.code_by_hash.reset();
+        self.storage.reset();
+        self.storage_public.store(0, Ordering::Relaxed);
+        self.storage_private.store(0, Ordering::Relaxed);
+        self.block_hash.reset();
+    }
+
+    fn record_storage<E>(
+        &self,
+        cache_hit: bool,
+        result: &Result<FlaggedStorage, E>,
+        elapsed_ns: Option<u64>,
+    ) {
+        self.storage.record(cache_hit, result, elapsed_ns);
+        match result {
+            Ok(value) if value.is_private => &self.storage_private,
+            Ok(_) => &self.storage_public,
+            Err(_) => return,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Exported latency histogram.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatencyReport {
+    /// Sample count per bucket, see [`latency_bucket_upper_bound_ns`] for the bounds.
+    pub buckets: [u64; LATENCY_BUCKETS],
+    /// Sum of all samples in nanoseconds.
+    pub total_ns: u64,
+}
+
+impl LatencyReport {
+    /// Returns the number of samples.
+    pub fn samples(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// Returns the mean latency in nanoseconds, or zero without samples.
+    pub fn mean_ns(&self) -> u64 {
+        self.total_ns
+            .checked_div(self.samples())
+            .unwrap_or_default()
+    }
+}
+
+/// Exported metrics of one database method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MethodReport {
+    /// Number of calls.
+    pub calls: u64,
+    /// Number of calls served from the cache of the wrapped database.
+    ///
+    /// Always zero unless the wrapper was built with [`MeteredDatabase::with_cache_stats`].
+    pub cache_hits: u64,
+    /// Number of calls that returned an error.
+    pub errors: u64,
+    /// Call latency, empty without `std`.
+    pub latency: LatencyReport,
+}
+
+impl MethodReport {
+    /// Returns the number of calls not served from the cache.
+    pub fn cache_misses(&self) -> u64 {
+        self.calls.saturating_sub(self.cache_hits)
+    }
+}
+
+/// Exported metrics of all database methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatabaseMetricsReport {
+    /// `basic` calls.
+    pub basic: MethodReport,
+    /// `code_by_hash` calls.
+    pub code_by_hash: MethodReport,
+    /// `storage` calls.
+    pub storage: MethodReport,
+    /// Successful `storage` calls that returned a public slot.
+    pub storage_public: u64,
+    /// Successful `storage` calls that returned a private slot.
+    pub storage_private: u64,
+    /// `block_hash` calls.
+    pub block_hash: MethodReport,
+}
+
+/// Database wrapper that records [`DatabaseMetrics`] for every call.
+#[derive(Debug)]
+pub struct MeteredDatabase<DB> {
+    inner: DB,
+    metrics: DatabaseMetrics,
+    cache_probe: Option<fn(&DB, &DatabaseLookup) -> bool>,
+}
+
+impl<DB> MeteredDatabase<DB> {
+    /// Wraps the database.
+    pub fn new(inner: DB) -> Self {
+        Self {
+            inner,
+            metrics: DatabaseMetrics::default(),
+            cache_probe: None,
+        }
+    }
+
+    /// Returns a reference to the wrapped database.
+    pub fn inner(&self) -> &DB {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped database.
+    pub fn inner_mut(&mut self) -> &mut DB {
+        &mut self.inner
+    }
+
+    /// Unwraps the database, dropping the metrics.
+    pub fn into_inner(self) -> DB {
+        self.inner
+    }
+
+    /// Returns the live metrics.
+    pub fn metrics(&self) -> &DatabaseMetrics {
+        &self.metrics
+    }
+
+    /// Returns a copy of the metrics.
+    pub fn report(&self) -> DatabaseMetricsReport {
+        self.metrics.report()
+    }
+
+    /// Resets all metrics to zero.
+    pub fn reset(&self) {
+        self.metrics.reset()
+    }
+
+    fn is_cached(&self, lookup: DatabaseLookup) -> bool {
+        self.cache_probe
+            .is_some_and(|is_cached| is_cached(&self.inner, &lookup))
+    }
+}
+
+impl<DB: CacheProbe> MeteredDatabase<DB> {
+    /// Wraps a caching database, also counting the calls served from its cache.
+    pub fn with_cache_stats(inner: DB) -> Self {
+        Self {
+            cache_probe: Some(DB::is_cached),
+            ..Self::new(inner)
+        }
+    }
+}
+
+/// Runs `f` and returns its output with the elapsed nanoseconds, if time is available.
+#[inline]
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    #[cfg(feature = "std")]
+    {
+        let start = std::time::Instant::now();
+        let output = f();
+        (output, Some(elapsed_ns(start)))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "std")]
+fn elapsed_ns(start: std::time::Instant) -> u64 {
+    start.elapsed().as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl<DB: Database> Database for MeteredDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::Basic(address));
+        let (result, elapsed) = timed(|| self.inner.basic(address));
+        self.metrics.basic.record(hit, &result, elapsed);
+        result
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::CodeByHash(code_hash));
+        let (result, elapsed) = timed(|| self.inner.code_by_hash(code_hash));
+        self.metrics.code_by_hash.record(hit, &result, elapsed);
+        result
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<FlaggedStorage, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::Storage(address, index));
+        let (result, elapsed) = timed(|| self.inner.storage(address, index));
+        self.metrics.record_storage(hit, &result, elapsed);
+        result
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::BlockHash(number));
+        let (result, elapsed) = timed(|| self.inner.block_hash(number));
+        self.metrics.block_hash.record(hit, &result, elapsed);
+        result
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for MeteredDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::Basic(address));
+        let (result, elapsed) = timed(|| self.inner.basic_ref(address));
+        self.metrics.basic.record(hit, &result, elapsed);
+        result
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::CodeByHash(code_hash));
+        let (result, elapsed) = timed(|| self.inner.code_by_hash_ref(code_hash));
+        self.metrics.code_by_hash.record(hit, &result, elapsed);
+        result
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<FlaggedStorage, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::Storage(address, index));
+        let (result, elapsed) = timed(|| self.inner.storage_ref(address, index));
+        self.metrics.record_storage(hit, &result, elapsed);
+        result
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        let hit = self.is_cached(DatabaseLookup::BlockHash(number));
+        let (result, elapsed) = timed(|| self.inner.block_hash_ref(number));
+        self.metrics.block_hash.record(hit, &result, elapsed);
+        result
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for MeteredDatabase<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.inner.commit(changes)
+    }
+}
+
+#[cfg(feature = "asyncdb")]
+mod async_impls {
+    use super::*;
+    use core::future::Future;
+    use database_interface::async_db::{DatabaseAsync, DatabaseAsyncRef};
+    use std::time::Instant;
+
+    impl<DB: DatabaseAsync + Send> DatabaseAsync for MeteredDatabase<DB> {
+        type Error = DB::Error;
+
+        fn basic_async(
+            &mut self,
+            address: Address,
+        ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::Basic(address));
+                let start = Instant::now();
+                let result = self.inner.basic_async(address).await;
+                self.metrics
+                    .basic
+                    .record(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+
+        fn code_by_hash_async(
+            &mut self,
+            code_hash: B256,
+        ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::CodeByHash(code_hash));
+                let start = Instant::now();
+                let result = self.inner.code_by_hash_async(code_hash).await;
+                self.metrics
+                    .code_by_hash
+                    .record(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+
+        fn storage_async(
+            &mut self,
+            address: Address,
+            index: U256,
+        ) -> impl Future<Output = Result<FlaggedStorage, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::Storage(address, index));
+                let start = Instant::now();
+                let result = self.inner.storage_async(address, index).await;
+                self.metrics
+                    .record_storage(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+
+        fn block_hash_async(
+            &mut self,
+            number: u64,
+        ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::BlockHash(number));
+                let start = Instant::now();
+                let result = self.inner.block_hash_async(number).await;
+                self.metrics
+                    .block_hash
+                    .record(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+    }
+
+    impl<DB: DatabaseAsyncRef + Sync> DatabaseAsyncRef for MeteredDatabase<DB> {
+        type Error = DB::Error;
+
+        fn basic_async_ref(
+            &self,
+            address: Address,
+        ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::Basic(address));
+                let start = Instant::now();
+                let result = self.inner.basic_async_ref(address).await;
+                self.metrics
+                    .basic
+                    .record(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+
+        fn code_by_hash_async_ref(
+            &self,
+            code_hash: B256,
+        ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::CodeByHash(code_hash));
+                let start = Instant::now();
+                let result = self.inner.code_by_hash_async_ref(code_hash).await;
+                self.metrics
+                    .code_by_hash
+                    .record(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+
+        fn storage_async_ref(
+            &self,
+            address: Address,
+            index: U256,
+        ) -> impl Future<Output = Result<FlaggedStorage, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::Storage(address, index));
+                let start = Instant::now();
+                let result = self.inner.storage_async_ref(address, index).await;
+                self.metrics
+                    .record_storage(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+
+        fn block_hash_async_ref(
+            &self,
+            number: u64,
+        ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+            async move {
+                let hit = self.is_cached(DatabaseLookup::BlockHash(number));
+                let start = Instant::now();
+                let result = self.inner.block_hash_async_ref(number).await;
+                self.metrics
+                    .block_hash
+                    .record(hit, &result, Some(elapsed_ns(start)));
+                result
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmptyDB, InMemoryDB};
+
+    #[test]
+    fn test_counts_calls_without_cache_stats() {
+        let mut db = MeteredDatabase::new(EmptyDB::default());
+        db.basic(Address::ZERO).unwrap();
+        db.basic(Address::ZERO).unwrap();
+        db.storage(Address::ZERO, U256::ZERO).unwrap();
+        db.block_hash_ref(1).unwrap();
+
+        let report = db.report();
+        assert_eq!(report.basic.calls, 2);
+        assert_eq!(report.basic.cache_hits, 0);
+        assert_eq!(report.storage.calls, 1);
+        assert_eq!(report.storage_public, 1);
+        assert_eq!(report.storage_private, 0);
+        assert_eq!(report.block_hash.calls, 1);
+        assert_eq!(report.code_by_hash, MethodReport::default());
+        assert_eq!(report.basic.latency.samples(), 2);
+    }
+
+    #[test]
+    fn test_cache_db_hits_and_private_storage() {
+        let address = Address::with_last_byte(1);
+        let mut cache = InMemoryDB::default();
+        cache.insert_account_info(address, AccountInfo::default());
+        cache
+            .insert_account_storage(
+                address,
+                U256::from(1),
+                FlaggedStorage::new(U256::from(7), true),
+            )
+            .unwrap();
+
+        let mut db = MeteredDatabase::with_cache_stats(cache);
+        db.basic(address).unwrap();
+        db.basic(Address::with_last_byte(2)).unwrap();
+        db.basic(Address::with_last_byte(2)).unwrap();
+        db.storage(address, U256::from(1)).unwrap();
+        db.storage(address, U256::from(2)).unwrap();
+        db.block_hash(1).unwrap();
+        db.block_hash(1).unwrap();
+
+        let report = db.report();
+        assert_eq!(report.basic.calls, 3);
+        assert_eq!(report.basic.cache_hits, 2);
+        assert_eq!(report.basic.cache_misses(), 1);
+        assert_eq!(report.storage.calls, 2);
+        assert_eq!(report.storage.cache_hits, 1);
+        assert_eq!(report.storage_private, 1);
+        assert_eq!(report.storage_public, 1);
+        assert_eq!(report.block_hash.cache_hits, 1);
+
+        db.reset();
+        assert_eq!(db.report(), DatabaseMetricsReport::default());
+    }
+
+    #[test]
+    fn test_state_hits() {
+        let address = Address::with_last_byte(1);
+        let mut db = MeteredDatabase::with_cache_stats(State::builder().build());
+        db.basic(address).unwrap();
+        db.basic(address).unwrap();
+        db.storage(address, U256::ZERO).unwrap();
+
+        let report = db.report();
+        assert_eq!(report.basic.cache_hits, 1);
+        // Not existing accounts have known, empty storage.
+        assert_eq!(report.storage.cache_hits, 1);
+    }
+
+    #[test]
+    fn test_cache_misses_saturate() {
+        let report = MethodReport {
+            calls: 1,
+            cache_hits: 2,
+            ..Default::default()
+        };
+        assert_eq!(report.cache_misses(), 0);
+    }
+
+    #[cfg(feature = "asyncdb")]
+    mod async_db {
+        use super::*;
+        use core::{convert::Infallible, future::Future};
+        use database_interface::async_db::{DatabaseAsync, DatabaseAsyncRef};
+        use tokio::runtime::Runtime;
+
+        /// In-memory database behind the async traits.
+        struct AsyncInMemoryDB(InMemoryDB);
+
+        impl CacheProbe for AsyncInMemoryDB {
+            fn is_cached(&self, lookup: &DatabaseLookup) -> bool {
+                self.0.is_cached(lookup)
+            }
+        }
+
+        impl DatabaseAsync for AsyncInMemoryDB {
+            type Error = Infallible;
+
+            fn basic_async(
+                &mut self,
+                address: Address,
+            ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send {
+                async move { self.0.basic(address) }
+            }
+
+            fn code_by_hash_async(
+                &mut self,
+                code_hash: B256,
+            ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send {
+                async move { self.0.code_by_hash(code_hash) }
+            }
+
+            fn storage_async(
+                &mut self,
+                address: Address,
+                index: U256,
+            ) -> impl Future<Output = Result<FlaggedStorage, Self::Error>> + Send {
+                async move { self.0.storage(address, index) }
+            }
+
+            fn block_hash_async(
+                &mut self,
+                number: u64,
+            ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+                async move { self.0.block_hash(number) }
+            }
+        }
+
+        impl DatabaseAsyncRef for AsyncInMemoryDB {
+            type Error = Infallible;
+
+            fn basic_async_ref(
+                &self,
+                address: Address,
+            ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send {
+                async move { self.0.basic_ref(address) }
+            }
+
+            fn code_by_hash_async_ref(
+                &self,
+                code_hash: B256,
+            ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send {
+                async move { self.0.code_by_hash_ref(code_hash) }
+            }
+
+            fn storage_async_ref(
+                &self,
+                address: Address,
+                index: U256,
+            ) -> impl Future<Output = Result<FlaggedStorage, Self::Error>> + Send {
+                async move { self.0.storage_ref(address, index) }
+            }
+
+            fn block_hash_async_ref(
+                &self,
+                number: u64,
+            ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+                async move { self.0.block_hash_ref(number) }
+            }
+        }
+
+        fn metered_db() -> (MeteredDatabase<AsyncInMemoryDB>, Runtime) {
+            let address = Address::with_last_byte(1);
+            let mut cache = InMemoryDB::default();
+            cache.insert_account_info(address, AccountInfo::default());
+            cache
+                .insert_account_storage(
+                    address,
+                    U256::from(1),
+                    FlaggedStorage::new(U256::from(7), true),
+                )
+                .unwrap();
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            (
+                MeteredDatabase::with_cache_stats(AsyncInMemoryDB(cache)),
+                runtime,
+            )
+        }
+
+        fn assert_report(report: DatabaseMetricsReport) {
+            assert_eq!(report.basic.calls, 2);
+            assert_eq!(report.basic.cache_hits, 1);
+            assert_eq!(report.basic.cache_misses(), 1);
+            assert_eq!(report.storage.calls, 1);
+            assert_eq!(report.storage.cache_hits, 1);
+            assert_eq!(report.storage_private, 1);
+            assert_eq!(report.block_hash.calls, 1);
+            assert_eq!(report.basic.latency.samples(), 2);
+        }
+
+        #[test]
+        fn test_async_calls_are_metered() {
+            let (mut db, runtime) = metered_db();
+            runtime.block_on(async {
+                db.basic_async(Address::with_last_byte(1)).await.unwrap();
+                db.basic_async(Address::with_last_byte(2)).await.unwrap();
+                db.storage_async(Address::with_last_byte(1), U256::from(1))
+                    .await
+                    .unwrap();
+                db.block_hash_async(1).await.unwrap();
+            });
+            assert_report(db.report());
+        }
+
+        #[test]
+        fn test_async_ref_calls_are_metered() {
+            let (db, runtime) = metered_db();
+            runtime.block_on(async {
+                db.basic_async_ref(Address::with_last_byte(1))
+                    .await
+                    .unwrap();
+                db.basic_async_ref(Address::with_last_byte(2))
+                    .await
+                    .unwrap();
+                db.storage_async_ref(Address::with_last_byte(1), U256::from(1))
+                    .await
+                    .unwrap();
+                db.block_hash_async_ref(1).await.unwrap();
+            });
+            assert_report(db.report());
+        }
+    }
+
+    #[test]
+    fn test_latency_buckets() {
+        let histogram = LatencyHistogram::default();
+        histogram.record_ns(500);
+        histogram.record_ns(1_500);
+        histogram.record_ns(u64::MAX / 2);
+
+        let report = histogram.report();
+        assert_eq!(report.buckets[0], 1);
+        assert_eq!(report.buckets[1], 1);
+        assert_eq!(report.buckets[LATENCY_BUCKETS - 1], 1);
+        assert_eq!(report.samples(), 3);
+        assert_eq!(latency_bucket_upper_bound_ns(LATENCY_BUCKETS - 1), None);
+    }
+}
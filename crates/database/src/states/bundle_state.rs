@@ -5,7 +5,8 @@ use super::{
     TransitionState,
 };
 use bytecode::Bytecode;
-use core::{mem, ops::RangeInclusive};
+use core::{convert::Infallible, mem, ops::RangeInclusive};
+use database_interface::{DatabaseIter, StorageFilter, StorageRange};
 use primitives::{alloy_primitives::FlaggedStorage, StorageKey};
 use primitives::{hash_map::Entry, Address, HashMap, HashSet, B256, KECCAK_EMPTY, U256};
 use state::AccountInfo;
@@ -868,6 +869,29 @@ impl BundleState {
     }
 }
 
+impl DatabaseIter for BundleState {
+    type Error = Infallible;
+
+    /// Lists the present value of the slots tracked by the bundle.
+    ///
+    /// Slots the bundle never touched are not listed, as it does not know them.
+    fn storage_range(
+        &self,
+        address: Address,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+    ) -> Result<StorageRange, Self::Error> {
+        let slots = self.account(&address).into_iter().flat_map(|account| {
+            account
+                .storage
+                .iter()
+                .map(|(key, slot)| (*key, slot.present_value))
+        });
+        Ok(StorageRange::paginate(slots, start, limit, filter))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
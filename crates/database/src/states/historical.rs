@@ -0,0 +1,242 @@
+use super::{AccountStatus, BundleAccount, BundleState};
+use bytecode::Bytecode;
+use database_interface::{Database, DatabaseRef};
+use primitives::alloy_primitives::FlaggedStorage;
+use primitives::{hash_map::Entry, Address, HashMap, B256, U256};
+use state::AccountInfo;
+
+/// Read-only view of the state as it was a number of transitions before the tip of a
+/// [`BundleState`].
+///
+/// The view applies the bundle [`Reverts`](super::reverts::Reverts) in reverse, latest first,
+/// without touching the bundle: only the accounts named by the applied reverts are copied.
+/// Privacy flags are restored along with the storage values.
+///
+/// Lookups not answered by the reverted bundle fall through to `database`, which must hold the
+/// state the bundle was built on top of.
+#[derive(Debug)]
+pub struct HistoricalState<'a, DB> {
+    bundle: &'a BundleState,
+    /// Accounts changed by the applied reverts, `None` if the account left the bundle.
+    reverted: HashMap<Address, Option<BundleAccount>>,
+    reverted_transitions: usize,
+    database: DB,
+}
+
+impl<'a, DB> HistoricalState<'a, DB> {
+    /// Creates a view of the state `num_transitions` transitions before the tip of the bundle.
+    ///
+    /// If the bundle holds fewer reverts, the view goes back as far as it can, see
+    /// [`HistoricalState::reverted_transitions`].
+    pub fn new(bundle: &'a BundleState, database: DB, num_transitions: usize) -> Self {
+        let mut reverted: HashMap<Address, Option<BundleAccount>> = HashMap::default();
+        let reverts = bundle.reverts.iter().rev().take(num_transitions);
+        let reverted_transitions = reverts.len();
+        for (address, revert) in reverts.flatten() {
+            let account = match reverted.entry(*address) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(bundle.account(address).cloned()),
+            };
+            // Same as `BundleState::revert_latest`, an account missing from the bundle can only
+            // be reverted from a not existing one.
+            let mut bundle_account = account.take().unwrap_or_else(|| {
+                BundleAccount::new(
+                    None,
+                    None,
+                    HashMap::default(),
+                    AccountStatus::LoadedNotExisting,
+                )
+            });
+            if !bundle_account.revert(revert.clone()) {
+                *account = Some(bundle_account);
+            }
+        }
+        Self {
+            bundle,
+            reverted,
+            reverted_transitions,
+            database,
+        }
+    }
+
+    /// Returns the number of transitions actually reverted.
+    pub fn reverted_transitions(&self) -> usize {
+        self.reverted_transitions
+    }
+
+    /// Returns the reverted bundle account, if the bundle knows the account at that point.
+    pub fn account(&self, address: &Address) -> Option<&BundleAccount> {
+        match self.reverted.get(address) {
+            Some(account) => account.as_ref(),
+            None => self.bundle.account(address),
+        }
+    }
+
+    /// Returns the underlying database.
+    pub fn into_database(self) -> DB {
+        self.database
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for HistoricalState<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.account(&address) {
+            Some(account) => Ok(account.account_info()),
+            None => self.database.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.bundle.contracts.get(&code_hash) {
+            Some(code) => Ok(code.clone()),
+            None => self.database.code_by_hash_ref(code_hash),
+        }
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<FlaggedStorage, Self::Error> {
+        match self
+            .account(&address)
+            .and_then(|account| account.storage_slot(index))
+        {
+            Some(value) => Ok(value),
+            None => self.database.storage_ref(address, index),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.database.block_hash_ref(number)
+    }
+}
+
+impl<DB: DatabaseRef> Database for HistoricalState<'_, DB> {
+    type Error = DB::Error;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    #[inline]
+    fn storage(&mut self, address: Address, index: U256) -> Result<FlaggedStorage, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{states::bundle_state::BundleRetention, InMemoryDB, State};
+    use database_interface::DatabaseCommit;
+    use state::{Account, AccountStatus as EvmAccountStatus, EvmStorageSlot};
+
+    /// Commits one transition that sets `slot` of `address` and bumps its nonce.
+    fn transition(
+        state: &mut State<InMemoryDB>,
+        address: Address,
+        slot: U256,
+        value: FlaggedStorage,
+    ) {
+        let mut info = state.basic(address).unwrap().unwrap_or_default();
+        let original = state.storage(address, slot).unwrap();
+        info.nonce += 1;
+        state.commit(HashMap::from_iter([(
+            address,
+            Account {
+                info,
+                storage: HashMap::from_iter([(slot, EvmStorageSlot::new_changed(original, value))]),
+                status: EvmAccountStatus::Touched,
+            },
+        )]));
+        state.merge_transitions(BundleRetention::Reverts);
+    }
+
+    #[test]
+    fn reverts_in_reverse_with_privacy_flags() {
+        let address = Address::with_last_byte(1);
+        let slot = U256::from(7);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(address, slot, FlaggedStorage::new_from_value(U256::from(1)))
+            .unwrap();
+
+        let mut state = State::builder()
+            .with_database(db.clone())
+            .with_bundle_update()
+            .build();
+        transition(
+            &mut state,
+            address,
+            slot,
+            FlaggedStorage::new(U256::from(2), true),
+        );
+        transition(
+            &mut state,
+            address,
+            slot,
+            FlaggedStorage::new(U256::from(3), false),
+        );
+        let new_address = Address::with_last_byte(2);
+        transition(
+            &mut state,
+            new_address,
+            slot,
+            FlaggedStorage::new(U256::from(4), true),
+        );
+        let bundle = state.take_bundle();
+
+        let mut tip = HistoricalState::new(&bundle, &db, 0);
+        assert_eq!(tip.basic(address).unwrap().unwrap().nonce, 3);
+        assert_eq!(
+            tip.storage(address, slot),
+            Ok(FlaggedStorage::new(U256::from(3), false))
+        );
+        assert_eq!(tip.basic(new_address).unwrap().unwrap().nonce, 1);
+
+        let mut view = HistoricalState::new(&bundle, &db, 2);
+        assert_eq!(view.reverted_transitions(), 2);
+        assert_eq!(view.basic(address).unwrap().unwrap().nonce, 2);
+        assert_eq!(
+            view.storage(address, slot),
+            Ok(FlaggedStorage::new(U256::from(2), true))
+        );
+        assert_eq!(view.basic(new_address), Ok(None));
+        assert_eq!(view.storage(new_address, slot), Ok(FlaggedStorage::ZERO));
+
+        let mut genesis = HistoricalState::new(&bundle, &db, 10);
+        assert_eq!(genesis.reverted_transitions(), 3);
+        assert_eq!(genesis.basic(address).unwrap().unwrap().nonce, 1);
+        assert_eq!(
+            genesis.storage(address, slot),
+            Ok(FlaggedStorage::new_from_value(U256::from(1)))
+        );
+
+        // The view matches reverting the bundle itself.
+        let mut reverted_bundle = bundle.clone();
+        reverted_bundle.revert(2);
+        assert_eq!(
+            reverted_bundle
+                .account(&address)
+                .and_then(|account| account.storage_slot(slot)),
+            view.account(&address)
+                .and_then(|account| account.storage_slot(slot))
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use super::{
     bundle_state::BundleRetention, cache::CacheState, plain_account::PlainStorage, BundleState,
-    CacheAccount, StateBuilder, TransitionAccount, TransitionState,
+    CacheAccount, HistoricalState, StateBuilder, TransitionAccount, TransitionState,
 };
 use bytecode::Bytecode;
-use database_interface::{Database, DatabaseCommit, EmptyDB};
+use database_interface::{
+    Database, DatabaseCommit, DatabaseIter, DatabaseRef, EmptyDB, StorageFilter, StorageRange,
+};
 use primitives::alloy_primitives::FlaggedStorage;
 use primitives::{hash_map, Address, HashMap, B256, BLOCK_HASH_HISTORY, U256};
 use state::{Account, AccountInfo};
@@ -311,6 +313,72 @@ impl<DB: Database> DatabaseCommit for State<DB> {
     }
 }
 
+impl<DB: DatabaseRef> State<DB> {
+    /// Returns a read-only view of the state `num_transitions` transitions before the tip of the
+    /// bundle state.
+    ///
+    /// Only merged transitions are seen by the view, see [`State::merge_transitions`].
+    pub fn historical(&self, num_transitions: usize) -> HistoricalState<'_, &DB> {
+        HistoricalState::new(&self.bundle_state, &self.database, num_transitions)
+    }
+}
+
+impl<DB: DatabaseIter> State<DB> {
+    /// Returns a page of the account storage below the cache, from the preloaded bundle over
+    /// the database.
+    fn uncached_storage_range(
+        &self,
+        address: Address,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+    ) -> Result<StorageRange, DB::Error> {
+        let bundle_account = self
+            .use_preloaded_bundle
+            .then(|| self.bundle_state.account(&address))
+            .flatten();
+        let Some(account) = bundle_account else {
+            return self.database.storage_range(address, start, limit, filter);
+        };
+        let storage = account
+            .storage
+            .iter()
+            .map(|(key, slot)| (*key, slot.present_value));
+        if account.status.is_storage_known() {
+            return Ok(StorageRange::paginate(storage, start, limit, filter));
+        }
+        StorageRange::overlay(storage, start, limit, filter, |start, limit, filter| {
+            self.database.storage_range(address, start, limit, filter)
+        })
+    }
+}
+
+impl<DB: DatabaseIter> DatabaseIter for State<DB> {
+    type Error = DB::Error;
+
+    fn storage_range(
+        &self,
+        address: Address,
+        start: U256,
+        limit: usize,
+        filter: StorageFilter,
+    ) -> Result<StorageRange, Self::Error> {
+        let Some(cache_account) = self.cache.accounts.get(&address) else {
+            return self.uncached_storage_range(address, start, limit, filter);
+        };
+        let Some(account) = &cache_account.account else {
+            return Ok(StorageRange::default());
+        };
+        let storage = account.storage.iter().map(|(key, value)| (*key, *value));
+        if cache_account.status.is_storage_known() {
+            return Ok(StorageRange::paginate(storage, start, limit, filter));
+        }
+        StorageRange::overlay(storage, start, limit, filter, |start, limit, filter| {
+            self.uncached_storage_range(address, start, limit, filter)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -852,4 +920,66 @@ mod tests {
             )])])
         )
     }
+
+    #[test]
+    fn storage_range_over_bundle_prestate() {
+        let address = Address::with_last_byte(1);
+        let bundle = BundleState::builder(0..=0)
+            .state_present_account_info(address, AccountInfo::default())
+            .state_storage(
+                address,
+                HashMap::from_iter([
+                    (
+                        U256::from(1),
+                        (
+                            FlaggedStorage::ZERO,
+                            FlaggedStorage::new_from_value(U256::from(10)),
+                        ),
+                    ),
+                    (
+                        U256::from(2),
+                        (
+                            FlaggedStorage::ZERO,
+                            FlaggedStorage::new(U256::from(20), true),
+                        ),
+                    ),
+                ]),
+            )
+            .build();
+        let mut state = State::builder().with_bundle_prestate(bundle).build();
+
+        let private = state.storage_all(address, StorageFilter::Private).unwrap();
+        assert_eq!(
+            private,
+            [(U256::from(2), FlaggedStorage::new(U256::from(20), true))]
+        );
+
+        // Cached values override the bundle.
+        state
+            .load_cache_account(address)
+            .unwrap()
+            .account
+            .as_mut()
+            .unwrap()
+            .storage
+            .insert(U256::from(1), FlaggedStorage::new(U256::from(11), true));
+        let page = state
+            .storage_range(address, U256::ZERO, 1, StorageFilter::Private)
+            .unwrap();
+        assert_eq!(
+            page.slots,
+            [(U256::from(1), FlaggedStorage::new(U256::from(11), true))]
+        );
+        assert_eq!(page.next_key, Some(U256::from(2)));
+        assert!(state
+            .storage_all(address, StorageFilter::Public)
+            .unwrap()
+            .is_empty());
+
+        // Accounts not known to the cache, the bundle or the database have no storage.
+        assert!(state
+            .storage_all(Address::with_last_byte(2), StorageFilter::All)
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -0,0 +1,1091 @@
+//! Merkle-Patricia trie roots and inclusion proofs over plain state.
+//!
+//! [`StateTrie`] holds a plain snapshot of accounts and their flagged storage, usually taken from
+//! a [`BundleState`] or a [`CacheDB`], and computes Ethereum-style secure trie roots over it.
+//! Accounts are keyed by `keccak256(address)` and storage slots by `keccak256(slot)`.
+//!
+//! Public slots are encoded exactly like Ethereum, as `rlp(value)`. Private slots never put their
+//! value in the trie. Their leaf is `rlp([commitment, 0x01])`, where the commitment is
+//! [`private_slot_commitment`] salted with [`private_slot_salt`]. Salts derive from a secret
+//! commitment key held by the snapshot, so a commitment cannot be opened by guessing the value:
+//! the salt of a slot has to be handed out by the key holder. A proof for a private slot reveals
+//! only the commitment and the privacy flag. Slots holding zero are left out of the trie, public
+//! or private.
+//!
+//! Proofs list the RLP encoded nodes on the path from the root to the key, root first. Nodes that
+//! are shorter than 32 bytes are embedded in their parent and are not repeated in the proof.
+use crate::{states::plain_account::PlainStorage, BundleState, CacheDB, PlainAccount};
+use core::fmt;
+use primitives::{
+    alloy_primitives::FlaggedStorage, b256, keccak256, Address, Bytes, HashMap, B256, U256,
+};
+use state::AccountInfo;
+use std::{vec, vec::Vec};
+
+/// Root hash of an empty trie, `keccak256(rlp(""))`.
+pub const EMPTY_ROOT_HASH: B256 =
+    b256!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+
+/// Plain state snapshot that trie roots and proofs are computed from.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StateTrie {
+    accounts: HashMap<Address, PlainAccount>,
+    /// Secret the salts of private slots derive from.
+    commitment_key: B256,
+}
+
+impl fmt::Debug for StateTrie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateTrie")
+            .field("accounts", &self.accounts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StateTrie {
+    /// Creates an empty state snapshot committing to private slots with `commitment_key`.
+    ///
+    /// The key must stay secret and be the same wherever the roots have to agree.
+    pub fn new(commitment_key: B256) -> Self {
+        Self {
+            accounts: HashMap::default(),
+            commitment_key,
+        }
+    }
+
+    /// Takes present values of the bundle accounts. Destroyed accounts are skipped.
+    ///
+    /// Only the storage known to the bundle is included, which is the full storage of
+    /// accounts created in the bundle but not of accounts loaded from an external database.
+    pub fn from_bundle_state(bundle: &BundleState, commitment_key: B256) -> Self {
+        let accounts = bundle
+            .state()
+            .iter()
+            .filter_map(|(address, account)| {
+                let info = account.info.clone()?;
+                let storage = account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, value.present_value))
+                    .collect();
+                Some((*address, PlainAccount { info, storage }))
+            })
+            .collect();
+        Self {
+            accounts,
+            commitment_key,
+        }
+    }
+
+    /// Takes the accounts and storage held by the cache. Not existing accounts are skipped.
+    ///
+    /// The underlying database is not consulted, so the snapshot is complete only when the
+    /// whole state lives in the cache, as it does with [`InMemoryDB`](crate::InMemoryDB).
+    pub fn from_cache_db<ExtDB>(db: &CacheDB<ExtDB>, commitment_key: B256) -> Self {
+        let accounts = db
+            .cache
+            .accounts
+            .iter()
+            .filter_map(|(address, account)| {
+                let info = account.info()?;
+                let storage = account.storage.clone();
+                Some((*address, PlainAccount { info, storage }))
+            })
+            .collect();
+        Self {
+            accounts,
+            commitment_key,
+        }
+    }
+
+    /// Inserts or replaces the account info, keeping any storage already inserted.
+    pub fn insert_account(&mut self, address: Address, info: AccountInfo) {
+        self.accounts.entry(address).or_default().info = info;
+    }
+
+    /// Inserts a storage slot of the account.
+    pub fn insert_storage(&mut self, address: Address, slot: U256, value: FlaggedStorage) {
+        self.accounts
+            .entry(address)
+            .or_default()
+            .storage
+            .insert(slot, value);
+    }
+
+    /// Returns the accounts in the snapshot.
+    pub fn accounts(&self) -> &HashMap<Address, PlainAccount> {
+        &self.accounts
+    }
+
+    /// Returns the salt of a storage slot of the account, see [`private_slot_salt`].
+    ///
+    /// Handing it out lets the receiver open the commitment of that slot, and only that slot.
+    pub fn slot_salt(&self, address: Address, slot: U256) -> B256 {
+        private_slot_salt(&self.commitment_key, address, slot)
+    }
+
+    /// Computes the state root.
+    pub fn state_root(&self) -> B256 {
+        root_and_proof(&self.account_leaves(), None).0
+    }
+
+    /// Computes the storage root of the account, or [`EMPTY_ROOT_HASH`] if it does not exist.
+    pub fn storage_root(&self, address: &Address) -> B256 {
+        self.accounts
+            .get(address)
+            .map_or(EMPTY_ROOT_HASH, |account| {
+                storage_root(&self.storage_leaves(*address, &account.storage))
+            })
+    }
+
+    /// Generates a proof for the account and the given storage slots.
+    ///
+    /// If the account does not exist the proof is an exclusion proof and the storage proofs
+    /// prove absence against [`EMPTY_ROOT_HASH`].
+    pub fn account_proof(&self, address: Address, slots: &[U256]) -> AccountProof {
+        let key = unpack_nibbles(keccak256(address).as_slice());
+        let (_, proof) = root_and_proof(&self.account_leaves(), Some(&key));
+
+        let Some(account) = self.accounts.get(&address) else {
+            return AccountProof {
+                address,
+                account: None,
+                proof,
+                storage_proofs: slots
+                    .iter()
+                    .map(|slot| StorageProof {
+                        key: *slot,
+                        value: None,
+                        proof: Vec::new(),
+                    })
+                    .collect(),
+            };
+        };
+
+        let leaves = self.storage_leaves(address, &account.storage);
+        let storage_proofs = slots
+            .iter()
+            .map(|slot| {
+                let key = unpack_nibbles(keccak256(slot.to_be_bytes::<32>()).as_slice());
+                let (_, proof) = root_and_proof(&leaves, Some(&key));
+                StorageProof {
+                    key: *slot,
+                    value: account.storage.get(slot).and_then(|value| {
+                        StorageProofValue::new(self.slot_salt(address, *slot), *slot, *value)
+                    }),
+                    proof,
+                }
+            })
+            .collect();
+
+        AccountProof {
+            address,
+            account: Some(TrieAccount::new(&account.info, storage_root(&leaves))),
+            proof,
+            storage_proofs,
+        }
+    }
+
+    fn account_leaves(&self) -> Vec<Leaf> {
+        let mut leaves: Vec<Leaf> = self
+            .accounts
+            .iter()
+            .map(|(address, account)| {
+                let storage_root = storage_root(&self.storage_leaves(*address, &account.storage));
+                let trie_account = TrieAccount::new(&account.info, storage_root);
+                (
+                    unpack_nibbles(keccak256(address).as_slice()),
+                    trie_account.rlp_encode(),
+                )
+            })
+            .collect();
+        leaves.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        leaves
+    }
+
+    fn storage_leaves(&self, address: Address, storage: &PlainStorage) -> Vec<Leaf> {
+        let mut leaves: Vec<Leaf> = storage
+            .iter()
+            .filter_map(|(slot, value)| {
+                let value = StorageProofValue::new(self.slot_salt(address, *slot), *slot, *value)?;
+                Some((
+                    unpack_nibbles(keccak256(slot.to_be_bytes::<32>()).as_slice()),
+                    value.rlp_encode(),
+                ))
+            })
+            .collect();
+        leaves.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        leaves
+    }
+}
+
+/// Account as it is stored in the state trie.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrieAccount {
+    /// Account nonce.
+    pub nonce: u64,
+    /// Account balance.
+    pub balance: U256,
+    /// Root of the account storage trie.
+    pub storage_root: B256,
+    /// Hash of the account code.
+    pub code_hash: B256,
+}
+
+impl TrieAccount {
+    /// Creates the trie account from the account info and its storage root.
+    pub fn new(info: &AccountInfo, storage_root: B256) -> Self {
+        Self {
+            nonce: info.nonce,
+            balance: info.balance,
+            storage_root,
+            code_hash: info.code_hash,
+        }
+    }
+
+    /// Returns `rlp([nonce, balance, storage_root, code_hash])`.
+    pub fn rlp_encode(&self) -> Vec<u8> {
+        rlp::encode_list(&[
+            rlp::encode_u256(U256::from(self.nonce)),
+            rlp::encode_u256(self.balance),
+            rlp::encode_bytes(self.storage_root.as_slice()),
+            rlp::encode_bytes(self.code_hash.as_slice()),
+        ])
+    }
+}
+
+/// Value of a storage slot as revealed by a [`StorageProof`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageProofValue {
+    /// Public slot, its value is revealed.
+    Public(U256),
+    /// Private slot, only the commitment to its value is revealed.
+    Private {
+        /// Commitment to the slot value, see [`private_slot_commitment`].
+        commitment: B256,
+    },
+}
+
+impl StorageProofValue {
+    /// Creates the proof value of a slot, or `None` if the slot is zero and not in the trie.
+    ///
+    /// `salt` is the salt of the slot, only used if the slot is private.
+    pub fn new(salt: B256, slot: U256, value: FlaggedStorage) -> Option<Self> {
+        if value.value.is_zero() {
+            None
+        } else if value.is_private {
+            Some(Self::Private {
+                commitment: private_slot_commitment(salt, slot, value.value),
+            })
+        } else {
+            Some(Self::Public(value.value))
+        }
+    }
+
+    /// Returns `true` if the slot is private.
+    pub fn is_private(&self) -> bool {
+        matches!(self, Self::Private { .. })
+    }
+
+    /// Returns the leaf value stored in the storage trie.
+    pub fn rlp_encode(&self) -> Vec<u8> {
+        match self {
+            Self::Public(value) => rlp::encode_u256(*value),
+            Self::Private { commitment } => {
+                rlp::encode_list(&[rlp::encode_bytes(commitment.as_slice()), vec![0x01]])
+            }
+        }
+    }
+}
+
+/// Salt of a private slot, `keccak256(commitment_key || address || slot)`.
+///
+/// Every slot of every account gets its own salt, so revealing one salt opens one commitment.
+pub fn private_slot_salt(commitment_key: &B256, address: Address, slot: U256) -> B256 {
+    let mut preimage = [0u8; 84];
+    preimage[..32].copy_from_slice(commitment_key.as_slice());
+    preimage[32..52].copy_from_slice(address.as_slice());
+    preimage[52..].copy_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(preimage)
+}
+
+/// Commitment stored in the trie in place of a private slot value.
+///
+/// It is `keccak256(salt || slot || value)` with the words big-endian and the salt from
+/// [`private_slot_salt`]. It binds the value to the slot and, as long as the salt is secret,
+/// hides it even when the value is easy to guess.
+pub fn private_slot_commitment(salt: B256, slot: U256, value: U256) -> B256 {
+    let mut preimage = [0u8; 96];
+    preimage[..32].copy_from_slice(salt.as_slice());
+    preimage[32..64].copy_from_slice(&slot.to_be_bytes::<32>());
+    preimage[64..].copy_from_slice(&value.to_be_bytes::<32>());
+    keccak256(preimage)
+}
+
+/// Inclusion or exclusion proof of a storage slot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageProof {
+    /// Storage slot.
+    pub key: U256,
+    /// Proven value, `None` if the slot is not in the trie.
+    pub value: Option<StorageProofValue>,
+    /// Trie nodes from the storage root to the slot.
+    pub proof: Vec<Bytes>,
+}
+
+impl StorageProof {
+    /// Verifies the proof against the storage root of the account.
+    pub fn verify(&self, storage_root: B256) -> Result<(), ProofVerificationError> {
+        let key = unpack_nibbles(keccak256(self.key.to_be_bytes::<32>()).as_slice());
+        let found = verify_proof(storage_root, &key, &self.proof)?;
+        if found != self.value.map(|value| value.rlp_encode()) {
+            return Err(ProofVerificationError::ValueMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Inclusion or exclusion proof of an account, with proofs of some of its storage slots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountProof {
+    /// Account address.
+    pub address: Address,
+    /// Proven account, `None` if the account is not in the trie.
+    pub account: Option<TrieAccount>,
+    /// Trie nodes from the state root to the account.
+    pub proof: Vec<Bytes>,
+    /// Proofs of the requested storage slots.
+    pub storage_proofs: Vec<StorageProof>,
+}
+
+impl AccountProof {
+    /// Verifies the account proof against the state root, and every storage proof against the
+    /// storage root of the proven account.
+    pub fn verify(&self, state_root: B256) -> Result<(), ProofVerificationError> {
+        let key = unpack_nibbles(keccak256(self.address).as_slice());
+        let found = verify_proof(state_root, &key, &self.proof)?;
+        if found != self.account.map(|account| account.rlp_encode()) {
+            return Err(ProofVerificationError::ValueMismatch);
+        }
+
+        let storage_root = self
+            .account
+            .map_or(EMPTY_ROOT_HASH, |account| account.storage_root);
+        self.storage_proofs
+            .iter()
+            .try_for_each(|proof| proof.verify(storage_root))
+    }
+}
+
+/// Errors returned when verifying a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProofVerificationError {
+    /// The proof ends before the key is resolved.
+    MissingNode,
+    /// A proof node does not hash to the reference held by its parent.
+    HashMismatch,
+    /// A proof node is not a valid trie node.
+    MalformedNode,
+    /// The proof has nodes left after the key was resolved.
+    UnusedNodes,
+    /// The value resolved by the proof differs from the claimed one.
+    ValueMismatch,
+}
+
+impl fmt::Display for ProofVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::MissingNode => "proof is missing a node",
+            Self::HashMismatch => "proof node does not match its hash",
+            Self::MalformedNode => "proof node is malformed",
+            Self::UnusedNodes => "proof has unused nodes",
+            Self::ValueMismatch => "proven value does not match the claimed value",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for ProofVerificationError {}
+
+/// Leaf of a trie, as the nibbles of its hashed key and its RLP encoded value.
+type Leaf = (Vec<u8>, Vec<u8>);
+
+fn storage_root(leaves: &[Leaf]) -> B256 {
+    root_and_proof(leaves, None).0
+}
+
+/// Computes the root of the trie over sorted `leaves`, collecting the proof of `target` if any.
+fn root_and_proof(leaves: &[Leaf], target: Option<&[u8]>) -> (B256, Vec<Bytes>) {
+    if leaves.is_empty() {
+        return (EMPTY_ROOT_HASH, Vec::new());
+    }
+    let mut proof = Vec::new();
+    let root = build_node(leaves, 0, target, &mut proof);
+    // Nodes are collected from the leaf upwards.
+    proof.reverse();
+    (keccak256(&root), proof)
+}
+
+/// Builds the node holding `leaves`, which all share the first `depth` nibbles.
+///
+/// Secure tries have keys of a fixed length, but keys may also be prefixes of one another, in
+/// which case the shorter key is held as the value of a branch.
+///
+/// `target` is the remaining path of the proven key if it goes through this node.
+fn build_node(
+    leaves: &[Leaf],
+    depth: usize,
+    target: Option<&[u8]>,
+    proof: &mut Vec<Bytes>,
+) -> Vec<u8> {
+    let node = if let [(path, value)] = leaves {
+        rlp::encode_list(&[
+            rlp::encode_bytes(&hex_prefix(&path[depth..], true)),
+            rlp::encode_bytes(value),
+        ])
+    } else {
+        let first = &leaves[0].0[depth..];
+        let last = &leaves[leaves.len() - 1].0[depth..];
+        let shared = first.iter().zip(last).take_while(|(a, b)| a == b).count();
+        if shared > 0 {
+            let prefix = &first[..shared];
+            let child_target = target
+                .filter(|target| target.starts_with(prefix))
+                .map(|target| &target[shared..]);
+            let child = build_node(leaves, depth + shared, child_target, proof);
+            rlp::encode_list(&[
+                rlp::encode_bytes(&hex_prefix(prefix, false)),
+                node_reference(&child),
+            ])
+        } else {
+            let mut items = Vec::with_capacity(17);
+            // A key ending at this branch is the shortest one, so it sorts first.
+            let (value, mut rest) = match leaves.split_first() {
+                Some(((path, value), rest)) if path.len() == depth => (value.as_slice(), rest),
+                _ => (&[][..], leaves),
+            };
+            for nibble in 0..16u8 {
+                let split = rest
+                    .iter()
+                    .position(|(path, _)| path[depth] != nibble)
+                    .unwrap_or(rest.len());
+                let (group, tail) = rest.split_at(split);
+                rest = tail;
+                if group.is_empty() {
+                    items.push(rlp::encode_bytes(&[]));
+                    continue;
+                }
+                let child_target = target
+                    .filter(|target| target.first() == Some(&nibble))
+                    .map(|target| &target[1..]);
+                let child = build_node(group, depth + 1, child_target, proof);
+                items.push(node_reference(&child));
+            }
+            items.push(rlp::encode_bytes(value));
+            rlp::encode_list(&items)
+        }
+    };
+
+    if target.is_some() && (depth == 0 || node.len() >= 32) {
+        proof.push(Bytes::copy_from_slice(&node));
+    }
+    node
+}
+
+/// Returns how a parent refers to the node: embedded if short, by hash otherwise.
+fn node_reference(node: &[u8]) -> Vec<u8> {
+    if node.len() < 32 {
+        node.to_vec()
+    } else {
+        rlp::encode_bytes(keccak256(node).as_slice())
+    }
+}
+
+/// Walks the proof along `key` and returns the value found, or `None` if the key is absent.
+fn verify_proof(
+    root: B256,
+    key: &[u8],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, ProofVerificationError> {
+    if root == EMPTY_ROOT_HASH {
+        return if proof.is_empty() {
+            Ok(None)
+        } else {
+            Err(ProofVerificationError::UnusedNodes)
+        };
+    }
+
+    let mut proof = proof.iter();
+    let mut reference = NodeReference::Hash(root);
+    let mut depth = 0;
+    let value = loop {
+        let node = match reference {
+            NodeReference::Hash(hash) => {
+                let node = proof.next().ok_or(ProofVerificationError::MissingNode)?;
+                if keccak256(node) != hash {
+                    return Err(ProofVerificationError::HashMismatch);
+                }
+                node.to_vec()
+            }
+            NodeReference::Inline(node) => node,
+        };
+
+        let items = rlp::decode_list(&node)?;
+        match items.as_slice() {
+            [path, value] => {
+                let (path, is_leaf) = decode_hex_prefix(rlp::decode_bytes(path)?)?;
+                let rest = key.get(depth..).unwrap_or_default();
+                if is_leaf {
+                    break (rest == path.as_slice())
+                        .then(|| rlp::decode_bytes(value).map(<[u8]>::to_vec))
+                        .transpose()?;
+                }
+                if !rest.starts_with(&path) {
+                    break None;
+                }
+                depth += path.len();
+                reference = NodeReference::decode(value)?;
+            }
+            [children @ .., value] if children.len() == 16 => {
+                let Some(&nibble) = key.get(depth) else {
+                    let value = rlp::decode_bytes(value)?;
+                    break (!value.is_empty()).then(|| value.to_vec());
+                };
+                let child = children[nibble as usize];
+                if rlp::decode_bytes(child).is_ok_and(<[u8]>::is_empty) {
+                    break None;
+                }
+                depth += 1;
+                reference = NodeReference::decode(child)?;
+            }
+            _ => return Err(ProofVerificationError::MalformedNode),
+        }
+    };
+
+    if proof.next().is_some() {
+        return Err(ProofVerificationError::UnusedNodes);
+    }
+    Ok(value)
+}
+
+/// Reference from a trie node to its child.
+enum NodeReference {
+    /// Child referenced by the hash of its encoding.
+    Hash(B256),
+    /// Child short enough to be embedded in its parent.
+    Inline(Vec<u8>),
+}
+
+impl NodeReference {
+    fn decode(item: &[u8]) -> Result<Self, ProofVerificationError> {
+        if rlp::is_list(item) {
+            return Ok(Self::Inline(item.to_vec()));
+        }
+        let hash = rlp::decode_bytes(item)?;
+        if hash.len() != 32 {
+            return Err(ProofVerificationError::MalformedNode);
+        }
+        Ok(Self::Hash(B256::from_slice(hash)))
+    }
+}
+
+fn unpack_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+/// Hex-prefix encoding of a nibble path, see the Ethereum yellow paper, appendix C.
+fn hex_prefix(path: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let mut out = Vec::with_capacity(path.len() / 2 + 1);
+    let even = if path.len() % 2 == 1 {
+        out.push(((flag + 1) << 4) | path[0]);
+        &path[1..]
+    } else {
+        out.push(flag << 4);
+        path
+    };
+    out.extend(even.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(Vec<u8>, bool), ProofVerificationError> {
+    let (first, rest) = encoded
+        .split_first()
+        .ok_or(ProofVerificationError::MalformedNode)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(ProofVerificationError::MalformedNode);
+    }
+    let mut path = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        path.push(first & 0x0f);
+    }
+    path.extend(unpack_nibbles(rest));
+    Ok((path, flag & 2 == 2))
+}
+
+/// Minimal RLP encoding and decoding needed by the trie.
+mod rlp {
+    use super::ProofVerificationError;
+    use primitives::U256;
+    use std::{vec, vec::Vec};
+
+    pub(super) fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+        if let [byte @ 0x00..=0x7f] = bytes {
+            return vec![*byte];
+        }
+        let mut out = header(0x80, bytes.len());
+        out.extend_from_slice(bytes);
+        out
+    }
+
+    pub(super) fn encode_u256(value: U256) -> Vec<u8> {
+        let bytes = value.to_be_bytes::<32>();
+        let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(32);
+        encode_bytes(&bytes[start..])
+    }
+
+    /// Encodes a list of already encoded items.
+    pub(super) fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload_len = items.iter().map(Vec::len).sum();
+        let mut out = header(0xc0, payload_len);
+        items.iter().for_each(|item| out.extend_from_slice(item));
+        out
+    }
+
+    fn header(offset: u8, len: usize) -> Vec<u8> {
+        if len < 56 {
+            return vec![offset + len as u8];
+        }
+        let len_bytes = len.to_be_bytes();
+        let start = len_bytes.iter().position(|byte| *byte != 0).unwrap_or(0);
+        let mut out = vec![offset + 55 + (len_bytes.len() - start) as u8];
+        out.extend_from_slice(&len_bytes[start..]);
+        out
+    }
+
+    pub(super) fn is_list(item: &[u8]) -> bool {
+        item.first().is_some_and(|byte| *byte >= 0xc0)
+    }
+
+    /// Decodes the first item of `buf`, returning whether it is a list, its payload and the
+    /// length of the whole item.
+    fn decode_item(buf: &[u8]) -> Result<(bool, &[u8], usize), ProofVerificationError> {
+        let first = *buf.first().ok_or(ProofVerificationError::MalformedNode)?;
+        let (is_list, header_len, payload_len) = match first {
+            0x00..=0x7f => return Ok((false, &buf[..1], 1)),
+            0x80..=0xb7 => (false, 1, (first - 0x80) as usize),
+            0xb8..=0xbf => {
+                let len_of_len = (first - 0xb7) as usize;
+                (false, 1 + len_of_len, decode_len(&buf[1..], len_of_len)?)
+            }
+            0xc0..=0xf7 => (true, 1, (first - 0xc0) as usize),
+            0xf8..=0xff => {
+                let len_of_len = (first - 0xf7) as usize;
+                (true, 1 + len_of_len, decode_len(&buf[1..], len_of_len)?)
+            }
+        };
+        let end = header_len
+            .checked_add(payload_len)
+            .filter(|end| *end <= buf.len())
+            .ok_or(ProofVerificationError::MalformedNode)?;
+        Ok((is_list, &buf[header_len..end], end))
+    }
+
+    fn decode_len(buf: &[u8], len_of_len: usize) -> Result<usize, ProofVerificationError> {
+        if len_of_len > core::mem::size_of::<usize>() || buf.len() < len_of_len {
+            return Err(ProofVerificationError::MalformedNode);
+        }
+        Ok(buf[..len_of_len]
+            .iter()
+            .fold(0, |len, byte| (len << 8) | *byte as usize))
+    }
+
+    /// Decodes a byte string that spans the whole `item`.
+    pub(super) fn decode_bytes(item: &[u8]) -> Result<&[u8], ProofVerificationError> {
+        match decode_item(item)? {
+            (false, payload, len) if len == item.len() => Ok(payload),
+            _ => Err(ProofVerificationError::MalformedNode),
+        }
+    }
+
+    /// Decodes a list that spans the whole `item` into the encodings of its items.
+    pub(super) fn decode_list(item: &[u8]) -> Result<Vec<&[u8]>, ProofVerificationError> {
+        let (true, mut payload, len) = decode_item(item)? else {
+            return Err(ProofVerificationError::MalformedNode);
+        };
+        if len != item.len() {
+            return Err(ProofVerificationError::MalformedNode);
+        }
+        let mut items = Vec::new();
+        while !payload.is_empty() {
+            let (_, _, item_len) = decode_item(payload)?;
+            items.push(&payload[..item_len]);
+            payload = &payload[item_len..];
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Database, InMemoryDB};
+    use hash_db::Hasher;
+    use plain_hasher::PlainHasher;
+    use primitives::KECCAK_EMPTY;
+
+    const COMMITMENT_KEY: B256 = B256::repeat_byte(0x11);
+
+    /// Account encoded by `alloy-rlp`, to check [`TrieAccount::rlp_encode`] against.
+    #[derive(alloy_rlp::RlpEncodable)]
+    struct ReferenceAccount {
+        nonce: u64,
+        balance: U256,
+        storage_root: B256,
+        code_hash: B256,
+    }
+
+    /// Keccak hasher for the reference trie implementation.
+    struct KeccakHasher;
+
+    impl Hasher for KeccakHasher {
+        type Out = B256;
+        type StdHasher = PlainHasher;
+        const LENGTH: usize = 32;
+
+        fn hash(x: &[u8]) -> Self::Out {
+            keccak256(x)
+        }
+    }
+
+    fn account(nonce: u64) -> AccountInfo {
+        AccountInfo {
+            nonce,
+            balance: U256::from(1_000 + nonce),
+            code_hash: KECCAK_EMPTY,
+            code: None,
+        }
+    }
+
+    fn sample_trie() -> StateTrie {
+        let mut trie = StateTrie::new(COMMITMENT_KEY);
+        for i in 1..=20u8 {
+            let address = Address::with_last_byte(i);
+            trie.insert_account(address, account(i as u64));
+            for slot in 0..i as u64 {
+                let value = FlaggedStorage::new(U256::from(slot + 1), slot % 2 == 1);
+                trie.insert_storage(address, U256::from(slot), value);
+            }
+        }
+        trie
+    }
+
+    #[test]
+    fn test_empty_roots() {
+        let trie = StateTrie::new(COMMITMENT_KEY);
+        assert_eq!(trie.state_root(), EMPTY_ROOT_HASH);
+        assert_eq!(trie.storage_root(&Address::ZERO), EMPTY_ROOT_HASH);
+        assert_eq!(keccak256([0x80]), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn test_single_account_root() {
+        let mut trie = StateTrie::new(COMMITMENT_KEY);
+        let address = Address::with_last_byte(1);
+        trie.insert_account(address, account(1));
+
+        let key = unpack_nibbles(keccak256(address).as_slice());
+        let value = TrieAccount::new(&account(1), EMPTY_ROOT_HASH).rlp_encode();
+        let leaf = rlp::encode_list(&[
+            rlp::encode_bytes(&hex_prefix(&key, true)),
+            rlp::encode_bytes(&value),
+        ]);
+        assert_eq!(trie.state_root(), keccak256(leaf));
+    }
+
+    /// Root and proofs of a plain, unhashed trie.
+    fn plain_trie(entries: &[(&str, &str)]) -> (B256, impl Fn(&str) -> Vec<Bytes>) {
+        let mut leaves: Vec<Leaf> = entries
+            .iter()
+            .map(|(key, value)| (unpack_nibbles(key.as_bytes()), value.as_bytes().to_vec()))
+            .collect();
+        leaves.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let root = root_and_proof(&leaves, None).0;
+        let prove = move |key: &str| {
+            root_and_proof(&leaves, Some(unpack_nibbles(key.as_bytes()).as_slice())).1
+        };
+        (root, prove)
+    }
+
+    #[test]
+    fn test_known_trie_roots() {
+        // Reference vectors of go-ethereum's trie tests and of the ethereum/tests trie suite.
+        let value = "a".repeat(50);
+        let (root, _) = plain_trie(&[("A", value.as_str())]);
+        assert_eq!(
+            root,
+            b256!("0xd23786fb4a010da3ce639d66d5e904a11dbc02746d1ce25029e53290cabf28ab")
+        );
+
+        let entries = [
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ];
+        let (root, _) = plain_trie(&entries);
+        assert_eq!(
+            root,
+            b256!("0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+        );
+
+        let entries = [
+            ("do", "verb"),
+            ("horse", "stallion"),
+            ("doge", "coin"),
+            ("dog", "puppy"),
+        ];
+        let (root, prove) = plain_trie(&entries);
+        assert_eq!(
+            root,
+            b256!("0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84")
+        );
+        for (key, value) in entries {
+            let found = verify_proof(root, &unpack_nibbles(key.as_bytes()), &prove(key));
+            assert_eq!(found, Ok(Some(value.as_bytes().to_vec())), "{key}");
+        }
+        for key in ["d", "dogs", "horses", "cat"] {
+            let found = verify_proof(root, &unpack_nibbles(key.as_bytes()), &prove(key));
+            assert_eq!(found, Ok(None), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_public_roots_match_reference_trie() {
+        let mut trie = StateTrie::new(COMMITMENT_KEY);
+        for i in 1..=40u8 {
+            let address = Address::repeat_byte(i);
+            trie.insert_account(address, account(i as u64));
+            for slot in 0..i as u64 {
+                let value = U256::from(slot * 0x1_0000_0001);
+                trie.insert_storage(address, U256::from(slot), FlaggedStorage::new(value, false));
+            }
+        }
+
+        let storage_root = |account: &PlainAccount| {
+            triehash::sec_trie_root::<KeccakHasher, _, _, _>(
+                account
+                    .storage
+                    .iter()
+                    .filter(|(_, value)| !value.value.is_zero())
+                    .map(|(slot, value)| {
+                        (slot.to_be_bytes::<32>(), alloy_rlp::encode(value.value))
+                    }),
+            )
+        };
+        let state_root = triehash::sec_trie_root::<KeccakHasher, _, _, _>(
+            trie.accounts().iter().map(|(address, account)| {
+                let account = ReferenceAccount {
+                    nonce: account.info.nonce,
+                    balance: account.info.balance,
+                    storage_root: storage_root(account),
+                    code_hash: account.info.code_hash,
+                };
+                (*address, alloy_rlp::encode(account))
+            }),
+        );
+
+        assert_eq!(trie.state_root(), state_root);
+        for (address, account) in trie.accounts() {
+            assert_eq!(trie.storage_root(address), storage_root(account));
+        }
+    }
+
+    #[test]
+    fn test_private_slot_salt_and_commitment() {
+        let address = Address::with_last_byte(1);
+        let salt = private_slot_salt(&COMMITMENT_KEY, address, U256::from(2));
+        assert_eq!(
+            salt,
+            b256!("0xc174ec3f471a2355aae5f6b2d4d8f3ece92b356f3197060c9b728fc4c2207280")
+        );
+        assert_eq!(
+            private_slot_commitment(salt, U256::from(2), U256::from(3)),
+            b256!("0x512a7b81f2ca40f88a417ecf267ed2aaef5031233b4a51305d19e18a4db4e804")
+        );
+        assert_eq!(
+            StateTrie::new(COMMITMENT_KEY).slot_salt(address, U256::from(2)),
+            salt
+        );
+
+        // Salts differ across slots, accounts and keys.
+        assert_ne!(
+            private_slot_salt(&COMMITMENT_KEY, address, U256::from(3)),
+            salt
+        );
+        assert_ne!(
+            private_slot_salt(&COMMITMENT_KEY, Address::with_last_byte(2), U256::from(2)),
+            salt
+        );
+        assert_ne!(private_slot_salt(&B256::ZERO, address, U256::from(2)), salt);
+    }
+
+    #[test]
+    fn test_commitment_key_changes_private_roots_only() {
+        let address = Address::with_last_byte(1);
+        let roots = |key: B256, is_private: bool| {
+            let mut trie = StateTrie::new(key);
+            trie.insert_account(address, account(1));
+            trie.insert_storage(
+                address,
+                U256::from(1),
+                FlaggedStorage::new(U256::from(1), is_private),
+            );
+            trie.state_root()
+        };
+        assert_eq!(roots(COMMITMENT_KEY, false), roots(B256::ZERO, false));
+        assert_ne!(roots(COMMITMENT_KEY, true), roots(B256::ZERO, true));
+    }
+
+    #[test]
+    fn test_account_and_storage_proofs() {
+        let trie = sample_trie();
+        let root = trie.state_root();
+
+        for i in 1..=20u8 {
+            let slots: Vec<U256> = (0..=i as u64).map(U256::from).collect();
+            let proof = trie.account_proof(Address::with_last_byte(i), &slots);
+            assert_eq!(proof.verify(root), Ok(()));
+            assert_eq!(
+                proof.account.unwrap().storage_root,
+                trie.storage_root(&Address::with_last_byte(i))
+            );
+            // The last requested slot was never written.
+            assert_eq!(proof.storage_proofs.last().unwrap().value, None);
+        }
+    }
+
+    #[test]
+    fn test_exclusion_proof() {
+        let trie = sample_trie();
+        let proof = trie.account_proof(Address::with_last_byte(0xff), &[U256::from(1)]);
+        assert_eq!(proof.account, None);
+        assert_eq!(proof.verify(trie.state_root()), Ok(()));
+    }
+
+    #[test]
+    fn test_private_slot_reveals_commitment_only() {
+        let trie = sample_trie();
+        let address = Address::with_last_byte(4);
+        let proof = trie.account_proof(address, &[U256::from(0), U256::from(1)]);
+        assert_eq!(proof.verify(trie.state_root()), Ok(()));
+
+        assert_eq!(
+            proof.storage_proofs[0].value,
+            Some(StorageProofValue::Public(U256::from(1)))
+        );
+        let private = proof.storage_proofs[1].value.unwrap();
+        assert_eq!(
+            private,
+            StorageProofValue::Private {
+                commitment: private_slot_commitment(
+                    trie.slot_salt(address, U256::from(1)),
+                    U256::from(1),
+                    U256::from(2)
+                )
+            }
+        );
+
+        let leaf = proof.storage_proofs[1].proof.last().unwrap();
+        let value = U256::from(2).to_be_bytes::<32>();
+        assert!(!leaf.windows(32).any(|window| window == value));
+    }
+
+    #[test]
+    fn test_tampered_proofs_fail() {
+        let trie = sample_trie();
+        let root = trie.state_root();
+        let address = Address::with_last_byte(6);
+
+        let mut proof = trie.account_proof(address, &[U256::from(2)]);
+        proof.storage_proofs[0].value = Some(StorageProofValue::Public(U256::from(3)));
+        assert_eq!(
+            proof.verify(root),
+            Err(ProofVerificationError::ValueMismatch)
+        );
+
+        let mut proof = trie.account_proof(address, &[]);
+        proof.account.as_mut().unwrap().balance += U256::from(1);
+        assert_eq!(
+            proof.verify(root),
+            Err(ProofVerificationError::ValueMismatch)
+        );
+
+        let mut proof = trie.account_proof(address, &[]);
+        proof.proof.pop();
+        assert_eq!(proof.verify(root), Err(ProofVerificationError::MissingNode));
+
+        let proof = trie.account_proof(address, &[]);
+        assert_eq!(
+            proof.verify(B256::with_last_byte(1)),
+            Err(ProofVerificationError::HashMismatch)
+        );
+    }
+
+    #[test]
+    fn test_privacy_flip_changes_root() {
+        let mut trie = sample_trie();
+        let root = trie.state_root();
+        let address = Address::with_last_byte(3);
+        trie.insert_storage(
+            address,
+            U256::from(0),
+            FlaggedStorage::new(U256::from(1), true),
+        );
+        assert_ne!(trie.state_root(), root);
+    }
+
+    #[test]
+    fn test_cache_db_and_bundle_state_roots_match() {
+        let mut db = InMemoryDB::default();
+        let mut bundle = BundleState::builder(0..=0);
+        for i in 1..=5u8 {
+            let address = Address::with_last_byte(i);
+            let storage: HashMap<U256, FlaggedStorage> = (0..i as u64)
+                .map(|slot| {
+                    let value = FlaggedStorage::new(U256::from(slot + 7), slot % 2 == 0);
+                    (U256::from(slot), value)
+                })
+                .collect();
+            db.insert_account_info(address, account(i as u64));
+            db.replace_account_storage(address, storage.clone())
+                .unwrap();
+            bundle = bundle
+                .state_present_account_info(address, account(i as u64))
+                .state_storage(
+                    address,
+                    storage
+                        .into_iter()
+                        .map(|(slot, value)| (slot, (FlaggedStorage::ZERO, value)))
+                        .collect(),
+                );
+        }
+        let bundle = bundle.build();
+
+        let db_trie = StateTrie::from_cache_db(&db, COMMITMENT_KEY);
+        let bundle_trie = StateTrie::from_bundle_state(&bundle, COMMITMENT_KEY);
+        assert_eq!(db_trie.state_root(), bundle_trie.state_root());
+
+        let address = Address::with_last_byte(2);
+        assert_eq!(db.basic(address).unwrap().unwrap().nonce, 2);
+        let proof = db_trie.account_proof(address, &[U256::from(0)]);
+        assert_eq!(proof.verify(bundle_trie.state_root()), Ok(()));
+    }
+}
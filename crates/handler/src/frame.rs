@@ -404,6 +404,7 @@ where
         memory: SharedMemory,
         inputs: Box<EOFCreateInputs>,
     ) -> Result<ItemOrResult<Self, FrameResult>, ERROR> {
+        let opcodes = evm.ctx_instructions().1.opcode_table();
         let context = evm.ctx();
         let spec = context.cfg().spec().into();
         let return_error = |e| {
@@ -433,7 +434,7 @@ where
                     return return_error(InstructionResult::InvalidEOFInitCode);
                 };
 
-                if eof.validate().is_err() {
+                if eof.validate_with_opcodes(opcodes).is_err() {
                     // TODO : (EOF) New error type.
                     context.journal().inc_account_nonce(inputs.caller)?;
                     return return_error(InstructionResult::InvalidEOFInitCode);
@@ -1,4 +1,5 @@
 use auto_impl::auto_impl;
+use bytecode::opcode::{OpCodeTable, MAINNET_OPCODES};
 use interpreter::{
     instructions::{instruction_table, InstructionTable},
     Host, Instruction, InterpreterTypes,
@@ -15,6 +16,13 @@ pub trait InstructionProvider {
 
     /// Returns the instruction table that is used by EvmTr to execute instructions.
     fn instruction_table(&self) -> &InstructionTable<Self::InterpreterTypes, Self::Context>;
+
+    /// Returns the opcode info of the instruction set, used to validate EOF initcode.
+    ///
+    /// Instruction sets that define their own opcodes override it.
+    fn opcode_table(&self) -> &'static OpCodeTable {
+        &MAINNET_OPCODES
+    }
 }
 
 /// Ethereum instruction contains list of mainnet instructions that is used for Interpreter execution.
@@ -16,6 +16,7 @@ mod inspect;
 mod inspector;
 mod mainnet_inspect;
 mod noop;
+mod state_diff;
 mod traits;
 
 /// Inspector implementations.
@@ -29,4 +30,5 @@ pub use handler::{inspect_instructions, InspectorHandler};
 pub use inspect::{InspectCommitEvm, InspectEvm};
 pub use inspector::*;
 pub use noop::NoOpInspector;
+pub use state_diff::{AccountState, DiffMode, PrivacyFlip, StateDiff, StateDiffBuilder};
 pub use traits::*;
@@ -0,0 +1,437 @@
+//! State diff of a transaction in the `prestateTracer` format.
+//!
+//! [`StateDiffBuilder`] turns the [`EvmState`] returned by a transaction, together with the
+//! database it was executed on, into a [`StateDiff`]. Without diff mode it lists the state of
+//! every touched account before the transaction, with diff mode it lists the `pre` and `post`
+//! state of the modified accounts only.
+//!
+//! Private storage slots are kept apart from public ones and their values are redacted unless
+//! [`StateDiffBuilder::reveal_private`] is set. In diff mode, slots whose privacy flag changed
+//! are listed in [`AccountState::privacy_flips`].
+use database_interface::DatabaseRef;
+use primitives::{Address, Bytes, B256, KECCAK_EMPTY, U256};
+use state::{Account, AccountInfo, EvmState, FlaggedStorage};
+use std::collections::BTreeMap;
+
+/// Change of the privacy flag of a storage slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum PrivacyFlip {
+    /// A public slot became private.
+    MadePrivate,
+    /// A private slot became public.
+    MadePublic,
+}
+
+/// State of an account, as listed by the `prestateTracer`.
+///
+/// Fields are `None` or empty when they are not part of the diff.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccountState {
+    /// Account balance.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub balance: Option<U256>,
+    /// Account nonce.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub nonce: Option<u64>,
+    /// Account code.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub code: Option<Bytes>,
+    /// Public storage slots.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub storage: BTreeMap<B256, B256>,
+    /// Private storage slots, with `None` values when redacted.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub private_storage: BTreeMap<B256, Option<B256>>,
+    /// Slots whose privacy flag changed, only set in the `post` state of diff mode.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub privacy_flips: BTreeMap<B256, PrivacyFlip>,
+}
+
+impl AccountState {
+    /// Returns `true` if the account state holds no field.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+            && self.private_storage.is_empty()
+            && self.privacy_flips.is_empty()
+    }
+}
+
+/// `pre` and `post` state of the modified accounts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffMode {
+    /// State before the transaction, accounts created by the transaction are omitted.
+    pub pre: BTreeMap<Address, AccountState>,
+    /// Changed fields after the transaction, accounts destroyed by the transaction are omitted.
+    pub post: BTreeMap<Address, AccountState>,
+}
+
+/// Output of the [`StateDiffBuilder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum StateDiff {
+    /// State of every touched account before the transaction.
+    Prestate(BTreeMap<Address, AccountState>),
+    /// `pre` and `post` state of the modified accounts.
+    Diff(DiffMode),
+}
+
+/// Builds a [`StateDiff`] out of an [`EvmState`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateDiffBuilder {
+    diff_mode: bool,
+    reveal_private: bool,
+    disable_code: bool,
+    disable_storage: bool,
+}
+
+impl StateDiffBuilder {
+    /// Creates a builder for the prestate output, with private values redacted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `diffMode` option, listing `pre` and `post` state of the modified accounts.
+    pub fn diff_mode(mut self, diff_mode: bool) -> Self {
+        self.diff_mode = diff_mode;
+        self
+    }
+
+    /// Sets whether private storage values are included instead of redacted.
+    pub fn reveal_private(mut self, reveal_private: bool) -> Self {
+        self.reveal_private = reveal_private;
+        self
+    }
+
+    /// Sets the `disableCode` option.
+    pub fn disable_code(mut self, disable_code: bool) -> Self {
+        self.disable_code = disable_code;
+        self
+    }
+
+    /// Sets the `disableStorage` option.
+    pub fn disable_storage(mut self, disable_storage: bool) -> Self {
+        self.disable_storage = disable_storage;
+        self
+    }
+
+    /// Builds the diff of `state`.
+    ///
+    /// `db` must hold the state the transaction was executed on, it is used to load the
+    /// account info and code before the transaction.
+    pub fn build<DB: DatabaseRef>(
+        &self,
+        state: &EvmState,
+        db: &DB,
+    ) -> Result<StateDiff, DB::Error> {
+        let mut pre = BTreeMap::new();
+        let mut post = BTreeMap::new();
+        for (address, account) in state.iter().filter(|(_, account)| account.is_touched()) {
+            let pre_info = db.basic_ref(*address)?;
+            let pre_code = match &pre_info {
+                Some(info) if !self.disable_code => self.load_code(info, db)?,
+                _ => None,
+            };
+
+            if !self.diff_mode {
+                let mut account_state = self.account_state(
+                    pre_info.as_ref().unwrap_or(&AccountInfo::default()),
+                    pre_code,
+                );
+                for (slot, value) in &account.storage {
+                    self.insert_slot(&mut account_state, *slot, value.original_value);
+                }
+                pre.insert(*address, account_state);
+                continue;
+            }
+
+            let (pre_state, post_state) = self.account_diff(account, pre_info.as_ref(), pre_code);
+            if let Some(pre_state) = pre_state.filter(|state| !state.is_empty()) {
+                pre.insert(*address, pre_state);
+            }
+            if let Some(post_state) = post_state.filter(|state| !state.is_empty()) {
+                post.insert(*address, post_state);
+            }
+        }
+
+        Ok(if self.diff_mode {
+            StateDiff::Diff(DiffMode { pre, post })
+        } else {
+            StateDiff::Prestate(pre)
+        })
+    }
+
+    /// Returns the `pre` and `post` state of a touched account, `None` if the account did not
+    /// exist before or after the transaction.
+    fn account_diff(
+        &self,
+        account: &Account,
+        pre_info: Option<&AccountInfo>,
+        pre_code: Option<Bytes>,
+    ) -> (Option<AccountState>, Option<AccountState>) {
+        let changed_slots: BTreeMap<_, _> = if self.disable_storage {
+            BTreeMap::new()
+        } else {
+            account
+                .changed_storage_slots()
+                .map(|(slot, value)| (*slot, (value.original_value, value.present_value)))
+                .collect()
+        };
+        let info_changed = pre_info != Some(&account.info);
+        if !info_changed && changed_slots.is_empty() && !account.is_selfdestructed() {
+            return (None, None);
+        }
+
+        let mut pre_state = pre_info.map(|info| self.account_state(info, pre_code.clone()));
+        if let Some(pre_state) = &mut pre_state {
+            for (slot, (original, _)) in &changed_slots {
+                if !original.value.is_zero() {
+                    self.insert_slot(pre_state, *slot, *original);
+                }
+            }
+        }
+
+        if account.is_selfdestructed() {
+            return (pre_state, None);
+        }
+
+        let mut post_state = AccountState::default();
+        let pre_info = pre_info.cloned().unwrap_or_default();
+        if pre_info.balance != account.info.balance {
+            post_state.balance = Some(account.info.balance);
+        }
+        if pre_info.nonce != account.info.nonce {
+            post_state.nonce = Some(account.info.nonce);
+        }
+        if !self.disable_code && pre_info.code_hash != account.info.code_hash {
+            post_state.code = account
+                .info
+                .code
+                .as_ref()
+                .map(|code| code.original_bytes())
+                .filter(|code| !code.is_empty());
+        }
+        for (slot, (original, present)) in changed_slots {
+            if original.is_private != present.is_private {
+                let flip = if present.is_private {
+                    PrivacyFlip::MadePrivate
+                } else {
+                    PrivacyFlip::MadePublic
+                };
+                post_state.privacy_flips.insert(slot.into(), flip);
+            }
+            if !present.value.is_zero() || original.is_private != present.is_private {
+                self.insert_slot(&mut post_state, slot, present);
+            }
+        }
+        (pre_state, Some(post_state))
+    }
+
+    /// Returns the balance, nonce and code of an account.
+    fn account_state(&self, info: &AccountInfo, code: Option<Bytes>) -> AccountState {
+        AccountState {
+            balance: Some(info.balance),
+            nonce: (info.nonce != 0).then_some(info.nonce),
+            code,
+            ..Default::default()
+        }
+    }
+
+    /// Loads the code of an account, `None` if it has none.
+    fn load_code<DB: DatabaseRef>(
+        &self,
+        info: &AccountInfo,
+        db: &DB,
+    ) -> Result<Option<Bytes>, DB::Error> {
+        if info.code_hash == KECCAK_EMPTY {
+            return Ok(None);
+        }
+        let code = match &info.code {
+            Some(code) => code.original_bytes(),
+            None => db.code_by_hash_ref(info.code_hash)?.original_bytes(),
+        };
+        Ok((!code.is_empty()).then_some(code))
+    }
+
+    /// Inserts a slot in the public or private storage, redacting private values if needed.
+    fn insert_slot(&self, account_state: &mut AccountState, slot: U256, value: FlaggedStorage) {
+        if self.disable_storage {
+            return;
+        }
+        let value_bytes = B256::from(value.value);
+        if value.is_private {
+            let value = self.reveal_private.then_some(value_bytes);
+            account_state.private_storage.insert(slot.into(), value);
+        } else {
+            account_state.storage.insert(slot.into(), value_bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::InMemoryDB;
+    use primitives::HashMap;
+    use state::{AccountStatus, EvmStorageSlot};
+
+    const ADDRESS: Address = Address::with_last_byte(1);
+
+    fn slot(key: u64) -> B256 {
+        U256::from(key).into()
+    }
+
+    /// Account 1 has a public slot 1, a private slot 2 and a public slot 3.
+    fn setup() -> (InMemoryDB, EvmState) {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            ADDRESS,
+            AccountInfo {
+                balance: U256::from(100),
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+
+        let storage = HashMap::from_iter([
+            (
+                U256::from(1),
+                EvmStorageSlot::new_changed(
+                    FlaggedStorage::new(U256::from(10), false),
+                    FlaggedStorage::new(U256::from(10), true),
+                ),
+            ),
+            (
+                U256::from(2),
+                EvmStorageSlot::new_changed(
+                    FlaggedStorage::new(U256::from(20), true),
+                    FlaggedStorage::new(U256::from(21), true),
+                ),
+            ),
+            (
+                U256::from(3),
+                EvmStorageSlot::new(FlaggedStorage::new(U256::from(30), false)),
+            ),
+        ]);
+        let state = HashMap::from_iter([(
+            ADDRESS,
+            Account {
+                info: AccountInfo {
+                    balance: U256::from(90),
+                    nonce: 1,
+                    ..Default::default()
+                },
+                storage,
+                status: AccountStatus::Touched,
+            },
+        )]);
+        (db, state)
+    }
+
+    #[test]
+    fn prestate_redacts_private_values() {
+        let (db, state) = setup();
+        let StateDiff::Prestate(pre) = StateDiffBuilder::new().build(&state, &db).unwrap() else {
+            panic!("expected prestate");
+        };
+
+        let account = &pre[&ADDRESS];
+        assert_eq!(account.balance, Some(U256::from(100)));
+        assert_eq!(account.nonce, Some(1));
+        assert_eq!(account.code, None);
+        assert_eq!(
+            account.storage,
+            BTreeMap::from([(slot(1), slot(10)), (slot(3), slot(30))])
+        );
+        assert_eq!(account.private_storage, BTreeMap::from([(slot(2), None)]));
+    }
+
+    #[test]
+    fn diff_mode_marks_privacy_flips() {
+        let (db, state) = setup();
+        let StateDiff::Diff(diff) = StateDiffBuilder::new()
+            .diff_mode(true)
+            .reveal_private(true)
+            .build(&state, &db)
+            .unwrap()
+        else {
+            panic!("expected diff");
+        };
+
+        let pre = &diff.pre[&ADDRESS];
+        assert_eq!(pre.storage, BTreeMap::from([(slot(1), slot(10))]));
+        assert_eq!(
+            pre.private_storage,
+            BTreeMap::from([(slot(2), Some(slot(20)))])
+        );
+
+        let post = &diff.post[&ADDRESS];
+        assert_eq!(post.balance, Some(U256::from(90)));
+        assert_eq!(post.nonce, None);
+        assert!(post.storage.is_empty());
+        assert_eq!(
+            post.private_storage,
+            BTreeMap::from([(slot(1), Some(slot(10))), (slot(2), Some(slot(21)))])
+        );
+        assert_eq!(
+            post.privacy_flips,
+            BTreeMap::from([(slot(1), PrivacyFlip::MadePrivate)])
+        );
+    }
+
+    #[test]
+    fn diff_mode_skips_created_and_untouched() {
+        let (db, mut state) = setup();
+        let created = Address::with_last_byte(2);
+        state.insert(
+            created,
+            Account {
+                info: AccountInfo {
+                    balance: U256::from(10),
+                    ..Default::default()
+                },
+                storage: HashMap::default(),
+                status: AccountStatus::Touched | AccountStatus::Created,
+            },
+        );
+        state.insert(Address::with_last_byte(3), Account::new_not_existing());
+
+        let StateDiff::Diff(diff) = StateDiffBuilder::new()
+            .diff_mode(true)
+            .build(&state, &db)
+            .unwrap()
+        else {
+            panic!("expected diff");
+        };
+        assert!(!diff.pre.contains_key(&created));
+        assert_eq!(diff.post[&created].balance, Some(U256::from(10)));
+        assert_eq!(diff.pre.len(), 1);
+        assert_eq!(diff.post.len(), 2);
+    }
+}
@@ -11,6 +11,8 @@ mod subroutine_stack;
 pub use ext_bytecode::ExtBytecode;
 pub use input::InputsImpl;
 pub use loop_control::LoopControl as LoopControlImpl;
+#[cfg(feature = "zeroize_memory")]
+pub use return_data::zeroize_bytes;
 pub use return_data::ReturnDataImpl;
 pub use runtime_flags::RuntimeFlags;
 pub use shared_memory::{num_words, SharedMemory};
@@ -13,6 +13,49 @@ impl ReturnData for ReturnDataImpl {
     }
 
     fn set_buffer(&mut self, bytes: Bytes) {
-        self.0 = bytes;
+        #[cfg(feature = "zeroize_memory")]
+        zeroize_bytes(core::mem::replace(&mut self.0, bytes));
+        #[cfg(not(feature = "zeroize_memory"))]
+        {
+            self.0 = bytes;
+        }
+    }
+}
+
+#[cfg(feature = "zeroize_memory")]
+impl Drop for ReturnDataImpl {
+    fn drop(&mut self) {
+        zeroize_bytes(core::mem::take(&mut self.0));
+    }
+}
+
+/// Wipes `bytes` if this is its last reference, and returns whether it did.
+///
+/// Shared or static buffers are left as they are: their other owners still read them, and
+/// they are not wiped when those owners drop them.
+#[cfg(feature = "zeroize_memory")]
+pub fn zeroize_bytes(bytes: Bytes) -> bool {
+    use zeroize::Zeroize;
+    match bytes.0.try_into_mut() {
+        Ok(mut bytes) => {
+            bytes.as_mut().zeroize();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(all(test, feature = "zeroize_memory"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zeroize_bytes_wipes_only_unshared_buffers() {
+        assert!(zeroize_bytes(Bytes::from(vec![0x5e; 32])));
+        assert!(!zeroize_bytes(Bytes::from_static(&[0x5e; 32])));
+
+        let shared = Bytes::from(vec![0x5e; 32]);
+        assert!(!zeroize_bytes(shared.clone()));
+        assert_eq!(shared, Bytes::from(vec![0x5e; 32]));
     }
 }
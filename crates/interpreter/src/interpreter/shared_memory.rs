@@ -7,6 +7,8 @@ use core::{
 };
 use primitives::{hex, B256, U256};
 use std::{rc::Rc, vec::Vec};
+#[cfg(feature = "zeroize_memory")]
+use zeroize::Zeroize;
 
 /// A sequential memory shared between calls, which uses
 /// a `Vec` for internal representation.
@@ -36,6 +38,16 @@ impl fmt::Debug for SharedMemory {
     }
 }
 
+/// Wipes the buffer if no other context shares it.
+#[cfg(feature = "zeroize_memory")]
+impl Drop for SharedMemory {
+    fn drop(&mut self) {
+        if let Some(buffer) = Rc::get_mut(&mut self.buffer) {
+            buffer.get_mut().zeroize();
+        }
+    }
+}
+
 impl Default for SharedMemory {
     #[inline]
     fn default() -> Self {
@@ -175,13 +187,18 @@ impl SharedMemory {
     }
 
     /// Prepares the shared memory for returning from child context. Do nothing if there is no child context.
+    ///
+    /// With the `zeroize_memory` feature, the memory of the child context is wiped.
     #[inline]
     pub fn free_child_context(&mut self) {
         let Some(child_checkpoint) = self.child_checkpoint.take() else {
             return;
         };
+        let mut buffer = self.buffer.borrow_mut();
+        #[cfg(feature = "zeroize_memory")]
+        buffer[child_checkpoint..].zeroize();
         unsafe {
-            self.buffer.borrow_mut().set_len(child_checkpoint);
+            buffer.set_len(child_checkpoint);
         }
     }
 
@@ -198,11 +215,21 @@ impl SharedMemory {
     }
 
     /// Resizes the memory in-place so that `len` is equal to `new_len`.
+    ///
+    /// With the `zeroize_memory` feature, growing past the capacity moves the memory to the new
+    /// allocation ourselves, so the old one is wiped before it is freed.
     #[inline]
     pub fn resize(&mut self, new_size: usize) {
-        self.buffer
-            .borrow_mut()
-            .resize(self.my_checkpoint + new_size, 0);
+        let new_len = self.my_checkpoint + new_size;
+        let mut buffer = self.buffer.borrow_mut();
+        #[cfg(feature = "zeroize_memory")]
+        if new_len > buffer.capacity() {
+            let mut grown = Vec::with_capacity(new_len.max(2 * buffer.capacity()));
+            grown.extend_from_slice(&buffer);
+            buffer.zeroize();
+            *buffer = grown;
+        }
+        buffer.resize(new_len, 0);
     }
 
     /// Returns a byte slice of the memory region at the given offset.
@@ -530,4 +557,38 @@ mod tests {
         assert_eq!(sm1.len(), 32);
         assert_eq!(sm1.buffer.borrow().get(0..32), Some(&[0_u8; 32] as &[u8]));
     }
+
+    #[cfg(feature = "zeroize_memory")]
+    #[test]
+    fn free_child_context_zeroizes() {
+        let mut sm1 = SharedMemory::new();
+        sm1.resize(32);
+        sm1.set(0, &[0xaa; 32]);
+
+        let mut sm2 = sm1.new_child_context();
+        sm2.resize(64);
+        sm2.set(0, &[0xbb; 64]);
+        sm1.free_child_context();
+
+        let buffer = sm1.buffer.borrow();
+        assert_eq!(buffer.len(), 32);
+        assert_eq!(&buffer[..], &[0xaa; 32]);
+        // SAFETY: The freed range was initialized by the child context.
+        let freed = unsafe { core::slice::from_raw_parts(buffer.as_ptr().add(32), 64) };
+        assert_eq!(freed, &[0; 64]);
+    }
+
+    #[cfg(feature = "zeroize_memory")]
+    #[test]
+    fn resize_past_capacity_keeps_memory() {
+        let mut sm1 = SharedMemory::with_capacity(32);
+        sm1.resize(32);
+        sm1.set(0, &[0xaa; 32]);
+
+        sm1.resize(96);
+        let buffer = sm1.buffer.borrow();
+        assert!(buffer.capacity() >= 96);
+        assert_eq!(&buffer[..32], &[0xaa; 32]);
+        assert_eq!(&buffer[32..], &[0; 64]);
+    }
 }
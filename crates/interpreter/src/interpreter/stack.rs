@@ -29,6 +29,13 @@ impl fmt::Display for Stack {
     }
 }
 
+#[cfg(feature = "zeroize_memory")]
+impl Drop for Stack {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl Default for Stack {
     #[inline]
     fn default() -> Self {
@@ -93,6 +100,14 @@ impl Stack {
         }
     }
 
+    /// Empties the stack and wipes its whole capacity, as popped words stay in it.
+    #[cfg(feature = "zeroize_memory")]
+    pub fn zeroize(&mut self) {
+        use zeroize::Zeroize;
+        self.data.clear();
+        self.data.spare_capacity_mut().zeroize();
+    }
+
     /// Returns the length of the stack in words.
     #[inline]
     pub fn len(&self) -> usize {
@@ -476,4 +491,21 @@ mod tests {
         assert!(!full_stack.push(U256::from(100)));
         assert!(!cloned_full.push(U256::from(100)));
     }
+
+    #[cfg(feature = "zeroize_memory")]
+    #[test]
+    fn zeroize_wipes_popped_words() {
+        run(|stack| {
+            // The capacity is filled with `U256::MAX` by `run`.
+            assert!(stack.push(U256::from(42)));
+            stack.pop().unwrap();
+            assert!(stack.push(U256::from(7)));
+            stack.zeroize();
+            assert_eq!(stack.len(), 0);
+            // SAFETY: `zeroize` initialized the whole capacity.
+            unsafe { stack.data.set_len(STACK_LIMIT) };
+            assert!(stack.data.iter().all(|word| word.is_zero()));
+            unsafe { stack.data.set_len(0) };
+        });
+    }
 }
@@ -0,0 +1,38 @@
+// `criterion_group!` throws the missing docs error.
+#![allow(missing_docs)]
+//! Benchmarks for the Seismic precompiles and confidential storage opcodes
+//!
+//! Each bench sets its gas as the throughput, so criterion reports `Melem/s`, that is gas per
+//! microsecond. Comparing these rates with those of the upstream precompiles, e.g. `ecrecover`
+//! in `revm-precompile`, validates the default `SeismicGasSchedule`.
+/// Seismic precompile benchmarks
+pub mod precompiles;
+/// `CLOAD` and `CSTORE` benchmarks
+pub mod storage;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Benchmarks the Seismic precompiles.
+pub fn benchmark_seismic_precompiles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Seismic Precompile benchmarks");
+
+    precompiles::add_rng_benches(&mut group);
+    precompiles::add_aes_gcm_benches(&mut group);
+    precompiles::add_key_derivation_benches(&mut group);
+    precompiles::add_secp256k1_sign_benches(&mut group);
+}
+
+/// Benchmarks contracts dominated by confidential storage accesses.
+pub fn benchmark_confidential_storage(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Confidential storage benchmarks");
+
+    storage::add_benches(&mut group);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = benchmark_seismic_precompiles, benchmark_confidential_storage
+}
+
+criterion_main!(benches);
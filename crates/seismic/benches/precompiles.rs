@@ -0,0 +1,181 @@
+//! Benchmarks for the Seismic precompiles
+use criterion::{measurement::Measurement, BatchSize, BenchmarkGroup, Throughput};
+use revm::{
+    database::EmptyDB,
+    handler::PrecompileProvider,
+    interpreter::{CallInput, InputsImpl},
+    precompile::u64_to_address,
+    primitives::{Address, Bytes, B256},
+    Context,
+};
+use secp256k1::{Secp256k1, SecretKey};
+use seismic_revm::{
+    precompiles::{
+        aes::{aes_gcm_dec::AES_GCM_DEC_ADDRESS, aes_gcm_enc::AES_GCM_ENC_ADDRESS},
+        ecdh_derive_sym_key::ECDH_ADDRESS,
+        hkdf_derive_sym_key::HKDF_ADDRESS,
+        rng::precompile::RNG_ADDRESS,
+        secp256k1_sign::SECP256K1_SIGN_ADDRESS,
+        SeismicPrecompiles,
+    },
+    DefaultSeismic, SeismicContext, SeismicSpecId, SeismicTransaction,
+};
+
+type BenchContext = SeismicContext<EmptyDB>;
+
+/// Payload sizes of the variable length benches, from a single word to a large calldata.
+const SIZES: [usize; 4] = [32, 256, 1024, 16 * 1024];
+
+fn context() -> BenchContext {
+    Context::seismic().with_tx(SeismicTransaction::default().with_tx_hash(B256::ZERO))
+}
+
+fn precompiles() -> SeismicPrecompiles<BenchContext> {
+    SeismicPrecompiles::new_with_spec(SeismicSpecId::MERCURY)
+}
+
+/// Calls the precompile at `address`, returning the output and the gas used.
+fn run(
+    precompiles: &mut SeismicPrecompiles<BenchContext>,
+    context: &mut BenchContext,
+    address: &Address,
+    input: &InputsImpl,
+) -> (Bytes, u64) {
+    let result = precompiles
+        .run(context, address, input, false, u64::MAX)
+        .unwrap()
+        .expect("address is a precompile");
+    assert!(result.is_ok(), "precompile failed: {:?}", result.result);
+    (result.output, result.gas.spent())
+}
+
+fn inputs(input: Vec<u8>) -> InputsImpl {
+    InputsImpl {
+        input: CallInput::Bytes(input.into()),
+        ..Default::default()
+    }
+}
+
+/// Benches a stateless precompile, with its gas as the throughput.
+fn add_bench<M: Measurement>(
+    group: &mut BenchmarkGroup<'_, M>,
+    name: &str,
+    address: u64,
+    input: Vec<u8>,
+) {
+    let mut precompiles = precompiles();
+    let mut context = context();
+    let address = u64_to_address(address);
+    let input = inputs(input);
+
+    let (_, gas) = run(&mut precompiles, &mut context, &address, &input);
+    group.throughput(Throughput::Elements(gas));
+    group.bench_function(name, |b| {
+        b.iter(|| run(&mut precompiles, &mut context, &address, &input))
+    });
+}
+
+fn rng_input(requested_output_len: usize, pers_len: usize) -> Vec<u8> {
+    let mut input = (requested_output_len as u32).to_be_bytes().to_vec();
+    input.resize(4 + pers_len, 0xAA);
+    input
+}
+
+/// Add benches for the RNG precompile.
+///
+/// The init benches run every call on a fresh transaction, so each one forks a leaf RNG, while
+/// the fill benches keep drawing from a leaf RNG that is already initialized.
+pub fn add_rng_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    let address = u64_to_address(RNG_ADDRESS);
+
+    for pers_len in [1, 32, 1024] {
+        let mut precompiles = precompiles();
+        let input = inputs(rng_input(32, pers_len));
+        let (_, gas) = run(&mut precompiles, &mut context(), &address, &input);
+
+        group.throughput(Throughput::Elements(gas));
+        group.bench_function(format!("rng init, {pers_len} bytes pers"), |b| {
+            b.iter_batched(
+                context,
+                |mut context| {
+                    run(&mut precompiles, &mut context, &address, &input);
+                    context
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    for len in SIZES {
+        let mut precompiles = precompiles();
+        let mut context = context();
+        let input = inputs(rng_input(len, 1));
+        run(&mut precompiles, &mut context, &address, &input);
+        let (_, gas) = run(&mut precompiles, &mut context, &address, &input);
+
+        group.throughput(Throughput::Elements(gas));
+        group.bench_function(format!("rng fill, {len} bytes"), |b| {
+            b.iter(|| run(&mut precompiles, &mut context, &address, &input))
+        });
+    }
+}
+
+/// Add benches for the AES-GCM encryption and decryption precompiles.
+pub fn add_aes_gcm_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    let key_and_nonce = [[0x11u8; 32].as_slice(), &[0x22; 12]].concat();
+
+    for len in SIZES {
+        let plaintext = [key_and_nonce.as_slice(), &vec![0x33; len]].concat();
+        let (ciphertext, _) = run(
+            &mut precompiles(),
+            &mut context(),
+            &u64_to_address(AES_GCM_ENC_ADDRESS),
+            &inputs(plaintext.clone()),
+        );
+
+        add_bench(
+            group,
+            &format!("aes-gcm encrypt, {len} bytes"),
+            AES_GCM_ENC_ADDRESS,
+            plaintext,
+        );
+        add_bench(
+            group,
+            &format!("aes-gcm decrypt, {len} bytes"),
+            AES_GCM_DEC_ADDRESS,
+            [key_and_nonce.as_slice(), &ciphertext].concat(),
+        );
+    }
+}
+
+/// Add benches for the ECDH and HKDF key derivation precompiles.
+pub fn add_key_derivation_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    let public_key = SecretKey::from_slice(&[0x55; 32])
+        .unwrap()
+        .public_key(&Secp256k1::new());
+    add_bench(
+        group,
+        "ecdh",
+        ECDH_ADDRESS,
+        [[0x44; 32].as_slice(), &public_key.serialize()].concat(),
+    );
+
+    for len in SIZES {
+        add_bench(
+            group,
+            &format!("hkdf, {len} bytes"),
+            HKDF_ADDRESS,
+            vec![0x66; len],
+        );
+    }
+}
+
+/// Add benches for the secp256k1 signing precompile.
+pub fn add_secp256k1_sign_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    add_bench(
+        group,
+        "secp256k1 sign",
+        SECP256K1_SIGN_ADDRESS,
+        [[0x77u8; 32], [0x88; 32]].concat(),
+    );
+}
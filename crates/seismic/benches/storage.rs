@@ -0,0 +1,73 @@
+//! Benchmarks for contracts dominated by `CLOAD` and `CSTORE`
+use criterion::{measurement::Measurement, BenchmarkGroup, Throughput};
+use revm::{
+    bytecode::{opcode, Bytecode},
+    database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET},
+    primitives::TxKind,
+    Context, ExecuteEvm,
+};
+use seismic_revm::{
+    instructions::instruction_provider::{CLOAD, CSTORE},
+    DefaultSeismic, SeismicBuilder,
+};
+
+/// Number of slots each workload touches.
+const SLOTS: u16 = 100;
+
+/// `value = 1; CSTORE(slot, value)` for each slot.
+fn cstore_code(code: &mut Vec<u8>) {
+    for slot in 0..SLOTS {
+        code.extend([opcode::PUSH1, 0x01, opcode::PUSH2]);
+        code.extend(slot.to_be_bytes());
+        code.push(CSTORE);
+    }
+}
+
+/// `POP(CLOAD(slot))` for each slot.
+fn cload_code(code: &mut Vec<u8>) {
+    for slot in 0..SLOTS {
+        code.push(opcode::PUSH2);
+        code.extend(slot.to_be_bytes());
+        code.extend([CLOAD, opcode::POP]);
+    }
+}
+
+/// Benches a call to `code`, with the gas of the transaction as the throughput.
+fn add_bench<M: Measurement>(group: &mut BenchmarkGroup<'_, M>, name: &str, mut code: Vec<u8>) {
+    code.push(opcode::STOP);
+    let mut evm = Context::seismic()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(code.into())))
+        .modify_tx_chained(|tx| {
+            tx.base.caller = BENCH_CALLER;
+            tx.base.kind = TxKind::Call(BENCH_TARGET);
+        })
+        .build_seismic();
+
+    let result = evm.replay().unwrap().result;
+    assert!(result.is_success(), "workload failed: {result:?}");
+    group.throughput(Throughput::Elements(result.gas_used()));
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            let _ = evm.replay();
+        })
+    });
+}
+
+/// Add benches for `CLOAD` and `CSTORE` heavy contracts.
+///
+/// Transactions are not committed, so every run stores to cold, empty slots. The gas includes
+/// the intrinsic cost of the transaction.
+pub fn add_benches<M: Measurement>(group: &mut BenchmarkGroup<'_, M>) {
+    let mut code = Vec::new();
+    cstore_code(&mut code);
+    add_bench(group, "cstore", code);
+
+    let mut code = Vec::new();
+    cload_code(&mut code);
+    add_bench(group, "cload", code);
+
+    let mut code = Vec::new();
+    cstore_code(&mut code);
+    cload_code(&mut code);
+    add_bench(group, "cstore then cload", code);
+}
@@ -0,0 +1,10 @@
+//! Runs bytecode free of confidential storage on `SeismicEvm` and the mainnet EVM and panics
+//! if their results, gas or state differ.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seismic_revm::differential::DiffCase;
+
+fuzz_target!(|data: &[u8]| {
+    DiffCase::from_bytes(data).check();
+});
@@ -1,5 +1,5 @@
 use crate::{
-    evm::SeismicEvm, handler::SeismicHandler,
+    evm::SeismicEvm, gas_schedule::SeismicGasSchedule, handler::SeismicHandler,
     instructions::instruction_provider::SeismicInstructions, transaction::abstraction::SeismicTxTr,
     SeismicChain, SeismicHaltReason, SeismicSpecId,
 };
@@ -24,6 +24,10 @@ pub trait SeismicContextTr:
     Chain = SeismicChain,
 >
 {
+    /// Gas schedule of the Seismic precompiles and opcodes for the current spec.
+    fn gas_schedule(&self) -> SeismicGasSchedule {
+        self.chain_ref().gas_schedule(self.cfg().spec())
+    }
 }
 
 impl<T> SeismicContextTr for T where
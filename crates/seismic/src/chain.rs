@@ -1,2 +1,4 @@
+pub mod entropy_source;
+pub mod key_source;
 pub mod rng_container;
 pub mod seismic_chain;
@@ -0,0 +1,22 @@
+use core::fmt;
+
+/// Source of local, non-deterministic entropy, mixed into the RNG of simulated calls so that
+/// they cannot be used to preview the randomness of executed transactions.
+///
+/// Enclave and zkVM targets have no operating system RNG, so they inject their own.
+pub trait EntropySource: fmt::Debug + Send + Sync {
+    /// Fills `dest` with random bytes.
+    fn fill_bytes(&self, dest: &mut [u8]);
+}
+
+/// Entropy source backed by the operating system RNG.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsEntropySource;
+
+#[cfg(feature = "std")]
+impl EntropySource for OsEntropySource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        rand_core::RngCore::fill_bytes(&mut rand_core::OsRng, dest);
+    }
+}
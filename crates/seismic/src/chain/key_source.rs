@@ -0,0 +1,49 @@
+use core::fmt;
+use zeroize::Zeroizing;
+
+/// Length of the chain master key, in bytes.
+pub const MASTER_KEY_LENGTH: usize = 32;
+
+/// Source of the node-held chain master key that contract keys are derived from.
+///
+/// On a node the key lives in the enclave. The EVM only needs to read it when a contract asks
+/// for a key, so implementors can fetch it lazily.
+pub trait KeySource: fmt::Debug + Send + Sync {
+    /// Returns the chain master key, wiped from memory once the caller drops it.
+    fn master_key(&self) -> Zeroizing<[u8; MASTER_KEY_LENGTH]>;
+}
+
+/// Key source holding a fixed master key in memory, for tests and local development.
+///
+/// The default key is public, so it must never be used on a live network.
+#[derive(Clone)]
+pub struct MockKeySource {
+    master_key: Zeroizing<[u8; MASTER_KEY_LENGTH]>,
+}
+
+impl MockKeySource {
+    pub fn new(master_key: [u8; MASTER_KEY_LENGTH]) -> Self {
+        Self {
+            master_key: Zeroizing::new(master_key),
+        }
+    }
+}
+
+impl Default for MockKeySource {
+    fn default() -> Self {
+        Self::new([0x5e; MASTER_KEY_LENGTH])
+    }
+}
+
+impl fmt::Debug for MockKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Hide the master key.
+        write!(f, "MockKeySource {{  }}")
+    }
+}
+
+impl KeySource for MockKeySource {
+    fn master_key(&self) -> Zeroizing<[u8; MASTER_KEY_LENGTH]> {
+        self.master_key.clone()
+    }
+}
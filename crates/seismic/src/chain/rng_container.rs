@@ -2,27 +2,40 @@ use core::fmt;
 use rand_core::RngCore;
 use revm::{
     precompile::PrecompileError,
-    primitives::{Bytes, B256},
+    primitives::{Bytes, HashMap, B256},
 };
 
 use crate::transaction::abstraction::RngMode;
 use seismic_enclave::get_unsecure_sample_schnorrkel_keypair;
+use std::string::ToString;
+use std::sync::Arc;
 
-use crate::precompiles::rng::{
-    domain_sep_rng::{LeafRng, RootRng},
-    precompile::{calculate_fill_cost, calculate_init_cost},
+use super::entropy_source::EntropySource;
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::rng::domain_sep_rng::{LeafKey, LeafRng, RootRng},
 };
 
 pub struct RngContainer {
     rng: RootRng,
-    leaf_rng: Option<LeafRng>,
+    /// Leaf RNGs forked in the current transaction.
+    leaf_rngs: HashMap<LeafKey, LeafRng>,
+    /// Leaf RNGs precompiles forked for their own use in the current transaction, by label.
+    precompile_rngs: HashMap<&'static [u8], LeafRng>,
+    /// Local entropy mixed into the root RNG of simulations.
+    entropy_source: Option<Arc<dyn EntropySource>>,
 }
 
+/// Number of bytes of local entropy appended to the root RNG of a simulation.
+const LOCAL_ENTROPY_LENGTH: usize = 32;
+
 impl Clone for RngContainer {
     fn clone(&self) -> Self {
         Self {
             rng: self.rng.clone(),
-            leaf_rng: None,
+            leaf_rngs: HashMap::default(),
+            precompile_rngs: HashMap::default(),
+            entropy_source: self.entropy_source.clone(),
         }
     }
 }
@@ -36,65 +49,142 @@ impl fmt::Debug for RngContainer {
 
 impl Default for RngContainer {
     fn default() -> Self {
-        Self {
-            rng: RootRng::new(get_unsecure_sample_schnorrkel_keypair()),
-            leaf_rng: None,
-        }
+        Self::new(get_unsecure_sample_schnorrkel_keypair())
     }
 }
 
 impl RngContainer {
+    /// Creates a container with the given root VRF key. With the `std` feature, simulations
+    /// draw their local entropy from the operating system, otherwise an entropy source must be
+    /// set with [`RngContainer::set_entropy_source`].
     pub fn new(root_vrf_key: schnorrkel::Keypair) -> Self {
         Self {
             rng: RootRng::new(root_vrf_key),
-            leaf_rng: None,
+            leaf_rngs: HashMap::default(),
+            precompile_rngs: HashMap::default(),
+            entropy_source: default_entropy_source(),
         }
     }
+
+    /// Sets the source of the local entropy of simulations.
+    pub fn set_entropy_source(&mut self, entropy_source: Arc<dyn EntropySource>) {
+        self.entropy_source = Some(entropy_source);
+    }
+}
+
+#[cfg(feature = "std")]
+fn default_entropy_source() -> Option<Arc<dyn EntropySource>> {
+    Some(Arc::new(super::entropy_source::OsEntropySource))
+}
+
+#[cfg(not(feature = "std"))]
+fn default_entropy_source() -> Option<Arc<dyn EntropySource>> {
+    None
 }
 
 impl RngContainer {
     pub fn reset_rng(&mut self) {
         let root_vrf_key = self.rng.get_root_vrf_key();
         self.rng = RootRng::new(root_vrf_key);
-        self.leaf_rng = None;
+        self.leaf_rngs.clear();
+        self.precompile_rngs.clear();
     }
 
     /// Appends entropy to the root RNG if in Simulation mode.
-    pub fn maybe_append_entropy(&mut self, mode: RngMode) {
+    ///
+    /// Fails with a fatal error if no entropy source is set: running the simulation without
+    /// one would reveal the randomness the transaction gets once executed.
+    pub fn maybe_append_entropy(&mut self, mode: RngMode) -> Result<(), PrecompileError> {
         if mode == RngMode::Simulation {
-            self.rng.append_local_entropy();
+            let entropy_source = self.entropy_source.as_ref().ok_or_else(|| {
+                PrecompileError::Fatal("Simulations need an entropy source".to_string())
+            })?;
+            let mut entropy = [0u8; LOCAL_ENTROPY_LENGTH];
+            entropy_source.fill_bytes(&mut entropy);
+            self.rng.append_local_entropy(&entropy);
         }
+        Ok(())
     }
 
-    pub fn calculate_gas_cost(&self, pers: &[u8], requested_output_len: usize) -> u64 {
-        match self.leaf_rng.as_ref() {
-            Some(_) => calculate_fill_cost(requested_output_len),
-            None => calculate_init_cost(pers.len()) + calculate_fill_cost(requested_output_len),
+    /// The init cost is charged on the first request for each leaf RNG of the transaction.
+    pub fn calculate_gas_cost(
+        &self,
+        leaf: &LeafKey,
+        requested_output_len: usize,
+        schedule: &SeismicGasSchedule,
+    ) -> u64 {
+        match self.leaf_rngs.get(&leaf.slot()) {
+            Some(_) => schedule.rng_fill_cost(requested_output_len),
+            None => {
+                schedule.rng_init_cost(leaf.transcript_len())
+                    + schedule.rng_fill_cost(requested_output_len)
+            }
         }
     }
 
     pub fn process_rng(
         &mut self,
-        pers: &[u8],
+        leaf: &LeafKey,
         requested_output_len: usize,
         kernel_mode: RngMode,
         tx_hash: &B256,
     ) -> Result<Bytes, PrecompileError> {
-        // Domain separation: update the root RNG
-        self.maybe_append_entropy(kernel_mode);
-        self.rng.append_tx(tx_hash);
+        self.separate_domain(kernel_mode, tx_hash)?;
 
         // Initialize the leaf RNG if not done already.
-        if self.leaf_rng.is_none() {
-            let leaf_rng = self.rng.fork(pers);
-            self.leaf_rng = Some(leaf_rng);
+        let rng = &self.rng;
+        let leaf_rng = self
+            .leaf_rngs
+            .entry(leaf.slot())
+            .or_insert_with(|| rng.fork_leaf(leaf));
+
+        Ok(fill(leaf_rng, requested_output_len))
+    }
+
+    /// Same as [`RngContainer::calculate_gas_cost`] for [`RngContainer::precompile_rng_bytes`].
+    pub fn calculate_precompile_gas_cost(
+        &self,
+        label: &[u8],
+        requested_output_len: usize,
+        schedule: &SeismicGasSchedule,
+    ) -> u64 {
+        match self.precompile_rngs.get(label) {
+            Some(_) => schedule.rng_fill_cost(requested_output_len),
+            None => {
+                schedule.rng_init_cost(label.len()) + schedule.rng_fill_cost(requested_output_len)
+            }
         }
+    }
 
-        // Get the random bytes.
-        let leaf_rng = self.leaf_rng.as_mut().unwrap();
-        let mut rng_bytes = vec![0u8; requested_output_len];
-        leaf_rng.fill_bytes(&mut rng_bytes);
-        Ok(Bytes::from(rng_bytes))
+    /// Draws random bytes for a precompile's own use, from a leaf RNG no contract can request
+    /// through the RNG precompile.
+    pub fn precompile_rng_bytes(
+        &mut self,
+        label: &'static [u8],
+        requested_output_len: usize,
+        kernel_mode: RngMode,
+        tx_hash: &B256,
+    ) -> Result<Bytes, PrecompileError> {
+        self.separate_domain(kernel_mode, tx_hash)?;
+
+        let rng = &self.rng;
+        let leaf_rng = self
+            .precompile_rngs
+            .entry(label)
+            .or_insert_with(|| rng.fork_precompile(label));
+
+        Ok(fill(leaf_rng, requested_output_len))
+    }
+
+    /// Domain separation: update the root RNG.
+    fn separate_domain(
+        &mut self,
+        kernel_mode: RngMode,
+        tx_hash: &B256,
+    ) -> Result<(), PrecompileError> {
+        self.maybe_append_entropy(kernel_mode)?;
+        self.rng.append_tx(tx_hash);
+        Ok(())
     }
 
     #[cfg(test)]
@@ -103,7 +193,40 @@ impl RngContainer {
     }
 
     #[cfg(test)]
-    pub fn leaf_rng(&self) -> &Option<LeafRng> {
-        &self.leaf_rng
+    pub fn precompile_rngs(&self) -> &HashMap<&'static [u8], LeafRng> {
+        &self.precompile_rngs
+    }
+
+    #[cfg(test)]
+    pub fn leaf_rngs(&self) -> &HashMap<LeafKey, LeafRng> {
+        &self.leaf_rngs
+    }
+}
+
+/// Get the random bytes.
+fn fill(leaf_rng: &mut LeafRng, len: usize) -> Bytes {
+    let mut rng_bytes = vec![0u8; len];
+    leaf_rng.fill_bytes(&mut rng_bytes);
+    Bytes::from(rng_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::rng::domain_sep_rng::LeafKey;
+
+    #[test]
+    fn test_simulation_without_entropy_source_fails() {
+        let mut container = RngContainer::default();
+        container.entropy_source = None;
+        let leaf = LeafKey::new(Bytes::from_static(b"pers"));
+
+        let result = container.process_rng(&leaf, 32, RngMode::Simulation, &B256::ZERO);
+        assert!(matches!(result, Err(PrecompileError::Fatal(_))));
+        let result = container.precompile_rng_bytes(b"label", 32, RngMode::Simulation, &B256::ZERO);
+        assert!(matches!(result, Err(PrecompileError::Fatal(_))));
+        assert!(container
+            .process_rng(&leaf, 32, RngMode::Execution, &B256::ZERO)
+            .is_ok());
     }
 }
@@ -2,31 +2,76 @@ use revm::{
     precompile::PrecompileError,
     primitives::{Bytes, B256},
 };
+use std::sync::Arc;
 
-use crate::transaction::abstraction::RngMode;
+use crate::{
+    gas_schedule::SeismicGasSchedule, precompiles::rng::domain_sep_rng::LeafKey,
+    transaction::abstraction::RngMode, SeismicSpecId,
+};
 
-use super::rng_container::RngContainer;
+use super::{entropy_source::EntropySource, key_source::KeySource, rng_container::RngContainer};
 
 #[derive(Clone, Debug)]
 pub struct SeismicChain {
     rng_container: RngContainer,
+    /// Source of the chain master key, contract key derivation fails without one.
+    key_source: Option<Arc<dyn KeySource>>,
+    /// Overrides the default gas schedule of the spec.
+    gas_schedule: Option<SeismicGasSchedule>,
 }
 
 impl Default for SeismicChain {
     fn default() -> Self {
         Self {
             rng_container: RngContainer::default(),
+            key_source: None,
+            gas_schedule: None,
         }
     }
 }
 
 impl SeismicChain {
+    /// Creates a chain with the given root VRF key.
+    ///
+    /// Like [`Default`], no key source is set: set one with [`Self::with_key_source`] for
+    /// contract keys to be derived.
     pub fn new(root_vrf_key: schnorrkel::Keypair) -> Self {
         Self {
             rng_container: RngContainer::new(root_vrf_key),
+            key_source: None,
+            gas_schedule: None,
         }
     }
 
+    /// Sets the source of the chain master key contract keys are derived from.
+    pub fn with_key_source(mut self, key_source: Arc<dyn KeySource>) -> Self {
+        self.key_source = Some(key_source);
+        self
+    }
+
+    /// Sets the source of the local entropy mixed into the RNG of simulations. Required
+    /// without the `std` feature, which otherwise uses the operating system RNG.
+    pub fn with_entropy_source(mut self, entropy_source: Arc<dyn EntropySource>) -> Self {
+        self.rng_container.set_entropy_source(entropy_source);
+        self
+    }
+
+    pub fn key_source(&self) -> Option<&dyn KeySource> {
+        self.key_source.as_deref()
+    }
+
+    /// Replaces the default gas schedule of the spec, e.g. to re-tune it on a devnet.
+    pub fn with_gas_schedule(mut self, gas_schedule: SeismicGasSchedule) -> Self {
+        self.gas_schedule = Some(gas_schedule);
+        self
+    }
+
+    /// Returns the gas schedule in effect for `spec`.
+    pub fn gas_schedule(&self, spec: SeismicSpecId) -> SeismicGasSchedule {
+        self.gas_schedule
+            .unwrap_or_else(|| SeismicGasSchedule::new(spec))
+    }
+
     pub fn rng_container(&self) -> &RngContainer {
         &self.rng_container
     }
@@ -39,23 +84,49 @@ impl SeismicChain {
         self.rng_container.reset_rng();
     }
 
-    pub fn maybe_append_entropy(&mut self, mode: RngMode) {
-        self.rng_container.maybe_append_entropy(mode);
+    pub fn maybe_append_entropy(&mut self, mode: RngMode) -> Result<(), PrecompileError> {
+        self.rng_container.maybe_append_entropy(mode)
     }
 
-    pub fn calculate_gas_cost(&self, pers: &[u8], requested_output_len: usize) -> u64 {
+    pub fn calculate_gas_cost(
+        &self,
+        leaf: &LeafKey,
+        requested_output_len: usize,
+        schedule: &SeismicGasSchedule,
+    ) -> u64 {
         self.rng_container
-            .calculate_gas_cost(pers, requested_output_len)
+            .calculate_gas_cost(leaf, requested_output_len, schedule)
     }
 
     pub fn process_rng(
         &mut self,
-        pers: &[u8],
+        leaf: &LeafKey,
+        requested_output_len: usize,
+        kernel_mode: RngMode,
+        tx_hash: &B256,
+    ) -> Result<Bytes, PrecompileError> {
+        self.rng_container
+            .process_rng(leaf, requested_output_len, kernel_mode, tx_hash)
+    }
+
+    pub fn calculate_precompile_gas_cost(
+        &self,
+        label: &[u8],
+        requested_output_len: usize,
+        schedule: &SeismicGasSchedule,
+    ) -> u64 {
+        self.rng_container
+            .calculate_precompile_gas_cost(label, requested_output_len, schedule)
+    }
+
+    pub fn precompile_rng_bytes(
+        &mut self,
+        label: &'static [u8],
         requested_output_len: usize,
         kernel_mode: RngMode,
         tx_hash: &B256,
     ) -> Result<Bytes, PrecompileError> {
         self.rng_container
-            .process_rng(pers, requested_output_len, kernel_mode, tx_hash)
+            .precompile_rng_bytes(label, requested_output_len, kernel_mode, tx_hash)
     }
 }
@@ -0,0 +1,394 @@
+//! Differential testing of [`SeismicEvm`](crate::SeismicEvm) against the mainnet EVM.
+//!
+//! Seismic replaces `SLOAD` and `SSTORE` with flag aware versions. On bytecode that never uses
+//! `CLOAD`/`CSTORE`, never reaches a Seismic precompile and only sees public storage, both EVMs
+//! must produce the same result, gas and state.
+//!
+//! A [`DiffCase`] is decoded from raw bytes, so the `cargo fuzz` target and the proptest
+//! harness share one generator:
+//!
+//! - byte 0: flags, bit 0 selects raw bytecode instead of generated bytecode, bit 1 sends one
+//!   wei with the call.
+//! - bytes 1..3: big endian gas limit, in units of 16 gas above the intrinsic cost.
+//! - byte 3: number of prestate slots `n` (modulo 8), followed by `n` pairs of slot and value.
+//! - next byte: length `k` of the callee program, followed by `k` bytes.
+//! - remaining bytes: the program of the called contract.
+use crate::{
+    instructions::instruction_provider::{CLOAD, CSTORE},
+    precompiles::mercury,
+    DefaultSeismic, SeismicBuilder, SeismicContext, SeismicHaltReason, SeismicSpecId,
+};
+use core::convert::Infallible;
+use revm::{
+    bytecode::{opcode::*, Bytecode},
+    context::{
+        result::{EVMError, ResultAndState},
+        Context,
+    },
+    database::InMemoryDB,
+    precompile::Precompiles,
+    primitives::{address, hardfork::SpecId, Address, Bytes, FlaggedStorage, TxKind, U256},
+    state::AccountInfo,
+    ExecuteEvm, MainBuilder, MainContext,
+};
+use std::vec::Vec;
+
+/// Sender of the transaction.
+pub const CALLER: Address = address!("0x00000000000000000000000000000000000ca11e");
+/// Contract the transaction calls.
+pub const CONTRACT: Address = address!("0x0000000000000000000000000000000000c0de01");
+/// Contract called by [`CONTRACT`] through the `CALL` family.
+pub const CALLEE: Address = address!("0x0000000000000000000000000000000000c0de02");
+
+/// Number of mainnet precompiles generated programs may call.
+const MAINNET_PRECOMPILES: u8 = 0x11;
+
+/// Outcome of a transaction on either EVM, with mainnet halt reasons wrapped.
+pub type DiffOutput = Result<ResultAndState<SeismicHaltReason>, EVMError<Infallible>>;
+
+/// A transaction calling [`CONTRACT`] and the public prestate it runs on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffCase {
+    /// Code of [`CONTRACT`].
+    pub code: Bytes,
+    /// Code of [`CALLEE`].
+    pub callee_code: Bytes,
+    /// Public storage of [`CONTRACT`].
+    pub storage: Vec<(U256, U256)>,
+    pub gas_limit: u64,
+    pub value: U256,
+}
+
+impl DiffCase {
+    /// Decodes a case, see the [module documentation](self) for the layout.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let mut input = ByteReader(data);
+        let flags = input.byte();
+        let gas = u16::from_be_bytes([input.byte(), input.byte()]);
+        let storage = (0..input.byte() % 8)
+            .map(|_| (U256::from(input.byte() % 16), U256::from(input.byte())))
+            .collect();
+        let callee_len = input.byte() as usize;
+        let callee = input.take(callee_len);
+        let program = input.rest();
+
+        let build = |program: &[u8]| -> Bytes {
+            if flags & 1 == 1 {
+                sanitize(program).into()
+            } else {
+                generate(program).into()
+            }
+        };
+        Self {
+            code: build(program),
+            callee_code: build(callee),
+            storage,
+            gas_limit: 21_000 + 16 * gas as u64,
+            value: U256::from((flags >> 1) & 1),
+        }
+    }
+
+    fn database(&self) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            CALLER,
+            AccountInfo {
+                balance: U256::from(u64::MAX),
+                ..Default::default()
+            },
+        );
+        for (address, code) in [(CONTRACT, &self.code), (CALLEE, &self.callee_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::default().with_code(Bytecode::new_legacy(code.clone())),
+            );
+        }
+        for (slot, value) in &self.storage {
+            db.insert_account_storage(CONTRACT, *slot, FlaggedStorage::new_from_value(*value))
+                .expect("in memory database is infallible");
+        }
+        db
+    }
+
+    /// Runs the case on [`SeismicEvm`](crate::SeismicEvm).
+    pub fn run_seismic(&self) -> DiffOutput {
+        let ctx: SeismicContext<InMemoryDB> = Context::seismic()
+            .with_db(self.database())
+            .modify_tx_chained(|tx| {
+                tx.base.caller = CALLER;
+                tx.base.kind = TxKind::Call(CONTRACT);
+                tx.base.gas_limit = self.gas_limit;
+                tx.base.value = self.value;
+            });
+        ctx.build_seismic().replay()
+    }
+
+    /// Runs the case on the mainnet EVM of the spec Mercury is based on.
+    pub fn run_mainnet(&self) -> DiffOutput {
+        let mut evm = Context::mainnet()
+            .with_db(self.database())
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::from(SeismicSpecId::MERCURY))
+            .modify_tx_chained(|tx| {
+                tx.caller = CALLER;
+                tx.kind = TxKind::Call(CONTRACT);
+                tx.gas_limit = self.gas_limit;
+                tx.value = self.value;
+            })
+            .build_mainnet();
+        evm.replay()
+            .map(|output| output.map_haltreason(SeismicHaltReason::Base))
+    }
+
+    /// Runs the case on both EVMs and panics if they disagree.
+    ///
+    /// Returns `false` if the case is not comparable because execution loaded a Seismic
+    /// precompile, whose address is warm and callable on Seismic only.
+    pub fn check(&self) -> bool {
+        let seismic = self.run_seismic();
+        let mainnet = self.run_mainnet();
+        if [&seismic, &mainnet]
+            .into_iter()
+            .any(touches_seismic_precompile)
+        {
+            return false;
+        }
+        match (seismic, mainnet) {
+            (Ok(seismic), Ok(mainnet)) => {
+                assert_eq!(
+                    seismic.result, mainnet.result,
+                    "execution result mismatch for {self:?}"
+                );
+                assert_eq!(seismic.state, mainnet.state, "state mismatch for {self:?}");
+            }
+            (seismic, mainnet) => assert_eq!(seismic, mainnet, "outcome mismatch for {self:?}"),
+        }
+        true
+    }
+}
+
+/// Returns the addresses of the precompiles Mercury adds to the mainnet ones.
+pub fn seismic_precompile_addresses() -> Vec<Address> {
+    let (precompiles, scheduled, stateful) = mercury::<SeismicContext<InMemoryDB>>();
+    let mainnet = Precompiles::prague();
+    precompiles
+        .addresses()
+        .filter(|address| !mainnet.contains(address))
+        .chain(scheduled.addresses())
+        .chain(stateful.addresses())
+        .copied()
+        .collect()
+}
+
+fn touches_seismic_precompile(output: &DiffOutput) -> bool {
+    let Ok(output) = output else {
+        return false;
+    };
+    seismic_precompile_addresses()
+        .iter()
+        .any(|address| output.state.contains_key(address))
+}
+
+/// Replaces `CLOAD`, `CSTORE` and a leading EOF magic byte of raw bytecode with `INVALID`.
+fn sanitize(code: &[u8]) -> Vec<u8> {
+    let mut code = code.to_vec();
+    let mut i = 0;
+    while i < code.len() {
+        let op = code[i];
+        if op == CLOAD || op == CSTORE || (i == 0 && op == 0xEF) {
+            code[i] = INVALID;
+        }
+        if (PUSH1..=PUSH32).contains(&op) {
+            i += (op - PUSH1) as usize + 1;
+        }
+        i += 1;
+    }
+    code
+}
+
+/// Builds a program of well formed snippets, each chosen by one byte of `choices`.
+///
+/// Snippets push their own operands, so most programs run past their first instructions.
+/// Storage accesses use 16 slots to collide with the prestate and earlier writes.
+fn generate(choices: &[u8]) -> Vec<u8> {
+    let mut input = ByteReader(choices);
+    let mut code = Vec::new();
+    let push = |code: &mut Vec<u8>, value: u8| code.extend([PUSH1, value]);
+    let call = |code: &mut Vec<u8>, op: u8, target: &[u8]| {
+        // retSize retOffset argsSize argsOffset [value] address gas
+        code.extend([PUSH1, 0x20, PUSH1, 0, PUSH1, 0x20, PUSH1, 0]);
+        if op == CALL || op == CALLCODE {
+            code.extend([PUSH1, 0]);
+        }
+        code.push(PUSH1 + target.len() as u8 - 1);
+        code.extend_from_slice(target);
+        code.extend([GAS, op]);
+    };
+
+    while !input.is_empty() {
+        match input.byte() % 24 {
+            0 => push(&mut code, input.byte()),
+            1 => {
+                code.push(PUSH32);
+                code.extend_from_slice(&U256::from(input.byte()).to_be_bytes::<32>());
+                code[code.len() - 32] = input.byte();
+            }
+            2 => code
+                .push([ADD, MUL, SUB, DIV, SDIV, MOD, EXP, SIGNEXTEND][input.byte() as usize % 8]),
+            3 => code.push([LT, GT, SLT, SGT, EQ, ISZERO, AND, OR][input.byte() as usize % 8]),
+            4 => code
+                .push([XOR, NOT, BYTE, SHL, SHR, SAR, ADDMOD, MULMOD][input.byte() as usize % 8]),
+            5 | 6 => {
+                push(&mut code, input.byte() % 16);
+                code.push(SLOAD);
+            }
+            7 | 8 => {
+                push(&mut code, input.byte());
+                push(&mut code, input.byte() % 16);
+                code.push(SSTORE);
+            }
+            9 => {
+                // Stores the top of the stack.
+                push(&mut code, input.byte() % 16);
+                code.push(SSTORE);
+            }
+            10 => {
+                push(&mut code, input.byte());
+                push(&mut code, input.byte() % 16);
+                code.push(TSTORE);
+                push(&mut code, input.byte() % 16);
+                code.push(TLOAD);
+            }
+            11 => {
+                push(&mut code, input.byte());
+                push(&mut code, input.byte() % 64);
+                code.push(MSTORE);
+            }
+            12 => {
+                push(&mut code, input.byte() % 64);
+                code.push([MLOAD, CALLDATALOAD][input.byte() as usize % 2]);
+            }
+            13 | 14 => {
+                let op = [CALL, DELEGATECALL, STATICCALL, CALLCODE][input.byte() as usize % 4];
+                call(&mut code, op, CALLEE.as_slice());
+            }
+            15 => {
+                let precompile = input.byte() % MAINNET_PRECOMPILES + 1;
+                call(&mut code, STATICCALL, &[precompile]);
+            }
+            16 => code.extend([RETURNDATASIZE, PUSH1, 0x20, PUSH1, 0, KECCAK256]),
+            17 => {
+                push(&mut code, input.byte());
+                code.extend([PUSH1, 0x20, PUSH1, 0, LOG1]);
+            }
+            18 => code.push(
+                [
+                    GAS,
+                    ADDRESS,
+                    CALLER,
+                    CALLVALUE,
+                    SELFBALANCE,
+                    MSIZE,
+                    PC,
+                    CODESIZE,
+                ][input.byte() as usize % 8],
+            ),
+            19 => {
+                code.push(PUSH20);
+                code.extend_from_slice(CALLEE.as_slice());
+                code.push([BALANCE, EXTCODESIZE, EXTCODEHASH][input.byte() as usize % 3]);
+            }
+            20 => code.push([POP, DUP1, DUP2, SWAP1, SWAP2][input.byte() as usize % 5]),
+            21 => {
+                // Conditional jump over a `STOP`.
+                let target = code.len() + 5;
+                code.extend([
+                    PUSH2,
+                    (target >> 8) as u8,
+                    target as u8,
+                    JUMPI,
+                    STOP,
+                    JUMPDEST,
+                ]);
+            }
+            22 => code.extend([
+                PUSH1,
+                0x20,
+                PUSH1,
+                0,
+                [RETURN, REVERT][input.byte() as usize % 2],
+            ]),
+            _ => code.push([STOP, INVALID][input.byte() as usize % 2]),
+        }
+    }
+    code
+}
+
+/// Reads bytes of a fuzz input, yielding zeros once it is exhausted.
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn byte(&mut self) -> u8 {
+        self.take(1).first().copied().unwrap_or_default()
+    }
+
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let (head, tail) = self.0.split_at(len.min(self.0.len()));
+        self.0 = tail;
+        head
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        self.take(self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{collection::vec, prelude::any, test_runner::TestRunner};
+
+    #[test]
+    fn generated_code_is_not_confidential() {
+        let choices: Vec<u8> = (0..=255).collect();
+        let code = generate(&choices);
+        assert_eq!(sanitize(&code), code);
+        assert_eq!(
+            sanitize(&[CLOAD, PUSH1, CSTORE, CSTORE]),
+            [INVALID, PUSH1, CSTORE, INVALID]
+        );
+    }
+
+    #[test]
+    fn storage_program_matches() {
+        // Generated: sstore(3, 7) then sload(3), on a prestate holding slot 3.
+        let case = DiffCase::from_bytes(&[0, 0x40, 0, 1, 3, 9, 0, 7, 7, 3, 5, 3]);
+        assert_eq!(case.storage, [(U256::from(3), U256::from(9))]);
+        assert!(case.check());
+    }
+
+    #[test]
+    fn seismic_precompile_cases_are_skipped() {
+        let mut case = DiffCase::from_bytes(&[0, 0x40, 0]);
+        let precompile = seismic_precompile_addresses()[0];
+        let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+        code.extend_from_slice(precompile.as_slice());
+        code.extend([GAS, STATICCALL]);
+        case.code = code.into();
+        assert!(!case.check());
+    }
+
+    #[test]
+    fn differential_proptest() {
+        let mut runner = TestRunner::deterministic();
+        runner
+            .run(&vec(any::<u8>(), 0..512), |data| {
+                DiffCase::from_bytes(&data).check();
+                Ok(())
+            })
+            .unwrap();
+    }
+}
@@ -32,7 +32,7 @@ impl<CTX: SeismicContextTr, INSP>
     }
 }
 
-impl<CTX, INSP, I, P> std::ops::Deref for SeismicEvm<CTX, INSP, I, P> {
+impl<CTX, INSP, I, P> core::ops::Deref for SeismicEvm<CTX, INSP, I, P> {
     type Target = Evm<CTX, INSP, I, P>;
 
     fn deref(&self) -> &Self::Target {
@@ -40,7 +40,7 @@ impl<CTX, INSP, I, P> std::ops::Deref for SeismicEvm<CTX, INSP, I, P> {
     }
 }
 
-impl<CTX, INSP, I, P> std::ops::DerefMut for SeismicEvm<CTX, INSP, I, P> {
+impl<CTX, INSP, I, P> core::ops::DerefMut for SeismicEvm<CTX, INSP, I, P> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
@@ -144,11 +144,10 @@ mod tests {
     use super::*;
     use crate::precompiles::rng;
     use crate::precompiles::rng::domain_sep_rng::RootRng;
-    use crate::precompiles::rng::precompile::{calculate_fill_cost, calculate_init_cost};
     use crate::transaction::abstraction::SeismicTransaction;
     use crate::{
-        DefaultSeismic, SeismicBuilder, SeismicChain, SeismicContext, SeismicHaltReason,
-        SeismicSpecId,
+        DefaultSeismic, SeismicBuilder, SeismicChain, SeismicContext, SeismicGasSchedule,
+        SeismicHaltReason, SeismicSpecId,
     };
     use anyhow::bail;
     use rand_core::RngCore;
@@ -290,9 +289,10 @@ mod tests {
         let InitialAndFloorGas { initial_gas, .. } =
             calculate_initial_tx_gas(spec.into(), &input[..], false, 0, 0, 0);
 
+        let schedule = SeismicGasSchedule::default();
         let total_gas = initial_gas
-            + calculate_init_cost(personalization.len())
-            + calculate_fill_cost(bytes_requested as usize);
+            + schedule.rng_init_cost(personalization.len())
+            + schedule.rng_fill_cost(bytes_requested as usize);
 
         Context::seismic()
             .modify_tx_chained(|tx| {
@@ -341,8 +341,8 @@ mod tests {
             0 as u64,
         );
         assert!(
-            evm.ctx().chain().rng_container().leaf_rng().is_none(),
-            "leaf rng should be none post execution"
+            evm.ctx().chain().rng_container().leaf_rngs().is_empty(),
+            "leaf rngs should be cleared post execution"
         );
         assert_eq!(
             evm.ctx()
@@ -354,4 +354,108 @@ mod tests {
             "root rng state should be as expected"
         );
     }
+
+    fn call_precompile(
+        spec: SeismicSpecId,
+        address: u64,
+        input: Bytes,
+    ) -> (ExecutionResult<SeismicHaltReason>, u64) {
+        let InitialAndFloorGas {
+            initial_gas,
+            floor_gas,
+        } = calculate_initial_tx_gas(spec.into(), &input[..], false, 0, 0, 0);
+        let mut evm = Context::seismic()
+            .modify_tx_chained(|tx| {
+                tx.base.kind = TxKind::Call(u64_to_address(address));
+                tx.base.data = input;
+                tx.base.gas_limit = 1_000_000;
+            })
+            .modify_cfg_chained(|cfg| cfg.spec = spec)
+            .build_seismic();
+        (evm.replay().unwrap().result, initial_gas.max(floor_gas))
+    }
+
+    #[test]
+    fn test_venus_precompiles_are_reachable_from_venus_only() {
+        use crate::precompiles::aes::{aes_gcm_enc_aad, precompile_encrypt_aad};
+
+        let aad = b"header";
+        let plaintext = b"private payload";
+        let mut input = [[0x11; 32].as_slice(), &[0x22; 12]].concat();
+        input.extend_from_slice(&(aad.len() as u32).to_be_bytes());
+        input.extend_from_slice(aad);
+        input.extend_from_slice(plaintext);
+        let input = Bytes::from(input);
+
+        let (result, _) = call_precompile(
+            SeismicSpecId::VENUS,
+            aes_gcm_enc_aad::AES_GCM_ENC_AAD_ADDRESS,
+            input.clone(),
+        );
+        let expected = precompile_encrypt_aad(&input, u64::MAX, &SeismicGasSchedule::MERCURY)
+            .unwrap()
+            .bytes;
+        assert!(result.is_success(), "{result:?}");
+        assert_eq!(result.into_output().unwrap(), expected);
+
+        // Under MERCURY the address is an empty account: the call succeeds, returns nothing and
+        // costs only the intrinsic gas.
+        let (result, intrinsic_gas) = call_precompile(
+            SeismicSpecId::MERCURY,
+            aes_gcm_enc_aad::AES_GCM_ENC_AAD_ADDRESS,
+            input,
+        );
+        assert!(result.is_success(), "{result:?}");
+        assert_eq!(result.gas_used(), intrinsic_gas);
+        assert!(result.into_output().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "zeroize_memory")]
+    #[test]
+    fn test_shared_memory_wiped_after_transaction() {
+        use revm::bytecode::Bytecode;
+        use revm::context::LocalContextTr;
+        use revm::primitives::hex;
+        use revm::state::AccountInfo;
+
+        let secret = [0x5e; 32];
+        let caller = Address::with_last_byte(0xaa);
+        let callee = Address::with_last_byte(0xbb);
+        // Both frames store the secret in memory: `PUSH32 secret PUSH1 0 MSTORE STOP`.
+        let store_secret = [&[0x7f][..], &secret, &hex!("60005200")].concat();
+        // `CALL(GAS, callee, 0, 0, 0, 0, 0) POP`, then store the secret.
+        let call_callee = [
+            &hex!("6000600060006000600073")[..],
+            callee.as_slice(),
+            &hex!("5af150"),
+            &store_secret,
+        ]
+        .concat();
+
+        let mut db = InMemoryDB::default();
+        for (address, code) in [(caller, call_callee), (callee, store_secret)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::default().with_code(Bytecode::new_legacy(code.into())),
+            );
+        }
+        let mut evm = Context::seismic()
+            .with_db(db)
+            .modify_tx_chained(|tx| {
+                tx.base.kind = TxKind::Call(caller);
+                tx.base.gas_limit = 1_000_000;
+            })
+            .build_seismic();
+        let output = evm.replay().unwrap();
+        assert!(output.result.is_success(), "{:?}", output.result);
+
+        let buffer = evm.ctx().local().shared_memory_buffer().borrow();
+        assert!(buffer.is_empty());
+        // SAFETY: Wiping the buffer initialized its whole capacity.
+        let capacity = unsafe { core::slice::from_raw_parts(buffer.as_ptr(), buffer.capacity()) };
+        assert!(
+            capacity.iter().all(|byte| *byte == 0),
+            "no memory of the transaction should remain in the shared buffer"
+        );
+    }
 }
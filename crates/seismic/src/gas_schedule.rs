@@ -0,0 +1,134 @@
+//! Gas schedule of the Seismic precompiles and opcodes.
+//!
+//! Each spec has a default schedule, see [`SeismicGasSchedule::new`]. Devnets can re-tune it
+//! without forking the crate by setting an override on the chain, see
+//! [`SeismicChain::with_gas_schedule`](crate::SeismicChain::with_gas_schedule).
+use revm::precompile::calc_linear_cost_u32;
+
+use crate::SeismicSpecId;
+
+/// Gas parameters of the Seismic precompiles and opcodes.
+///
+/// The rationale behind each default lives with the precompile using it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeismicGasSchedule {
+    /// Base cost of initializing a leaf RNG.
+    pub rng_init_base: u64,
+    /// Cost per 32-byte word absorbed or squeezed by the Strobe128 sponge of the RNG.
+    pub rng_per_word: u64,
+    /// Overhead of AES-GCM setup and finalization.
+    pub aes_gcm_base: u64,
+    /// Cost per 16-byte AES-GCM block, one AES encryption and one GHASH multiply.
+    pub aes_gcm_per_block: u64,
+    /// Cost of a signature, or of a comparable scalar multiplication: secp256k1, Schnorr and
+    /// Ed25519 signing and verification, and secp256k1 key derivation.
+    pub signature_base: u64,
+    /// Cost of an ECDH shared secret.
+    pub shared_secret: u64,
+    /// Base cost of a SHA-256 pass.
+    pub sha256_base: u64,
+    /// Cost per 32-byte word of a SHA-256 pass.
+    pub sha256_per_word: u64,
+    /// Cost per 32-byte word of a SHA-512 pass.
+    pub sha512_per_word: u64,
+    /// Charged by `CLOAD` on top of the `SLOAD` cost.
+    pub cload_surcharge: u64,
+    /// Charged by `CSTORE` on top of the `SSTORE` cost.
+    pub cstore_surcharge: u64,
+}
+
+impl SeismicGasSchedule {
+    /// Schedule of the `MERCURY` spec.
+    pub const MERCURY: Self = Self {
+        rng_init_base: 3500,
+        rng_per_word: 5,
+        aes_gcm_base: 1000,
+        aes_gcm_per_block: 30,
+        signature_base: 3000,
+        shared_secret: 3000,
+        sha256_base: 60,
+        sha256_per_word: 12,
+        sha512_per_word: 12,
+        cload_surcharge: 0,
+        cstore_surcharge: 0,
+    };
+
+    /// Returns the default schedule of `spec`.
+    pub const fn new(spec: SeismicSpecId) -> Self {
+        match spec {
+            SeismicSpecId::MERCURY | SeismicSpecId::VENUS => Self::MERCURY,
+        }
+    }
+
+    /// Cost of initializing a leaf RNG whose transcript holds `pers_len` personalization bytes.
+    pub fn rng_init_cost(&self, pers_len: usize) -> u64 {
+        calc_linear_cost_u32(pers_len, self.rng_init_base, self.rng_per_word)
+    }
+
+    /// Cost of filling `fill_len` bytes from an initialized leaf RNG.
+    pub fn rng_fill_cost(&self, fill_len: usize) -> u64 {
+        calc_linear_cost_u32(fill_len, 0, self.rng_per_word)
+    }
+
+    /// Cost of encrypting or decrypting `len` bytes with AES-GCM.
+    pub fn aes_gcm_cost(&self, len: usize) -> u64 {
+        calc_block_cost(len, self.aes_gcm_base, self.aes_gcm_per_block)
+    }
+
+    /// Cost of the AAD variants of AES-GCM. The AAD and the plaintext are padded to blocks
+    /// separately before GHASH, so both are counted in blocks on their own.
+    pub fn aes_gcm_aad_cost(&self, plaintext_len: usize, aad_len: usize) -> u64 {
+        self.aes_gcm_cost(plaintext_len) + calc_block_cost(aad_len, 0, self.aes_gcm_per_block)
+    }
+
+    /// Cost of an HMAC-SHA256 over `input_len` bytes, two SHA-256 passes (inner and outer).
+    pub fn hmac_sha256_cost(&self, input_len: usize) -> u64 {
+        2 * calc_linear_cost_u32(input_len, self.sha256_base, self.sha256_per_word)
+    }
+
+    /// Cost of an HKDF expand step with a short, fixed-length info, approximated as two
+    /// SHA-256 base costs.
+    pub fn hkdf_expand_cost(&self) -> u64 {
+        2 * self.sha256_base
+    }
+
+    /// Cost of an ECDH shared secret followed by an HKDF expand step.
+    pub fn derive_sym_key_cost(&self) -> u64 {
+        self.shared_secret + self.hkdf_expand_cost()
+    }
+
+    /// Cost of `passes` SHA-512 passes over `len` bytes, on top of `base`.
+    pub fn sha512_cost(&self, len: usize, base: u64, passes: u64) -> u64 {
+        calc_linear_cost_u32(len, base, passes * self.sha512_per_word)
+    }
+}
+
+impl Default for SeismicGasSchedule {
+    fn default() -> Self {
+        Self::new(SeismicSpecId::default())
+    }
+}
+
+/// Linear cost per 16-byte block.
+fn calc_block_cost(len: usize, base: u64, per_block: u64) -> u64 {
+    (len as u64).div_ceil(16) * per_block + base
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_specs_keep_mercury_costs() {
+        for spec in [SeismicSpecId::MERCURY, SeismicSpecId::VENUS] {
+            let schedule = SeismicGasSchedule::new(spec);
+            assert_eq!(schedule.rng_init_cost(0), 3500);
+            assert_eq!(schedule.rng_fill_cost(33), 10);
+            assert_eq!(schedule.aes_gcm_cost(17), 1060);
+            assert_eq!(schedule.aes_gcm_aad_cost(16, 17), 1090);
+            assert_eq!(schedule.hmac_sha256_cost(32), 144);
+            assert_eq!(schedule.derive_sym_key_cost(), 3120);
+        }
+    }
+}
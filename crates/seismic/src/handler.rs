@@ -3,7 +3,7 @@ use crate::{api::exec::SeismicContextTr, SeismicHaltReason};
 use revm::{
     context::{
         result::{ExecutionResult, InvalidTransaction, ResultAndState},
-        ContextTr, JournalTr, Transaction,
+        ContextTr, JournalTr, LocalContextTr, Transaction,
     },
     context_interface::{context::ContextError, result::FromStringError},
     handler::{
@@ -12,6 +12,7 @@ use revm::{
     inspector::{Inspector, InspectorEvmTr, InspectorFrame, InspectorHandler},
     interpreter::{interpreter::EthInterpreter, FrameInput},
 };
+use std::string::ToString;
 
 pub struct SeismicHandler<EVM, ERROR, FRAME> {
     pub mainnet: MainnetHandler<EVM, ERROR, FRAME>,
@@ -66,6 +67,7 @@ where
                     SeismicHaltReason::try_from_error_string(&e.to_string())
                 {
                     let state = evm.ctx().journal().finalize().state;
+                    evm.ctx().local().clear();
                     evm.ctx().journal().clear();
                     evm.ctx().chain().reset_rng();
 
@@ -82,6 +84,7 @@ where
             }
             Ok(_) => {
                 let output = post_execution::output(evm.ctx(), result);
+                evm.ctx().local().clear();
                 evm.ctx().journal().clear();
                 evm.ctx().chain().reset_rng();
                 Ok(output)
@@ -100,6 +103,8 @@ where
         evm: &mut Self::Evm,
         error: Self::Error,
     ) -> Result<ResultAndState<Self::HaltReason>, Self::Error> {
+        // Clean up local context, which also wipes the shared memory.
+        evm.ctx().local().clear();
         // Clean up journal state if error occurs
         evm.ctx().journal().clear();
         evm.ctx().chain().reset_rng();
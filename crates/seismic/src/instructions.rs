@@ -1,5 +1,7 @@
 pub mod confidential_storage;
 pub mod instruction_provider;
 pub mod seismic_host;
+#[cfg(test)]
+mod storage_invariants;
 #[macro_use]
 pub mod macros;
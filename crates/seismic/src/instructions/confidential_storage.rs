@@ -25,6 +25,7 @@ pub fn cload<WIRE: InterpreterTypes, H: SeismicHost + ?Sized>(
         gas!(
             interpreter,
             gas::sload_cost(interpreter.runtime_flag.spec_id(), value.is_cold)
+                + host.gas_schedule().cload_surcharge
         );
         *index = value.data;
     } else {
@@ -35,7 +36,7 @@ pub fn cload<WIRE: InterpreterTypes, H: SeismicHost + ?Sized>(
     }
 }
 
-pub fn cstore<WIRE: InterpreterTypes, H: Host + ?Sized>(
+pub fn cstore<WIRE: InterpreterTypes, H: SeismicHost + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
     host: &mut H,
 ) {
@@ -65,7 +66,7 @@ pub fn cstore<WIRE: InterpreterTypes, H: Host + ?Sized>(
             interpreter.runtime_flag.spec_id(),
             &state_load.data,
             state_load.is_cold
-        )
+        ) + host.gas_schedule().cstore_surcharge
     );
 
     interpreter
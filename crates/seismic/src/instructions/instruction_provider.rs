@@ -1,5 +1,5 @@
 use revm::{
-    bytecode::opcode::{SLOAD, SSTORE},
+    bytecode::opcode::{stack_io, OpCodeInfo, OpCodeTable, SLOAD, SSTORE},
     handler::instructions::InstructionProvider,
     interpreter::{
         instructions::{instruction_table, InstructionTable},
@@ -18,6 +18,13 @@ use super::confidential_storage::{
 pub const CLOAD: u8 = 0xB0;
 pub const CSTORE: u8 = 0xB1;
 
+/// Opcode info of the Seismic instruction set: the mainnet opcodes plus CLOAD and CSTORE.
+///
+/// Used to disassemble and EOF validate Seismic bytecode.
+pub static SEISMIC_OPCODES: OpCodeTable = OpCodeTable::MAINNET
+    .with_opcode(CLOAD, stack_io(OpCodeInfo::new("CLOAD"), 1, 1))
+    .with_opcode(CSTORE, stack_io(OpCodeInfo::new("CSTORE"), 2, 0));
+
 /// Seismic instruction provider that adds our instruction set
 pub struct SeismicInstructions<WIRE: InterpreterTypes, HOST> {
     pub instruction_table: Box<InstructionTable<WIRE, HOST>>,
@@ -73,6 +80,10 @@ where
     fn instruction_table(&self) -> &InstructionTable<Self::InterpreterTypes, Self::Context> {
         &self.instruction_table
     }
+
+    fn opcode_table(&self) -> &'static OpCodeTable {
+        &SEISMIC_OPCODES
+    }
 }
 
 #[cfg(test)]
@@ -148,6 +159,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seismic_opcode_info() {
+        let cload = SEISMIC_OPCODES.get(CLOAD).unwrap();
+        assert_eq!(cload.name(), "CLOAD");
+        assert_eq!((cload.inputs(), cload.outputs()), (1, 1));
+        assert!(!cload.is_disabled_in_eof());
+
+        let cstore = SEISMIC_OPCODES.get(CSTORE).unwrap();
+        assert_eq!(cstore.name(), "CSTORE");
+        assert_eq!((cstore.inputs(), cstore.outputs()), (2, 0));
+        assert_eq!(cstore.immediate_size(), 0);
+
+        // Same stack behaviour as their public counterparts.
+        let sload = SEISMIC_OPCODES.get(SLOAD).unwrap();
+        let sstore = SEISMIC_OPCODES.get(SSTORE).unwrap();
+        assert_eq!(cload.io_diff(), sload.io_diff());
+        assert_eq!(cstore.io_diff(), sstore.io_diff());
+
+        let instructions = SeismicInstructions::<EthInterpreter, SeismicDummyHost>::new_mainnet();
+        assert!(core::ptr::eq(instructions.opcode_table(), &SEISMIC_OPCODES));
+    }
+
     #[test]
     fn test_insert_instruction() {
         // Create a base SeismicInstructions
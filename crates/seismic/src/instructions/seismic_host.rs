@@ -6,7 +6,8 @@ use revm::{
     primitives::{Address, Bytes, Log, B256, U256},
 };
 
-use crate::{api::exec::SeismicContextTr, SeismicHaltReason};
+use crate::{api::exec::SeismicContextTr, SeismicGasSchedule, SeismicHaltReason};
+use std::string::ToString;
 
 // Extend Host with an associated Db type and error() method
 pub trait SeismicHost: Host {
@@ -23,6 +24,9 @@ pub trait SeismicHost: Host {
     fn set_halt_reason(&mut self, reason: SeismicHaltReason) {
         *self.ctx_error() = Err(ContextError::Custom(reason.to_string()));
     }
+
+    /// Gas schedule of the Seismic opcodes for the current spec.
+    fn gas_schedule(&self) -> SeismicGasSchedule;
 }
 
 impl<CTX> SeismicHost for CTX
@@ -34,6 +38,10 @@ where
     fn ctx_error(&mut self) -> &mut Result<(), ContextError<<Self::Db as Database>::Error>> {
         <Self as ContextTr>::error(self)
     }
+
+    fn gas_schedule(&self) -> SeismicGasSchedule {
+        SeismicContextTr::gas_schedule(self)
+    }
 }
 
 pub struct SeismicDummyHost {
@@ -56,6 +64,10 @@ impl SeismicHost for SeismicDummyHost {
     fn ctx_error(&mut self) -> &mut Result<(), ContextError<<Self::Db as Database>::Error>> {
         &mut self.ctx_result
     }
+
+    fn gas_schedule(&self) -> SeismicGasSchedule {
+        SeismicGasSchedule::default()
+    }
 }
 
 impl Host for SeismicDummyHost {
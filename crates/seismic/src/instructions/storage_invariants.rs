@@ -0,0 +1,312 @@
+//! Property tests of the flagged storage access rules.
+//!
+//! Random programs of `SLOAD`, `SSTORE`, `CLOAD` and `CSTORE`, spread over nested `CALL` and
+//! `DELEGATECALL` frames that may revert, are checked against a reference model:
+//!
+//! - `SLOAD` of a private slot aborts the transaction.
+//! - `CLOAD` of a public slot aborts the transaction, unless the slot holds zero.
+//! - Stores are allowed whatever the flag of the slot and tag it with the flag of the
+//!   instruction. Storing the value a slot already holds leaves the slot untouched.
+//! - Reverting a frame restores the values and flags of the slots it wrote.
+//!
+//! Every load logs the loaded value, so the values seen by the program are compared as well.
+use super::instruction_provider::{CLOAD, CSTORE};
+use crate::{DefaultSeismic, SeismicBuilder, SeismicHaltReason};
+use core::convert::Infallible;
+use proptest::{collection::vec, prelude::*};
+use revm::{
+    bytecode::{opcode::*, Bytecode},
+    context::{
+        result::{EVMError, ExecutionResult, ResultAndState},
+        Context,
+    },
+    database::InMemoryDB,
+    primitives::{Address, Bytes, FlaggedStorage, HashMap, TxKind, U256},
+    state::AccountInfo,
+    ExecuteEvm,
+};
+
+/// Slots used by the programs, few enough for accesses to collide.
+const SLOTS: u8 = 4;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Sload(u8),
+    Cload(u8),
+    Sstore(u8, u8),
+    Cstore(u8, u8),
+    Call { delegate: bool, frame: Frame },
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    ops: Vec<Op>,
+    reverts: bool,
+}
+
+/// Address of the contract running the frame of depth first index `index`.
+fn contract_address(index: usize) -> Address {
+    let mut address = [0u8; 20];
+    address[18] = 0xc0;
+    address[19] = index as u8;
+    Address::from(address)
+}
+
+/// Compiles the frame and its children, in depth first order, into `contracts`.
+fn compile(frame: &Frame, contracts: &mut Vec<Bytes>) -> Address {
+    let index = contracts.len();
+    contracts.push(Bytes::new());
+    let mut code = Vec::new();
+    for op in &frame.ops {
+        match op {
+            Op::Sload(slot) | Op::Cload(slot) => {
+                let load = if matches!(op, Op::Sload(_)) {
+                    SLOAD
+                } else {
+                    CLOAD
+                };
+                code.extend([PUSH1, *slot, load]);
+                code.extend([PUSH1, 0, MSTORE, PUSH1, 0x20, PUSH1, 0, LOG0]);
+            }
+            Op::Sstore(slot, value) => code.extend([PUSH1, *value, PUSH1, *slot, SSTORE]),
+            Op::Cstore(slot, value) => code.extend([PUSH1, *value, PUSH1, *slot, CSTORE]),
+            Op::Call { delegate, frame } => {
+                let target = compile(frame, contracts);
+                code.extend([PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0]);
+                if !delegate {
+                    code.extend([PUSH1, 0]);
+                }
+                code.push(PUSH20);
+                code.extend_from_slice(target.as_slice());
+                code.extend([GAS, if *delegate { DELEGATECALL } else { CALL }, POP]);
+            }
+        }
+    }
+    if frame.reverts {
+        code.extend([PUSH1, 0, PUSH1, 0, REVERT]);
+    } else {
+        code.push(STOP);
+    }
+    contracts[index] = code.into();
+    contract_address(index)
+}
+
+/// Reference model of the flagged storage rules.
+#[derive(Debug, Default)]
+struct Model {
+    storage: HashMap<(Address, u8), FlaggedStorage>,
+    logs: Vec<U256>,
+    /// Number of frames entered so far, numbers them like [`compile`].
+    frames: usize,
+}
+
+impl Model {
+    fn slot(&self, address: Address, slot: u8) -> FlaggedStorage {
+        self.storage
+            .get(&(address, slot))
+            .copied()
+            .unwrap_or(FlaggedStorage::ZERO)
+    }
+
+    fn store(&mut self, address: Address, slot: u8, value: u8, is_private: bool) {
+        let value = U256::from(value);
+        if self.slot(address, slot).value != value {
+            self.storage
+                .insert((address, slot), FlaggedStorage::new(value, is_private));
+        }
+    }
+
+    /// Runs the next frame in the storage of `context`, or of its own contract if `None`.
+    fn run(&mut self, frame: &Frame, context: Option<Address>) -> Result<(), SeismicHaltReason> {
+        let context = context.unwrap_or(contract_address(self.frames));
+        self.frames += 1;
+        let storage = self.storage.clone();
+        let logs = self.logs.len();
+        for op in &frame.ops {
+            match *op {
+                Op::Sload(slot) => {
+                    let value = self.slot(context, slot);
+                    if value.is_private {
+                        return Err(SeismicHaltReason::InvalidPrivateStorageAccess);
+                    }
+                    self.logs.push(value.value);
+                }
+                Op::Cload(slot) => {
+                    let value = self.slot(context, slot);
+                    if !value.is_private && !value.value.is_zero() {
+                        return Err(SeismicHaltReason::InvalidPublicStorageAccess);
+                    }
+                    self.logs.push(value.value);
+                }
+                Op::Sstore(slot, value) => self.store(context, slot, value, false),
+                Op::Cstore(slot, value) => self.store(context, slot, value, true),
+                Op::Call {
+                    delegate,
+                    ref frame,
+                } => self.run(frame, delegate.then_some(context))?,
+            }
+        }
+        if frame.reverts {
+            self.storage = storage;
+            self.logs.truncate(logs);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the access rule violation that aborted the transaction, if any.
+///
+/// A violation in the first frame halts the transaction, in a nested frame it surfaces as an
+/// error.
+fn violation(
+    output: &Result<ResultAndState<SeismicHaltReason>, EVMError<Infallible>>,
+) -> Option<SeismicHaltReason> {
+    match output {
+        Ok(ResultAndState {
+            result: ExecutionResult::Halt { reason, .. },
+            ..
+        }) => Some(reason.clone()),
+        Err(EVMError::Custom(error)) => SeismicHaltReason::try_from_error_string(error),
+        _ => None,
+    }
+}
+
+/// Runs the program on the prestate and checks it against the model.
+///
+/// Slot `s` of the contract of frame `i` starts as `prestate[(i * SLOTS + s) % len]`.
+fn check(frame: &Frame, prestate: &[(u8, bool)]) {
+    let mut contracts = Vec::new();
+    let entry = compile(frame, &mut contracts);
+
+    let mut model = Model::default();
+    let mut db = InMemoryDB::default();
+    for (index, code) in contracts.into_iter().enumerate() {
+        let address = contract_address(index);
+        db.insert_account_info(
+            address,
+            AccountInfo::default().with_code(Bytecode::new_legacy(code)),
+        );
+        for slot in 0..SLOTS {
+            let (value, is_private) =
+                prestate[(index * SLOTS as usize + slot as usize) % prestate.len()];
+            let value = FlaggedStorage::new(U256::from(value), is_private);
+            model.storage.insert((address, slot), value);
+            db.insert_account_storage(address, U256::from(slot), value)
+                .unwrap();
+        }
+    }
+    let expected = model.run(frame, None);
+
+    let output = Context::seismic()
+        .with_db(db)
+        .modify_tx_chained(|tx| {
+            tx.base.kind = TxKind::Call(entry);
+            tx.base.gas_limit = 10_000_000;
+        })
+        .build_seismic()
+        .replay();
+
+    if let Err(reason) = expected {
+        assert_eq!(violation(&output), Some(reason), "{output:?}");
+        return;
+    }
+    let output = output.expect("transaction without violations must execute");
+    assert_eq!(violation(&Ok(output.clone())), None);
+    assert_eq!(
+        output.result.is_success(),
+        !frame.reverts,
+        "{:?}",
+        output.result
+    );
+
+    let logs: Vec<U256> = output
+        .result
+        .logs()
+        .iter()
+        .map(|log| U256::from_be_slice(&log.data.data))
+        .collect();
+    assert_eq!(logs, model.logs, "loaded values");
+
+    for (&(address, slot), expected) in &model.storage {
+        let actual = output
+            .state
+            .get(&address)
+            .and_then(|account| account.storage.get(&U256::from(slot)));
+        if let Some(actual) = actual {
+            assert_eq!(actual.present_value, *expected, "slot {slot} of {address}");
+        }
+    }
+}
+
+fn value() -> impl Strategy<Value = u8> {
+    prop_oneof![Just(0u8), 1..4u8, any::<u8>()]
+}
+
+fn storage_op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (0..SLOTS).prop_map(Op::Sload),
+        (0..SLOTS).prop_map(Op::Cload),
+        (0..SLOTS, value()).prop_map(|(slot, value)| Op::Sstore(slot, value)),
+        (0..SLOTS, value()).prop_map(|(slot, value)| Op::Cstore(slot, value)),
+    ]
+}
+
+fn frame() -> impl Strategy<Value = Frame> {
+    let leaf = (vec(storage_op(), 0..6), prop::bool::weighted(0.25))
+        .prop_map(|(ops, reverts)| Frame { ops, reverts });
+    leaf.prop_recursive(3, 16, 4, |frame| {
+        let op = prop_oneof![
+            3 => storage_op(),
+            1 => (any::<bool>(), frame).prop_map(|(delegate, frame)| Op::Call { delegate, frame }),
+        ];
+        (vec(op, 0..6), prop::bool::weighted(0.25))
+            .prop_map(|(ops, reverts)| Frame { ops, reverts })
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn flagged_storage_rules(frame in frame(), prestate in vec((value(), any::<bool>()), 1..16)) {
+        check(&frame, &prestate);
+    }
+}
+
+#[test]
+fn revert_restores_public_flag() {
+    // A reverted delegate frame makes a public slot private, SLOAD must still see it public.
+    let frame = Frame {
+        ops: vec![
+            Op::Call {
+                delegate: true,
+                frame: Frame {
+                    ops: vec![Op::Cstore(0, 2)],
+                    reverts: true,
+                },
+            },
+            Op::Sload(0),
+        ],
+        reverts: false,
+    };
+    check(&frame, &[(1, false)]);
+}
+
+#[test]
+fn revert_restores_private_flag() {
+    // A reverted delegate frame makes a private slot public, CLOAD must still see it private.
+    let frame = Frame {
+        ops: vec![
+            Op::Call {
+                delegate: true,
+                frame: Frame {
+                    ops: vec![Op::Sstore(0, 2)],
+                    reverts: true,
+                },
+            },
+            Op::Cload(0),
+        ],
+        reverts: false,
+    };
+    check(&frame, &[(1, true)]);
+}
@@ -3,11 +3,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(not(feature = "std"))]
+#[macro_use]
 extern crate alloc as std;
 
 pub mod api;
 pub mod chain;
+#[cfg(any(test, feature = "differential"))]
+pub mod differential;
 pub mod evm;
+pub mod gas_schedule;
 pub mod handler;
 pub mod instructions;
 pub mod precompiles;
@@ -21,6 +25,7 @@ pub use api::{
 };
 pub use chain::seismic_chain::SeismicChain;
 pub use evm::SeismicEvm;
+pub use gas_schedule::SeismicGasSchedule;
 pub use instructions::seismic_host::SeismicHost;
 pub use result::SeismicHaltReason;
 pub use spec::*;
@@ -6,23 +6,44 @@
 //!
 //! # Modules
 //!
-//! - [`aes`]: Provides AES-GCM encryption and decryption functionalities.
+//! - [`aes`]: Provides AES-GCM encryption and decryption functionalities, and from
+//!     `VENUS` variants taking associated data with a detached tag.
+//! - [`contract_key`]: From `VENUS`, derives stable per-contract keys from the chain master
+//!     key, so contracts need not keep key material in private storage.
 //! - [`ecdh_derive_sym_key`]: Implements symmetric key derivation using
 //!     Elliptic Curve Diffie-Hellman (ECDH) with the secp256k1 curve,
 //!     producing an AES-compatible key.
 //! - [`hkdf_derive_sym_key`]: Implements key derivation using the HKDF
 //!     (HMAC-based Key Derivation Function) algorithm to derive AES-compatible
 //!     symmetric keys from raw input bytes.
+//! - [`hpke`]: From `VENUS`, seals data to a secp256k1 public key with HPKE, with an
+//!     ephemeral key drawn from the RNG, and opens it with the matching secret key.
+//! - [`schnorr`] and [`ed25519`]: From `VENUS`, sign and verify BIP-340 Schnorr and Ed25519
+//!     signatures.
+//! - [`secp256k1_keys`]: From `VENUS`, derives the public keys and address of a secp256k1
+//!     secret key, or generates a keypair from the RNG.
 //! - [`rng`]: Generates cryptographically secure random bytes. The randomness
 //!     is based on a secret Verifiable Random Function (VRF) key and the
 //!     block's transcript.
+//!
+//! The stateless Seismic precompiles are kept apart from the Ethereum ones, in
+//! [`ScheduledPrecompiles`], so that their gas is read from the
+//! [`SeismicGasSchedule`](crate::SeismicGasSchedule) of the chain at call time.
 
 pub mod aes;
+pub mod contract_key;
 pub mod ecdh_derive_sym_key;
+pub mod ed25519;
 pub mod hkdf_derive_sym_key;
+pub mod hpke;
 pub mod rng;
+pub mod scheduled_precompile;
+pub mod schnorr;
+pub mod secp256k1_keys;
 pub mod secp256k1_sign;
+mod secret;
 pub mod stateful_precompile;
+pub use scheduled_precompile::ScheduledPrecompiles;
 pub use stateful_precompile::StatefulPrecompiles;
 
 use crate::{api::exec::SeismicContextTr, SeismicSpecId};
@@ -40,26 +61,36 @@ use std::string::String;
 #[derive(Debug, Clone)]
 pub struct SeismicPrecompiles<CTX: SeismicContextTr> {
     pub(crate) inner: EthPrecompiles,
+    scheduled_precompiles: &'static ScheduledPrecompiles,
     stateful_precompiles: StatefulPrecompiles<CTX>,
 }
 
+/// Regular, scheduled and stateful precompiles of a spec.
+pub type SeismicPrecompileSet<CTX> = (
+    &'static Precompiles,
+    &'static ScheduledPrecompiles,
+    StatefulPrecompiles<CTX>,
+);
+
 impl<CTX: SeismicContextTr> SeismicPrecompiles<CTX> {
     /// Create a new [`SeismicPrecompiles`] with the given precompiles.
-    pub fn new(precompiles: (&'static Precompiles, StatefulPrecompiles<CTX>)) -> Self {
+    pub fn new(precompiles: SeismicPrecompileSet<CTX>) -> Self {
         Self {
             inner: EthPrecompiles {
                 precompiles: precompiles.0,
                 spec: <CTX::Cfg as Cfg>::Spec::MERCURY.into(),
             },
-            stateful_precompiles: precompiles.1,
+            scheduled_precompiles: precompiles.1,
+            stateful_precompiles: precompiles.2,
         }
     }
 
-    /// Create a new precompile provider with the given optimismispec.
+    /// Create a new precompile provider with the given seismic spec.
     #[inline]
     pub fn new_with_spec(spec: SeismicSpecId) -> Self {
         match spec {
-            _spec @ SeismicSpecId::MERCURY => Self::new(mercury::<CTX>()),
+            SeismicSpecId::MERCURY => Self::new(mercury::<CTX>()),
+            SeismicSpecId::VENUS => Self::new(venus::<CTX>()),
         }
     }
 }
@@ -67,9 +98,10 @@ impl<CTX: SeismicContextTr> SeismicPrecompiles<CTX> {
 /// Returns precompiles for MERCURY spec.
 pub fn mercury_with_extra<CTX: SeismicContextTr>(
     extra: Option<&'static Precompiles>,
-) -> (&'static Precompiles, StatefulPrecompiles<CTX>) {
+) -> SeismicPrecompileSet<CTX> {
     // Store only the stateless precompiles in the static OnceBox
     static INSTANCE: OnceBox<Precompiles> = OnceBox::new();
+    static SCHEDULED: OnceBox<ScheduledPrecompiles> = OnceBox::new();
 
     let regular_precompiles = INSTANCE.get_or_init(|| {
         let mut precompiles = Precompiles::prague().clone();
@@ -82,8 +114,12 @@ pub fn mercury_with_extra<CTX: SeismicContextTr>(
                     .map(|(a, p)| PrecompileWithAddress(a, p)),
             );
         }
+        precompiles.extend([secp256r1::P256VERIFY]);
+        Box::new(precompiles)
+    });
+    let scheduled_precompiles = SCHEDULED.get_or_init(|| {
+        let mut precompiles = ScheduledPrecompiles::new();
         precompiles.extend([
-            secp256r1::P256VERIFY,
             ecdh_derive_sym_key::ECDH,
             hkdf_derive_sym_key::HKDF,
             aes::aes_gcm_enc::AES_GCM_ENC,
@@ -96,14 +132,49 @@ pub fn mercury_with_extra<CTX: SeismicContextTr>(
     //TODO: check how expensive is the below instead of a single init! issue with generics
     let mut stateful_precompiles = StatefulPrecompiles::new();
     stateful_precompiles.extend(rng::precompile::rng_precompile_iter::<CTX>().map(|p| (p.0, p.1)));
-    (regular_precompiles, stateful_precompiles)
+    (
+        regular_precompiles,
+        scheduled_precompiles,
+        stateful_precompiles,
+    )
 }
 
 /// Returns precompiles for MERCURY spec.
-pub fn mercury<CTX: SeismicContextTr>() -> (&'static Precompiles, StatefulPrecompiles<CTX>) {
+pub fn mercury<CTX: SeismicContextTr>() -> SeismicPrecompileSet<CTX> {
     mercury_with_extra::<CTX>(None)
 }
 
+/// Returns precompiles for VENUS spec.
+pub fn venus<CTX: SeismicContextTr>() -> SeismicPrecompileSet<CTX> {
+    static SCHEDULED: OnceBox<ScheduledPrecompiles> = OnceBox::new();
+
+    let (regular_precompiles, mercury, mut stateful_precompiles) = mercury::<CTX>();
+    let scheduled_precompiles = SCHEDULED.get_or_init(|| {
+        let mut precompiles = mercury.clone();
+        precompiles.extend([
+            aes::aes_gcm_enc_aad::AES_GCM_ENC_AAD,
+            aes::aes_gcm_dec_aad::AES_GCM_DEC_AAD,
+            hpke::HPKE_OPEN,
+            schnorr::SCHNORR_SIGN,
+            schnorr::SCHNORR_VERIFY,
+            ed25519::ED25519_SIGN,
+            ed25519::ED25519_VERIFY,
+        ]);
+        Box::new(precompiles)
+    });
+    stateful_precompiles.extend(
+        hpke::hpke_seal_precompile_iter::<CTX>()
+            .chain(secp256k1_keys::secp256k1_keys_precompile_iter::<CTX>())
+            .chain(contract_key::contract_key_precompile_iter::<CTX>())
+            .map(|p| (p.0, p.1)),
+    );
+    (
+        regular_precompiles,
+        scheduled_precompiles,
+        stateful_precompiles,
+    )
+}
+
 impl<CTX> PrecompileProvider<CTX> for SeismicPrecompiles<CTX>
 where
     CTX: SeismicContextTr,
@@ -126,13 +197,7 @@ where
         is_static: bool,
         gas_limit: u64,
     ) -> Result<Option<Self::Output>, String> {
-        if let Some(precompile) = self.stateful_precompiles.get(address) {
-            let mut result = InterpreterResult {
-                result: InstructionResult::Return,
-                gas: Gas::new(gas_limit),
-                output: Bytes::new(),
-            };
-
+        let output = if let Some(precompile) = self.stateful_precompiles.get(address) {
             // Extract the bytes first, creating owned data to avoid lifetime issues
             let bytes = match &inputs.input {
                 CallInput::SharedBuffer(range) => {
@@ -147,43 +212,77 @@ where
             };
 
             // Now call the precompile with the owned bytes
-            match (*precompile)(context, &bytes, gas_limit) {
-                Ok(output) => {
-                    let underflow = result.gas.record_cost(output.gas_used);
-                    assert!(underflow, "Gas underflow should not occur");
-                    result.result = InstructionResult::Return;
-                    result.output = output.bytes;
-                }
-                Err(PrecompileError::Fatal(e)) => return Err(e),
-                Err(e) => {
-                    result.result = if e.is_oog() {
-                        InstructionResult::PrecompileOOG
+            let output = (*precompile)(context, &bytes, gas_limit, inputs.caller_address);
+            // The copy may hold keys or plaintext, wipe it once the precompile is done. Input
+            // given as bytes is shared with the caller and is left as is.
+            #[cfg(feature = "zeroize_memory")]
+            revm::interpreter::interpreter::zeroize_bytes(bytes);
+            output
+        } else if let Some(precompile) = self.scheduled_precompiles.get(address) {
+            let schedule = context.gas_schedule();
+            let r;
+            let input_bytes = match &inputs.input {
+                CallInput::SharedBuffer(range) => {
+                    if let Some(slice) = context.local().shared_memory_buffer_slice(range.clone()) {
+                        r = slice;
+                        r.as_ref()
                     } else {
-                        InstructionResult::PrecompileError
-                    };
+                        &[]
+                    }
                 }
-            }
-
-            Ok(Some(result))
+                CallInput::Bytes(bytes) => bytes.0.iter().as_slice(),
+            };
+            (*precompile)(input_bytes, gas_limit, &schedule)
         } else {
             // Fall back to standard precompiles
-            self.inner
-                .run(context, address, inputs, is_static, gas_limit)
+            return self
+                .inner
+                .run(context, address, inputs, is_static, gas_limit);
+        };
+
+        let mut result = InterpreterResult {
+            result: InstructionResult::Return,
+            gas: Gas::new(gas_limit),
+            output: Bytes::new(),
+        };
+        match output {
+            Ok(output) => {
+                let underflow = result.gas.record_cost(output.gas_used);
+                assert!(underflow, "Gas underflow should not occur");
+                result.result = InstructionResult::Return;
+                result.output = output.bytes;
+            }
+            Err(PrecompileError::Fatal(e)) => return Err(e),
+            Err(e) => {
+                result.result = if e.is_oog() {
+                    InstructionResult::PrecompileOOG
+                } else {
+                    InstructionResult::PrecompileError
+                };
+            }
         }
+        Ok(Some(result))
     }
 
     #[inline]
     fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
-        // Combine both standard and stateful precompile addresses
+        // Combine the standard, scheduled and stateful precompile addresses
         let standard_addresses = self.inner.warm_addresses().into_iter();
+        let scheduled_addresses = self.scheduled_precompiles.addresses().cloned();
         let stateful_addresses = self.stateful_precompiles.addresses().cloned();
 
-        Box::new(standard_addresses.chain(stateful_addresses))
+        Box::new(
+            standard_addresses
+                .chain(scheduled_addresses)
+                .chain(stateful_addresses),
+        )
     }
 
     #[inline]
     fn contains(&self, address: &Address) -> bool {
-        self.inner.contains(address) || self.stateful_precompiles.contains(address)
+        self.inner.contains(address)
+            || self.scheduled_precompiles.contains(address)
+            || self.stateful_precompiles.contains(address)
     }
 }
 
@@ -199,19 +298,230 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use revm::{database::EmptyDB, primitives::hex};
+    use revm::{
+        database::EmptyDB,
+        precompile::u64_to_address,
+        primitives::{hex, U256},
+    };
 
-    use crate::{DefaultSeismic, SeismicContext};
+    use crate::{DefaultSeismic, SeismicChain, SeismicContext, SeismicGasSchedule};
 
     #[test]
     fn test_cancun_precompiles_in_mercury() {
+        let (regular, scheduled, _) = mercury::<SeismicContext<EmptyDB>>();
+        assert_eq!(regular.difference(Precompiles::prague()).len(), 1);
+        assert_eq!(scheduled.len(), 5);
+        assert!(scheduled
+            .addresses()
+            .all(|address| !regular.contains(address)));
+    }
+
+    #[test]
+    fn test_venus_precompiles() {
+        let mercury = mercury::<SeismicContext<EmptyDB>>().1;
+        let (_, venus, venus_stateful) = venus::<SeismicContext<EmptyDB>>();
+        let added: Vec<_> = venus.difference(mercury).copied().collect();
+        assert_eq!(added.len(), 7);
+        assert!(added.contains(&u64_to_address(hpke::HPKE_OPEN_ADDRESS)));
+        assert!(venus_stateful.contains(&u64_to_address(hpke::HPKE_SEAL_ADDRESS)));
+        assert!(venus_stateful.contains(&u64_to_address(secp256k1_keys::SECP256K1_KEYS_ADDRESS)));
+        assert!(venus_stateful.contains(&u64_to_address(contract_key::CONTRACT_KEY_ADDRESS)));
+        assert!(added.contains(&u64_to_address(
+            aes::aes_gcm_enc_aad::AES_GCM_ENC_AAD_ADDRESS
+        )));
+        assert!(added.contains(&u64_to_address(
+            aes::aes_gcm_dec_aad::AES_GCM_DEC_AAD_ADDRESS
+        )));
+        assert!(!mercury.contains(&u64_to_address(
+            aes::aes_gcm_enc_aad::AES_GCM_ENC_AAD_ADDRESS
+        )));
+    }
+
+    /// Runs a precompile through the provider of `spec`, as a call from `caller`.
+    fn run_precompile(
+        spec: SeismicSpecId,
+        context: &mut SeismicContext<EmptyDB>,
+        address: u64,
+        input: &[u8],
+        caller: Address,
+    ) -> Option<InterpreterResult> {
+        let inputs = InputsImpl {
+            input: CallInput::Bytes(Bytes::copy_from_slice(input)),
+            caller_address: caller,
+            ..Default::default()
+        };
+        SeismicPrecompiles::<SeismicContext<EmptyDB>>::new_with_spec(spec)
+            .run(context, &u64_to_address(address), &inputs, false, 1_000_000)
+            .unwrap()
+    }
+
+    /// Runs a precompile of the `VENUS` provider, checking it is absent from `MERCURY`.
+    fn run_venus(context: &mut SeismicContext<EmptyDB>, address: u64, input: &[u8]) -> Bytes {
+        let caller = Address::with_last_byte(0xc0);
+        assert!(
+            run_precompile(SeismicSpecId::MERCURY, context, address, input, caller).is_none(),
+            "{address:#x} is not a MERCURY precompile"
+        );
+        let result = run_precompile(SeismicSpecId::VENUS, context, address, input, caller)
+            .expect("VENUS precompile");
+        assert_eq!(result.result, InstructionResult::Return, "{address:#x}");
+        result.output
+    }
+
+    /// Compressed secp256k1 public key of `secret_key`.
+    fn secp256k1_public_key(secret_key: &[u8; 32]) -> [u8; 33] {
+        secp256k1::SecretKey::from_slice(secret_key)
+            .unwrap()
+            .public_key(&secp256k1::Secp256k1::new())
+            .serialize()
+    }
+
+    #[test]
+    fn test_venus_provider_runs_hpke() {
+        let mut context = SeismicContext::<EmptyDB>::seismic();
+        let secret_key = [0x42; 32];
+        let framed = |data: &[u8]| [&(data.len() as u32).to_be_bytes()[..], data].concat();
+        let (info, aad, plaintext) = (b"info", b"aad", b"sealed payload");
+
+        let sealed = run_venus(
+            &mut context,
+            hpke::HPKE_SEAL_ADDRESS,
+            &[
+                &secp256k1_public_key(&secret_key)[..],
+                &framed(info),
+                &framed(aad),
+                plaintext,
+            ]
+            .concat(),
+        );
+        let (enc, ciphertext) = sealed.split_at(hpke::ENC_LENGTH);
+        let opened = run_venus(
+            &mut context,
+            hpke::HPKE_OPEN_ADDRESS,
+            &[
+                &secret_key[..],
+                enc,
+                &framed(info),
+                &framed(aad),
+                ciphertext,
+            ]
+            .concat(),
+        );
+        assert_eq!(opened.as_ref(), plaintext);
+    }
+
+    #[test]
+    fn test_venus_provider_runs_signatures() {
+        let mut context = SeismicContext::<EmptyDB>::seismic();
+        let valid = Bytes::from(U256::from(1).to_be_bytes::<32>());
+        let message = [0x33; 32];
+
+        // Schnorr, verified against the x-only public key.
+        let secret_key = [0x42; 32];
+        let signature = run_venus(
+            &mut context,
+            schnorr::SCHNORR_SIGN_ADDRESS,
+            &[&secret_key[..], &message, &[0u8; 32]].concat(),
+        );
+        let verified = run_venus(
+            &mut context,
+            schnorr::SCHNORR_VERIFY_ADDRESS,
+            &[
+                &secp256k1_public_key(&secret_key)[1..],
+                &message,
+                &signature,
+            ]
+            .concat(),
+        );
+        assert_eq!(verified, valid);
+
+        // Ed25519, with the keys of test 1 of RFC 8032.
+        let secret_key = hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        let public_key = hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        let signature = run_venus(
+            &mut context,
+            ed25519::ED25519_SIGN_ADDRESS,
+            &[&secret_key[..], &message].concat(),
+        );
+        let verified = run_venus(
+            &mut context,
+            ed25519::ED25519_VERIFY_ADDRESS,
+            &[&public_key[..], &signature, &message].concat(),
+        );
+        assert_eq!(verified, valid);
+    }
+
+    #[test]
+    fn test_venus_provider_runs_secp256k1_keys() {
+        let mut context = SeismicContext::<EmptyDB>::seismic();
+        let secret_key = [0x42; 32];
+        let keys = run_venus(
+            &mut context,
+            secp256k1_keys::SECP256K1_KEYS_ADDRESS,
+            &[&[secp256k1_keys::OP_DERIVE][..], &secret_key].concat(),
+        );
+        assert_eq!(keys[..33], secp256k1_public_key(&secret_key));
+
+        let generated = run_venus(
+            &mut context,
+            secp256k1_keys::SECP256K1_KEYS_ADDRESS,
+            &[secp256k1_keys::OP_GENERATE],
+        );
+        let generated_key: [u8; 32] = generated[..32].try_into().unwrap();
+        assert_eq!(generated[32..65], secp256k1_public_key(&generated_key));
+    }
+
+    #[test]
+    fn test_venus_provider_runs_contract_key() {
+        use crate::chain::key_source::MockKeySource;
+        use std::sync::Arc;
+
+        let mut context = SeismicContext::<EmptyDB>::seismic().with_chain(
+            SeismicChain::default().with_key_source(Arc::new(MockKeySource::new([7u8; 32]))),
+        );
+        let key = run_venus(&mut context, contract_key::CONTRACT_KEY_ADDRESS, b"vault");
+        assert_eq!(key.len(), 32);
         assert_eq!(
-            mercury::<SeismicContext<EmptyDB>>()
-                .0
-                .difference(Precompiles::prague())
-                .len(),
-            6
-        )
+            key,
+            run_venus(&mut context, contract_key::CONTRACT_KEY_ADDRESS, b"vault"),
+            "Keys are stable"
+        );
+    }
+
+    #[test]
+    fn test_scheduled_precompiles_use_chain_gas_schedule() {
+        let mut precompiles =
+            SeismicPrecompiles::<SeismicContext<EmptyDB>>::new_with_spec(SeismicSpecId::MERCURY);
+        let address = u64_to_address(hkdf_derive_sym_key::HKDF_ADDRESS);
+        let input = InputsImpl {
+            input: CallInput::Bytes(Bytes::from_static(&[1u8; 32])),
+            ..Default::default()
+        };
+        let mut gas_used = |mut context: SeismicContext<EmptyDB>| {
+            precompiles
+                .run(&mut context, &address, &input, false, 100_000)
+                .unwrap()
+                .expect("HKDF should be a precompile")
+                .gas
+                .spent()
+        };
+
+        let default = SeismicGasSchedule::MERCURY;
+        assert_eq!(
+            gas_used(SeismicContext::seismic()),
+            default.hmac_sha256_cost(32) + default.hkdf_expand_cost()
+        );
+
+        let retuned = SeismicGasSchedule {
+            sha256_base: 600,
+            ..default
+        };
+        let context = SeismicContext::seismic()
+            .with_chain(SeismicChain::default().with_gas_schedule(retuned));
+        assert_eq!(
+            gas_used(context),
+            retuned.hmac_sha256_cost(32) + retuned.hkdf_expand_cost()
+        );
     }
 
     #[test]
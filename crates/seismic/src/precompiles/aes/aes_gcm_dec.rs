@@ -1,9 +1,11 @@
-use revm::precompile::{
-    u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
-};
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
 
 use super::common::{
-    calculate_cost, parse_aes_key, validate_gas_limit, validate_input_length, validate_nonce_length,
+    parse_aes_key, validate_gas_limit, validate_input_length, validate_nonce_length,
+};
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
 };
 
 use seismic_enclave::aes_decrypt;
@@ -15,12 +17,12 @@ Constants & Setup
 pub const AES_GCM_DEC_ADDRESS: u64 = 103;
 
 /// Returns the aes-gcm-decryption precompile with its address.
-pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
     [AES_GCM_DEC].into_iter()
 }
 
-pub const AES_GCM_DEC: PrecompileWithAddress =
-    PrecompileWithAddress(u64_to_address(AES_GCM_DEC_ADDRESS), precompile_decrypt);
+pub const AES_GCM_DEC: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(AES_GCM_DEC_ADDRESS), precompile_decrypt);
 
 /// Minimal input size for AES-GCM (32-byte key + 12-byte nonce + 16-byte tag).
 pub const MIN_INPUT_LENGTH: usize = 60;
@@ -42,7 +44,11 @@ Precompile Logic
 ///
 /// **Gas Model**:
 /// Refer to the encryption file for further discussion.
-pub fn precompile_decrypt(input: &[u8], gas_limit: u64) -> PrecompileResult {
+pub fn precompile_decrypt(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
     validate_input_length(input.len(), MIN_INPUT_LENGTH)?;
 
     let aes_key = parse_aes_key(&input[0..32])?;
@@ -51,7 +57,7 @@ pub fn precompile_decrypt(input: &[u8], gas_limit: u64) -> PrecompileResult {
 
     let ciphertext = &input[44..];
 
-    let cost = calculate_cost(ciphertext.len());
+    let cost = schedule.aes_gcm_cost(ciphertext.len());
     validate_gas_limit(cost, gas_limit)?;
 
     let plaintext = aes_decrypt(&aes_key.into(), ciphertext, nonce)
@@ -77,7 +83,8 @@ mod tests {
         input.extend_from_slice(&output_enc);
 
         let gas_limit = 2000;
-        let result = precompile_decrypt(&Bytes::from(input), gas_limit);
+        let result =
+            precompile_decrypt(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY);
 
         assert!(result.is_ok(), "Should succeed for one-block ciphertext");
         let output = result.unwrap();
@@ -92,7 +99,11 @@ mod tests {
         let input = vec![0u8; 44 + 96];
         let small_gas_limit = 500; // well below 1180 needed
 
-        let result = precompile_decrypt(&Bytes::from(input), small_gas_limit);
+        let result = precompile_decrypt(
+            &Bytes::from(input),
+            small_gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err());
         match result.err() {
             Some(PrecompileError::OutOfGas) => {}
@@ -106,7 +117,8 @@ mod tests {
         let input = vec![0u8; 20];
         let gas_limit = 2000;
 
-        let result = precompile_decrypt(&Bytes::from(input), gas_limit);
+        let result =
+            precompile_decrypt(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_err());
 
         match result.err() {
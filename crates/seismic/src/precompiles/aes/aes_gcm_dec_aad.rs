@@ -0,0 +1,189 @@
+use aes_gcm::{aead::AeadInPlace, Nonce, Tag};
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
+
+use super::common::{
+    aes_gcm_cipher, parse_aad, parse_aes_key, validate_gas_limit, validate_input_length,
+    AAD_LENGTH_PREFIX,
+};
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::{scheduled_precompile::ScheduledPrecompileWithAddress, secret::Secret},
+};
+
+/* --------------------------------------------------------------------------
+Constants & Setup
+-------------------------------------------------------------------------- */
+
+/// Address of AES-GCM decryption with associated data precompile.
+pub const AES_GCM_DEC_AAD_ADDRESS: u64 = 107;
+
+/// Returns the aes-gcm-decryption with associated data precompile with its address.
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
+    [AES_GCM_DEC_AAD].into_iter()
+}
+
+pub const AES_GCM_DEC_AAD: ScheduledPrecompileWithAddress = ScheduledPrecompileWithAddress(
+    u64_to_address(AES_GCM_DEC_AAD_ADDRESS),
+    precompile_decrypt_aad,
+);
+
+/// Length of the detached authentication tag.
+pub const TAG_LENGTH: usize = 16;
+
+/// Minimal input size: 32-byte key + 12-byte nonce + 4-byte AAD length + 16-byte tag.
+pub const MIN_INPUT_LENGTH: usize = 32 + 12 + AAD_LENGTH_PREFIX + TAG_LENGTH;
+
+/* --------------------------------------------------------------------------
+Precompile Logic
+-------------------------------------------------------------------------- */
+
+/// # AES-256-GCM Decryption with Associated Data Precompile
+///
+/// **Input Layout** (mirrors the output of the encryption):
+/// ```text
+/// [0..32]         :  AES-256 key  (32 bytes)
+/// [32..44]        :  12-byte nonce
+/// [44..48]        :  big-endian uint32 `aad_len`
+/// [48..48+aad_len]:  associated data
+/// [48+aad_len..]  :  16-byte authentication tag, then the ciphertext
+/// ```
+/// The output is the plaintext. If the tag doesn't match the ciphertext and the associated
+/// data, decryption fails with an error.
+///
+/// **Gas Model**:
+/// Refer to the encryption file for further discussion.
+pub fn precompile_decrypt_aad(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    validate_input_length(input.len(), MIN_INPUT_LENGTH)?;
+    let aes_key = parse_aes_key(&input[0..32])?;
+    let nonce = Nonce::from_slice(&input[32..44]);
+    let (aad, rest) = parse_aad(&input[44..])?;
+    if rest.len() < TAG_LENGTH {
+        return Err(PrecompileError::Other(format!(
+            "invalid input length: expected a {TAG_LENGTH}-byte tag after the AAD, got {} bytes",
+            rest.len()
+        )));
+    }
+    let (tag, ciphertext) = rest.split_at(TAG_LENGTH);
+
+    let cost = schedule.aes_gcm_aad_cost(ciphertext.len(), aad.len());
+    validate_gas_limit(cost, gas_limit)?;
+
+    // The buffer holds the decrypted data even when the tag does not match.
+    let mut plaintext = Secret::new(ciphertext.to_vec());
+    aes_gcm_cipher(&aes_key)
+        .decrypt_in_place_detached(nonce, aad, &mut *plaintext, Tag::from_slice(tag))
+        .map_err(|e| PrecompileError::Other(format!("Decryption failed: {e}")))?;
+
+    Ok(PrecompileOutput::new(
+        cost,
+        core::mem::take(&mut *plaintext).into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::aes::precompile_encrypt_aad;
+    use revm::precompile::PrecompileError;
+
+    fn input(aad: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut input = vec![0u8; 44];
+        input.extend_from_slice(&(aad.len() as u32).to_be_bytes());
+        input.extend_from_slice(aad);
+        input.extend_from_slice(data);
+        input
+    }
+
+    /// 1) Decryption inverts encryption, for the same AAD.
+    #[test]
+    fn test_decrypt_aad_roundtrip() {
+        let aad = b"record 42";
+        let plaintext = b"some secret that spans more than one block";
+        let sealed =
+            precompile_encrypt_aad(&input(aad, plaintext), 5000, &SeismicGasSchedule::MERCURY)
+                .unwrap();
+
+        let opened = precompile_decrypt_aad(
+            &input(aad, &sealed.bytes),
+            5000,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap();
+        assert_eq!(opened.bytes.as_ref(), plaintext);
+        assert_eq!(opened.gas_used, sealed.gas_used);
+    }
+
+    /// 2) A different AAD or a tampered tag fails authentication.
+    #[test]
+    fn test_decrypt_aad_rejects_mismatch() {
+        let sealed = precompile_encrypt_aad(
+            &input(b"record 42", b"secret"),
+            5000,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap();
+
+        let result = precompile_decrypt_aad(
+            &input(b"record 43", &sealed.bytes),
+            5000,
+            &SeismicGasSchedule::MERCURY,
+        );
+        assert!(
+            matches!(result, Err(PrecompileError::Other(msg)) if msg.contains("Decryption failed"))
+        );
+
+        let mut tampered = sealed.bytes.to_vec();
+        tampered[0] ^= 1;
+        let result = precompile_decrypt_aad(
+            &input(b"record 42", &tampered),
+            5000,
+            &SeismicGasSchedule::MERCURY,
+        );
+        assert!(
+            matches!(result, Err(PrecompileError::Other(msg)) if msg.contains("Decryption failed"))
+        );
+    }
+
+    /// 3) The tag must follow the AAD in full.
+    #[test]
+    fn test_decrypt_aad_missing_tag() {
+        // Long enough overall, but the AAD leaves only 15 bytes for the tag.
+        let result = precompile_decrypt_aad(
+            &input(&[0u8; 8], &[0u8; 15]),
+            5000,
+            &SeismicGasSchedule::MERCURY,
+        );
+        match result.err() {
+            Some(PrecompileError::Other(msg)) => {
+                assert!(msg.contains("invalid input length"), "{msg}")
+            }
+            other => panic!("Expected invalid length error, got {:?}", other),
+        }
+    }
+
+    /// 4) Inputs shorter than the fixed fields are rejected.
+    #[test]
+    fn test_decrypt_aad_invalid_input_length() {
+        let result = precompile_decrypt_aad(
+            &[0u8; MIN_INPUT_LENGTH - 1],
+            5000,
+            &SeismicGasSchedule::MERCURY,
+        );
+        assert!(matches!(result, Err(PrecompileError::Other(_))));
+    }
+
+    /// 5) Out of gas when the ciphertext and AAD push the cost over the limit.
+    #[test]
+    fn test_decrypt_aad_out_of_gas() {
+        let result = precompile_decrypt_aad(
+            &input(&[0u8; 160], &[0u8; 16 + 160]),
+            1500,
+            &SeismicGasSchedule::MERCURY,
+        );
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+    }
+}
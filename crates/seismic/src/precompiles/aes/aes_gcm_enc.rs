@@ -1,9 +1,11 @@
-use revm::precompile::{
-    u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
-};
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
 
 use super::common::{
-    calculate_cost, parse_aes_key, validate_gas_limit, validate_input_length, validate_nonce_length,
+    parse_aes_key, validate_gas_limit, validate_input_length, validate_nonce_length,
+};
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
 };
 
 use seismic_enclave::aes_encrypt;
@@ -16,12 +18,12 @@ Constants & Setup
 pub const AES_GCM_ENC_ADDRESS: u64 = 102;
 
 /// Returns the aes-gcm-encryption precompile with its address.
-pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
     [AES_GCM_ENC].into_iter()
 }
 
-pub const AES_GCM_ENC: PrecompileWithAddress =
-    PrecompileWithAddress(u64_to_address(AES_GCM_ENC_ADDRESS), precompile_encrypt);
+pub const AES_GCM_ENC: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(AES_GCM_ENC_ADDRESS), precompile_encrypt);
 
 /// Minimal input size:
 /// - 32 bytes for the AES key,
@@ -54,11 +56,17 @@ Precompile Logic
 ///
 /// ## Gas Model
 /// let num_blocks = (plaintext_len + 15) / 16;
-/// let cost = AES_GCM_BASE + AES_GCM_PER_BLOCK * num_blocks;
+/// let cost = schedule.aes_gcm_base + schedule.aes_gcm_per_block * num_blocks;
 /// If `cost > gas_limit`, we revert with `OutOfGas`.
 ///
-/// We set the final `gas_used` = `cost`.
-pub fn precompile_encrypt(input: &[u8], gas_limit: u64) -> PrecompileResult {
+/// We set the final `gas_used` = `cost`. The defaults are very rough estimates: the base
+/// covers setup and finalization, intentionally overpriced to stay safe, and each 16-byte
+/// block one AES encryption and one GHASH multiply, plus cushion.
+pub fn precompile_encrypt(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
     validate_input_length(input.len(), MIN_INPUT_LENGTH)?;
     let aes_key = parse_aes_key(&input[0..32])?;
 
@@ -66,7 +74,7 @@ pub fn precompile_encrypt(input: &[u8], gas_limit: u64) -> PrecompileResult {
     let nonce: [u8; 12] = input[32..44].try_into().expect("must be 12 bytes");
 
     let plaintext = &input[44..];
-    let cost = calculate_cost(plaintext.len());
+    let cost = schedule.aes_gcm_cost(plaintext.len());
     validate_gas_limit(cost, gas_limit)?;
     let ciphertext = aes_encrypt(&aes_key.into(), plaintext, nonce)
         .map_err(|e| PrecompileError::Other(format!("Encryption failed: {e}")))?;
@@ -95,10 +103,11 @@ mod tests {
         input[44..60].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
 
         // The cost formula is:
-        //   cost = 1000 (AES_GCM_BASE) + 30 (AES_GCM_PER_BLOCK) * 1 block => 1030
+        //   cost = 1000 (aes_gcm_base) + 30 (aes_gcm_per_block) * 1 block => 1030
         let gas_limit = 2_000; // well above 1030
 
-        let result = precompile_encrypt(&Bytes::from(input), gas_limit);
+        let result =
+            precompile_encrypt(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_ok(), "Should succeed for small plaintext");
 
         let output = result.unwrap();
@@ -120,7 +129,8 @@ mod tests {
         let input = vec![0u8; 44];
         // cost = 1000 + 30 * 0 = 1000
         let gas_limit = 2_000;
-        let result = precompile_encrypt(&Bytes::from(input), gas_limit);
+        let result =
+            precompile_encrypt(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_ok(), "Empty plaintext should be valid");
 
         let output = result.unwrap();
@@ -139,7 +149,11 @@ mod tests {
         // Just fill with zeros
         let small_gas_limit = 500; // well below 1180
 
-        let result = precompile_encrypt(&Bytes::from(input), small_gas_limit);
+        let result = precompile_encrypt(
+            &Bytes::from(input),
+            small_gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err());
 
         match result.err() {
@@ -155,7 +169,8 @@ mod tests {
         let input = vec![0u8; 20];
         let gas_limit = 2_000;
 
-        let result = precompile_encrypt(&Bytes::from(input), gas_limit);
+        let result =
+            precompile_encrypt(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_err());
 
         // We expect a PCError::Other complaining about input length
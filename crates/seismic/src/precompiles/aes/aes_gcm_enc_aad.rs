@@ -0,0 +1,173 @@
+use aes_gcm::{aead::AeadInPlace, Nonce};
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
+
+use super::common::{
+    aes_gcm_cipher, parse_aad, parse_aes_key, validate_gas_limit, validate_input_length,
+    AAD_LENGTH_PREFIX,
+};
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
+};
+
+/* --------------------------------------------------------------------------
+Constants & Setup
+-------------------------------------------------------------------------- */
+
+/// Address of AES-GCM encryption with associated data precompile.
+pub const AES_GCM_ENC_AAD_ADDRESS: u64 = 106;
+
+/// Returns the aes-gcm-encryption with associated data precompile with its address.
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
+    [AES_GCM_ENC_AAD].into_iter()
+}
+
+pub const AES_GCM_ENC_AAD: ScheduledPrecompileWithAddress = ScheduledPrecompileWithAddress(
+    u64_to_address(AES_GCM_ENC_AAD_ADDRESS),
+    precompile_encrypt_aad,
+);
+
+/// Minimal input size: 32-byte key + 12-byte nonce + 4-byte AAD length.
+pub const MIN_INPUT_LENGTH: usize = 32 + 12 + AAD_LENGTH_PREFIX;
+
+/* --------------------------------------------------------------------------
+Precompile Logic
+-------------------------------------------------------------------------- */
+
+/// # AES-256-GCM Encryption with Associated Data Precompile
+///
+/// **Input Layout**:
+/// ```text
+/// [0..32]         :  AES-256 key  (32 bytes)
+/// [32..44]        :  12-byte nonce
+/// [44..48]        :  big-endian uint32 `aad_len`
+/// [48..48+aad_len]:  associated data
+/// [48+aad_len..]  :  plaintext
+/// ```
+///
+/// **Output Layout**:
+/// ```text
+/// [0..16] :  authentication tag
+/// [16..]  :  ciphertext, as long as the plaintext
+/// ```
+///
+/// The associated data is authenticated but not encrypted, so a contract can bind the
+/// ciphertext to e.g. its own address or a record id.
+///
+/// **Gas Model**:
+/// The plaintext and the AAD are both charged per 16-byte block, on top of the base cost of
+/// the encryption precompile.
+pub fn precompile_encrypt_aad(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    validate_input_length(input.len(), MIN_INPUT_LENGTH)?;
+    let aes_key = parse_aes_key(&input[0..32])?;
+    let nonce = Nonce::from_slice(&input[32..44]);
+    let (aad, plaintext) = parse_aad(&input[44..])?;
+
+    let cost = schedule.aes_gcm_aad_cost(plaintext.len(), aad.len());
+    validate_gas_limit(cost, gas_limit)?;
+
+    let mut ciphertext = plaintext.to_vec();
+    let tag = aes_gcm_cipher(&aes_key)
+        .encrypt_in_place_detached(nonce, aad, &mut ciphertext)
+        .map_err(|e| PrecompileError::Other(format!("Encryption failed: {e}")))?;
+
+    let mut output = tag.to_vec();
+    output.extend_from_slice(&ciphertext);
+    Ok(PrecompileOutput::new(cost, output.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::aes::precompile_encrypt;
+    use revm::precompile::PrecompileError;
+    use revm::primitives::hex;
+
+    fn input(aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut input = vec![0u8; 44];
+        input.extend_from_slice(&(aad.len() as u32).to_be_bytes());
+        input.extend_from_slice(aad);
+        input.extend_from_slice(plaintext);
+        input
+    }
+
+    /// 1) Empty AAD and plaintext: the tag matches the plain encryption precompile.
+    #[test]
+    fn test_encrypt_aad_empty() {
+        let result =
+            precompile_encrypt_aad(&input(&[], &[]), 2000, &SeismicGasSchedule::MERCURY).unwrap();
+        assert_eq!(result.gas_used, 1000, "Cost must be base only (no blocks)");
+        assert_eq!(
+            result.bytes.as_ref(),
+            hex!("530f8afbc74536b9a963b4f1c4cb738b"),
+            "Output should be the tag alone"
+        );
+    }
+
+    /// 2) Without AAD, the output is the plain encryption with the tag moved to the front.
+    #[test]
+    fn test_encrypt_aad_detaches_tag() {
+        let plaintext = [7u8; 40];
+        let with_aad =
+            precompile_encrypt_aad(&input(&[], &plaintext), 2000, &SeismicGasSchedule::MERCURY)
+                .unwrap();
+        let mut plain_input = vec![0u8; 44];
+        plain_input.extend_from_slice(&plaintext);
+        let plain = precompile_encrypt(&plain_input, 2000, &SeismicGasSchedule::MERCURY).unwrap();
+
+        assert_eq!(with_aad.gas_used, plain.gas_used);
+        assert_eq!(&with_aad.bytes[..16], &plain.bytes[40..]);
+        assert_eq!(&with_aad.bytes[16..], &plain.bytes[..40]);
+    }
+
+    /// 3) The AAD changes the tag but not the ciphertext, and is charged per block.
+    #[test]
+    fn test_encrypt_aad_binds_tag() {
+        let plaintext = [7u8; 16];
+        let without =
+            precompile_encrypt_aad(&input(&[], &plaintext), 2000, &SeismicGasSchedule::MERCURY)
+                .unwrap();
+        let with = precompile_encrypt_aad(
+            &input(&[1u8; 17], &plaintext),
+            2000,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap();
+
+        assert_eq!(with.gas_used, 1000 + 30 + 2 * 30);
+        assert_ne!(with.bytes[..16], without.bytes[..16]);
+        assert_eq!(with.bytes[16..], without.bytes[16..]);
+    }
+
+    /// 4) An AAD length past the end of the input is rejected.
+    #[test]
+    fn test_encrypt_aad_length_overflow() {
+        let mut input = input(&[1u8; 8], &[]);
+        input[44..48].copy_from_slice(&9u32.to_be_bytes());
+        match precompile_encrypt_aad(&input, 2000, &SeismicGasSchedule::MERCURY).err() {
+            Some(PrecompileError::Other(msg)) => {
+                assert!(msg.contains("invalid input length"), "{msg}")
+            }
+            other => panic!("Expected invalid length error, got {:?}", other),
+        }
+    }
+
+    /// 5) Inputs too short to hold the AAD length are rejected.
+    #[test]
+    fn test_encrypt_aad_invalid_input_length() {
+        let result = precompile_encrypt_aad(&[0u8; 46], 2000, &SeismicGasSchedule::MERCURY);
+        assert!(matches!(result, Err(PrecompileError::Other(_))));
+    }
+
+    /// 6) Out of gas when the AAD pushes the cost over the limit.
+    #[test]
+    fn test_encrypt_aad_out_of_gas() {
+        let result =
+            precompile_encrypt_aad(&input(&[0u8; 320], &[]), 1500, &SeismicGasSchedule::MERCURY);
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+    }
+}
@@ -1,11 +1,6 @@
+use aes_gcm::{aead::KeyInit, Aes256Gcm, Key};
 use revm::precompile::PrecompileError;
-
-/// The below gas cost are very rough estimates.
-/// Overhead cost for AES-GCM setup & finalization. We intentionally overprice to stay safe.
-const AES_GCM_BASE: u64 = 1000;
-
-/// Per 16-byte block cost. One AES encryption + one GHASH multiply per block, plus cushion.
-const AES_GCM_PER_BLOCK: u64 = 30;
+use std::string::ToString;
 
 pub(crate) fn validate_input_length(
     input_len: usize,
@@ -36,12 +31,25 @@ pub(crate) fn validate_nonce_length(slice: &[u8]) -> Result<(), PrecompileError>
     Ok(())
 }
 
-pub(crate) fn calculate_cost(ciphertext_len: usize) -> u64 {
-    calc_linear_cost(16, ciphertext_len, AES_GCM_BASE, AES_GCM_PER_BLOCK)
+/// Length of the big-endian `uint32` prefix giving the AAD length.
+pub(crate) const AAD_LENGTH_PREFIX: usize = 4;
+
+/// Splits `input` into the length-prefixed AAD and the bytes following it.
+pub(crate) fn parse_aad(input: &[u8]) -> Result<(&[u8], &[u8]), PrecompileError> {
+    let (prefix, rest) = input.split_at_checked(AAD_LENGTH_PREFIX).ok_or_else(|| {
+        PrecompileError::Other("invalid input length: missing AAD length".to_string())
+    })?;
+    let aad_len = u32::from_be_bytes(prefix.try_into().expect("must be 4 bytes")) as usize;
+    rest.split_at_checked(aad_len).ok_or_else(|| {
+        PrecompileError::Other(format!(
+            "invalid input length: AAD length {aad_len} exceeds the {} remaining bytes",
+            rest.len()
+        ))
+    })
 }
 
-fn calc_linear_cost(bus: u64, len: usize, base: u64, word: u64) -> u64 {
-    (len as u64).div_ceil(bus) * word + base
+pub(crate) fn aes_gcm_cipher(key: &[u8; 32]) -> Aes256Gcm {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
 }
 
 pub(crate) fn validate_gas_limit(cost: u64, gas_limit: u64) -> Result<(), PrecompileError> {
@@ -4,4 +4,10 @@ pub use aes_gcm_enc::precompile_encrypt;
 pub mod aes_gcm_dec;
 pub use aes_gcm_dec::precompile_decrypt;
 
-mod common;
+pub mod aes_gcm_enc_aad;
+pub use aes_gcm_enc_aad::precompile_encrypt_aad;
+
+pub mod aes_gcm_dec_aad;
+pub use aes_gcm_dec_aad::precompile_decrypt_aad;
+
+pub(crate) mod common;
@@ -0,0 +1,186 @@
+use hkdf::Hkdf;
+use revm::{
+    context::{ContextTr, Transaction},
+    precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::{Address, Bytes},
+};
+use sha2::Sha256;
+
+use super::{secret::Secret, stateful_precompile::StatefulPrecompileWithAddress};
+use crate::{api::exec::SeismicContextTr, chain::key_source::MASTER_KEY_LENGTH};
+use std::string::ToString;
+
+/* --------------------------------------------------------------------------
+Precompile Wiring
+-------------------------------------------------------------------------- */
+/// Address of contract key derivation precompile.
+pub const CONTRACT_KEY_ADDRESS: u64 = 115;
+
+pub fn contract_key_precompile_iter<CTX: SeismicContextTr>(
+) -> impl Iterator<Item = StatefulPrecompileWithAddress<CTX>> {
+    [contract_key_precompile::<CTX>()].into_iter()
+}
+
+pub fn contract_key_precompile<CTX: SeismicContextTr>() -> StatefulPrecompileWithAddress<CTX> {
+    StatefulPrecompileWithAddress(
+        u64_to_address(CONTRACT_KEY_ADDRESS),
+        derive_contract_key::<CTX>,
+    )
+}
+
+/// HKDF salt, separating contract keys from anything else derived from the master key.
+const SALT: &[u8] = b"seismic contract key";
+
+/// Length of the derived key.
+const KEY_LENGTH: usize = 32;
+
+/* --------------------------------------------------------------------------
+Precompile Logic and Gas Calculation
+-------------------------------------------------------------------------- */
+/// # Contract Key Derivation
+///
+/// Input is a label of any length, chosen by the contract. Output is a 32-byte key:
+///
+/// `HKDF-SHA256(salt = "seismic contract key", ikm = master_key, info = caller || label)`
+///
+/// The master key is read from the chain's [`KeySource`](crate::chain::key_source::KeySource),
+/// so the same contract and label always get the same key, and a contract can recreate its
+/// keys on each call instead of keeping them in private storage. Different labels give
+/// independent keys, and no contract can obtain the keys of another. A chain without a key
+/// source fails the call with a fatal error rather than derive keys from a known master key.
+///
+/// The precompile cannot be called directly by the transaction: `eth_call` lets the sender
+/// claim any address, including that of a contract, whose keys would then leak.
+///
+/// ## Gas
+/// The extract step is one HMAC-SHA256 over the master key and the expand step one over the
+/// info, each charged `hmac_sha256_cost` of the gas schedule.
+fn derive_contract_key<CTX: SeismicContextTr>(
+    evmctx: &mut CTX,
+    input: &Bytes,
+    gas_limit: u64,
+    caller: Address,
+) -> PrecompileResult {
+    let info = [caller.as_slice(), input].concat();
+    let schedule = evmctx.gas_schedule();
+    let cost = schedule.hmac_sha256_cost(MASTER_KEY_LENGTH) + schedule.hmac_sha256_cost(info.len());
+    if cost > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    if caller == evmctx.tx().caller() {
+        return Err(PrecompileError::Other(
+            "Contract keys cannot be derived by the transaction sender".to_string(),
+        ));
+    }
+
+    let master_key = evmctx
+        .chain()
+        .key_source()
+        .ok_or_else(|| PrecompileError::Fatal("No key source set on the chain".to_string()))?
+        .master_key();
+    let hkdf = Hkdf::<Sha256>::new(Some(SALT), master_key.as_slice());
+    let mut key = Secret::new([0u8; KEY_LENGTH]);
+    hkdf.expand(&info, &mut key[..])
+        .map_err(|_| PrecompileError::Other("HKDF expansion error".to_string()))?;
+
+    Ok(PrecompileOutput::new(cost, key.to_vec().into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain::key_source::MockKeySource, gas_schedule::SeismicGasSchedule,
+        transaction::abstraction::SeismicTransaction, DefaultSeismic, SeismicChain, SeismicContext,
+    };
+    use revm::{
+        database::EmptyDB,
+        primitives::{address, B256},
+        Context,
+    };
+    use seismic_enclave::get_unsecure_sample_schnorrkel_keypair;
+    use std::sync::Arc;
+
+    const SENDER: Address = address!("0x1000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("0x2000000000000000000000000000000000000002");
+
+    fn context(master_key: [u8; 32]) -> SeismicContext<EmptyDB> {
+        let mut tx = SeismicTransaction::default().with_tx_hash(B256::ZERO);
+        tx.base.caller = SENDER;
+        Context::seismic().with_tx(tx).with_chain(
+            SeismicChain::default().with_key_source(Arc::new(MockKeySource::new(master_key))),
+        )
+    }
+
+    fn derive(context: &mut SeismicContext<EmptyDB>, caller: Address, label: &[u8]) -> Bytes {
+        derive_contract_key(context, &Bytes::copy_from_slice(label), 10_000, caller)
+            .unwrap()
+            .bytes
+    }
+
+    #[test]
+    fn test_matches_hkdf() {
+        let mut context = context([7u8; 32]);
+        let output = derive_contract_key(
+            &mut context,
+            &Bytes::from_static(b"vault"),
+            10_000,
+            CONTRACT,
+        )
+        .unwrap();
+        let schedule = SeismicGasSchedule::default();
+        assert_eq!(
+            output.gas_used,
+            schedule.hmac_sha256_cost(32) + schedule.hmac_sha256_cost(20 + 5)
+        );
+
+        let mut expected = [0u8; 32];
+        Hkdf::<Sha256>::new(Some(b"seismic contract key"), &[7u8; 32])
+            .expand(&[CONTRACT.as_slice(), b"vault"].concat(), &mut expected)
+            .unwrap();
+        assert_eq!(output.bytes.as_ref(), expected);
+    }
+
+    #[test]
+    fn test_keys_are_scoped() {
+        let mut context = context([7u8; 32]);
+        let key = derive(&mut context, CONTRACT, b"vault");
+
+        assert_eq!(
+            key,
+            derive(&mut context, CONTRACT, b"vault"),
+            "Keys are stable"
+        );
+        assert_ne!(key, derive(&mut context, CONTRACT, b"other"));
+        assert_ne!(key, derive(&mut context, SENDER.create(0), b"vault"));
+        assert_ne!(
+            key,
+            derive(&mut self::context([8u8; 32]), CONTRACT, b"vault")
+        );
+    }
+
+    #[test]
+    fn test_sender_rejected() {
+        let result = derive_contract_key(&mut context([7u8; 32]), &Bytes::new(), 10_000, SENDER);
+        assert!(matches!(result, Err(PrecompileError::Other(_))));
+    }
+
+    #[test]
+    fn test_missing_key_source() {
+        for chain in [
+            SeismicChain::default(),
+            SeismicChain::new(get_unsecure_sample_schnorrkel_keypair()),
+        ] {
+            let mut context = context([7u8; 32]).with_chain(chain);
+            let result = derive_contract_key(&mut context, &Bytes::new(), 10_000, CONTRACT);
+            assert!(matches!(result, Err(PrecompileError::Fatal(_))));
+        }
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        let result = derive_contract_key(&mut context([7u8; 32]), &Bytes::new(), 100, CONTRACT);
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+    }
+}
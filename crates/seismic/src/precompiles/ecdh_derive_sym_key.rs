@@ -1,6 +1,8 @@
-use super::hkdf_derive_sym_key::EXPAND_FIXED_COST;
-use revm::precompile::{
-    u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
+
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
 };
 
 use seismic_enclave::{derive_aes_key, ecdh::SharedSecret, PublicKey, SecretKey};
@@ -9,40 +11,18 @@ use seismic_enclave::{derive_aes_key, ecdh::SharedSecret, PublicKey, SecretKey};
 pub const ECDH_ADDRESS: u64 = 101;
 
 /// Returns the ecdh precompile with its address.
-pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
     [ECDH].into_iter()
 }
 
-pub const ECDH: PrecompileWithAddress =
-    PrecompileWithAddress(u64_to_address(ECDH_ADDRESS), derive_symmetric_key);
+pub const ECDH: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(ECDH_ADDRESS), derive_symmetric_key);
 
 /// Expected input layout:
 /// - 32 bytes: secp256k1 secret key
 /// - 33 bytes: secp256k1 compressed public key
 pub const INPUT_LENGTH: usize = 65;
 
-/* --------------------------------------------------------------------------
- Cost Model
--------------------------------------------------------------------------- */
-
-/// We adopt an intentionally *high* (ceiling) gas price for ECDH + HKDF:
-///
-/// 1. **ECDH** cost is roughly one scalar multiplication on secp256k1,  
-///    but we price it near or above `ECRecover` (~3000 gas) to be safe. ECRecover
-///    has many scalar multiplications and additions compared to ECDH scalar mul.
-/// 2. **HKDF** overhead is minor (see the HKDF precompile doc for details),
-///    but we account for it anyways.
-///
-/// By setting a single constant (`DERIVE_SYM_KEY_COST`), we cover the entire
-/// flow (uncompressing the secp256k1 public key, scalar-multiplying, plus
-/// the HKDF extraction & expansion to produce the final AES key).
-///
-/// This ensures we don't underprice the operation, even though ECDH is
-/// arguably simpler than a full ECDSA recover (which includes signature checks).
-///
-const SHARED_SECRET_COST: u64 = 3000;
-const DERIVE_SYM_KEY_COST: u64 = SHARED_SECRET_COST + EXPAND_FIXED_COST;
-
 /* --------------------------------------------------------------------------
  Precompile Logic
 -------------------------------------------------------------------------- */
@@ -61,12 +41,29 @@ const DERIVE_SYM_KEY_COST: u64 = SHARED_SECRET_COST + EXPAND_FIXED_COST;
 /// Returns the 32-byte AES key if successful, or an error otherwise.
 ///
 /// ## Gas:
-/// *We apply a constant `DERIVE_SYM_KEY_COST` (3,800), ensuring we
-/// overestimate in comparison to `ECRecover` and simpler HKDF ops.*  
+/// We adopt an intentionally *high* (ceiling) gas price for ECDH + HKDF:
 ///
-/// If `gas_limit < DERIVE_SYM_KEY_COST`, we revert with `OutOfGas`.
-pub fn derive_symmetric_key(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    if DERIVE_SYM_KEY_COST > gas_limit {
+/// 1. **ECDH** cost is roughly one scalar multiplication on secp256k1,  
+///    but we price it near or above `ECRecover` (~3000 gas) to be safe. ECRecover
+///    has many scalar multiplications and additions compared to ECDH scalar mul.
+/// 2. **HKDF** overhead is minor (see the HKDF precompile doc for details),
+///    but we account for it anyways.
+///
+/// By charging a single constant (`schedule.derive_sym_key_cost()`, 3,120 by default), we
+/// cover the entire flow (uncompressing the secp256k1 public key, scalar-multiplying, plus
+/// the HKDF extraction & expansion to produce the final AES key).
+///
+/// This ensures we don't underprice the operation, even though ECDH is
+/// arguably simpler than a full ECDSA recover (which includes signature checks).
+///
+/// If `gas_limit` is below the cost, we revert with `OutOfGas`.
+pub fn derive_symmetric_key(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    let cost = schedule.derive_sym_key_cost();
+    if cost > gas_limit {
         return Err(PrecompileError::OutOfGas.into());
     }
 
@@ -94,7 +91,7 @@ pub fn derive_symmetric_key(input: &[u8], gas_limit: u64) -> PrecompileResult {
 
     let output_32: [u8; 32] = aes_key.to_vec().try_into().expect("must be 32 bytes");
 
-    Ok(PrecompileOutput::new(DERIVE_SYM_KEY_COST, output_32.into()))
+    Ok(PrecompileOutput::new(cost, output_32.into()))
 }
 
 #[cfg(test)]
@@ -119,7 +116,11 @@ mod tests {
         // Sufficient gas
         let gas_limit = 10_000;
 
-        let result = derive_symmetric_key(&Bytes::from(input_data), gas_limit);
+        let result = derive_symmetric_key(
+            &Bytes::from(input_data),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_ok(), "Expected successful derivation");
 
         let output = result.unwrap();
@@ -134,7 +135,11 @@ mod tests {
         input_data[32] = 0x02; // compressed format marker
 
         let tiny_gas_limit = 1_000; // Less than 3,800
-        let result = derive_symmetric_key(&Bytes::from(input_data), tiny_gas_limit);
+        let result = derive_symmetric_key(
+            &Bytes::from(input_data),
+            tiny_gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err(), "Should fail due to out of gas");
 
         match result.err() {
@@ -151,7 +156,11 @@ mod tests {
         let input_data = vec![0u8; 64];
         let gas_limit = 10_000;
 
-        let result = derive_symmetric_key(&Bytes::from(input_data), gas_limit);
+        let result = derive_symmetric_key(
+            &Bytes::from(input_data),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err());
 
         // We check it's not `OutOfGas`, but a parse error
@@ -174,7 +183,11 @@ mod tests {
         input_data[32] = 0x02; // pretend it's compressed
 
         let gas_limit = 10_000;
-        let result = derive_symmetric_key(&Bytes::from(input_data), gas_limit);
+        let result = derive_symmetric_key(
+            &Bytes::from(input_data),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err());
 
         match result.err() {
@@ -198,12 +211,20 @@ mod tests {
 
         let gas_limit = 10_000;
 
-        let out1 = derive_symmetric_key(&Bytes::from(input_data.clone()), gas_limit)
-            .unwrap()
-            .bytes;
-        let out2 = derive_symmetric_key(&Bytes::from(input_data), gas_limit)
-            .unwrap()
-            .bytes;
+        let out1 = derive_symmetric_key(
+            &Bytes::from(input_data.clone()),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
+        let out2 = derive_symmetric_key(
+            &Bytes::from(input_data),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
 
         assert_eq!(
             out1, out2,
@@ -229,12 +250,20 @@ mod tests {
 
         let gas_limit = 10_000;
 
-        let out1 = derive_symmetric_key(&Bytes::from(input_1), gas_limit)
-            .unwrap()
-            .bytes;
-        let out2 = derive_symmetric_key(&Bytes::from(input_2), gas_limit)
-            .unwrap()
-            .bytes;
+        let out1 = derive_symmetric_key(
+            &Bytes::from(input_1),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
+        let out2 = derive_symmetric_key(
+            &Bytes::from(input_2),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
 
         assert_eq!(
             out1, out2,
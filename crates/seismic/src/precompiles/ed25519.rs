@@ -0,0 +1,189 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use revm::{
+    precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::{Bytes, B256},
+};
+
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
+};
+use std::string::ToString;
+
+/* --------------------------------------------------------------------------
+Precompile Wiring
+-------------------------------------------------------------------------- */
+/// Address of Ed25519 sign precompile.
+pub const ED25519_SIGN_ADDRESS: u64 = 112;
+
+/// Address of Ed25519 verify precompile.
+pub const ED25519_VERIFY_ADDRESS: u64 = 113;
+
+/// Returns the Ed25519 precompiles with their addresses.
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
+    [ED25519_SIGN, ED25519_VERIFY].into_iter()
+}
+
+pub const ED25519_SIGN: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(ED25519_SIGN_ADDRESS), ed25519_sign);
+
+pub const ED25519_VERIFY: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(ED25519_VERIFY_ADDRESS), ed25519_verify);
+
+/* --------------------------------------------------------------------------
+Precompile Logic and Gas Calculation
+-------------------------------------------------------------------------- */
+/// # Ed25519 Sign
+///
+/// Input is `secret_key (32) || message`, with the RFC 8032 32-byte secret key (seed). Output
+/// is the 64-byte signature.
+///
+/// ## Gas
+/// Signing takes one fixed-base scalar multiplication for the nonce point and one for the
+/// public key, priced like ECDSA signing at `signature_base`. On top, the message is hashed twice
+/// with SHA-512, charged `sha512_per_word` per 32-byte word, as for the sha256 precompile.
+pub fn ed25519_sign(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    if input.len() < 32 {
+        return Err(PrecompileError::Other("Invalid input length".to_string()));
+    }
+    let (secret_key, message) = input.split_at(32);
+
+    let gas_used = schedule.sha512_cost(message.len(), schedule.signature_base, 2);
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let signing_key = SigningKey::from_bytes(secret_key.try_into().unwrap());
+    let signature = signing_key.sign(message);
+
+    Ok(PrecompileOutput::new(
+        gas_used,
+        signature.to_bytes().to_vec().into(),
+    ))
+}
+
+/// # Ed25519 Verify
+///
+/// Input is `public_key (32) || signature (64) || message`. Like `P256VERIFY`, the output is
+/// the 32-byte word `1` if the signature is valid, and empty otherwise. Verification is
+/// strict: small order public keys and non-canonical encodings are rejected.
+///
+/// ## Gas
+/// Verification is one double scalar multiplication and a point decompression, the same order
+/// of work as ecrecover, so it is charged `signature_base`, plus one SHA-512 pass over the message.
+pub fn ed25519_verify(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    if input.len() < 96 {
+        return Err(PrecompileError::Other("Invalid input length".to_string()));
+    }
+    let (public_key, rest) = input.split_at(32);
+    let (signature, message) = rest.split_at(64);
+
+    let gas_used = schedule.sha512_cost(message.len(), schedule.signature_base, 1);
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let signature = Signature::from_bytes(signature.try_into().unwrap());
+    let valid = VerifyingKey::from_bytes(public_key.try_into().unwrap())
+        .is_ok_and(|key| key.verify_strict(message, &signature).is_ok());
+
+    let output = if valid {
+        B256::with_last_byte(1).into()
+    } else {
+        Bytes::new()
+    };
+    Ok(PrecompileOutput::new(gas_used, output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::scheduled_precompile::ScheduledPrecompileFn;
+    use revm::primitives::hex;
+
+    const BASE_GAS: u64 = SeismicGasSchedule::MERCURY.signature_base;
+    const SHA512_PER_WORD: u64 = SeismicGasSchedule::MERCURY.sha512_per_word;
+
+    /// Tests 1 and 2 of RFC 8032, section 7.1: secret key, public key, message, signature.
+    const VECTORS: [(&[u8; 32], &[u8; 32], &[u8], &[u8; 64]); 2] = [
+        (
+            &hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"),
+            &hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"),
+            &[],
+            &hex!("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"),
+        ),
+        (
+            &hex!("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb"),
+            &hex!("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"),
+            &hex!("72"),
+            &hex!("92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"),
+        ),
+    ];
+
+    #[test]
+    fn test_sign_rfc8032_vectors() {
+        for (secret_key, _, message, signature) in VECTORS {
+            let input = [&secret_key[..], message].concat();
+            let output = ed25519_sign(&input, 10_000, &SeismicGasSchedule::MERCURY).unwrap();
+            assert_eq!(output.bytes.as_ref(), signature);
+        }
+    }
+
+    #[test]
+    fn test_verify_rfc8032_vectors() {
+        for (_, public_key, message, signature) in VECTORS {
+            let input = [&public_key[..], signature, message].concat();
+            let output = ed25519_verify(&input, 10_000, &SeismicGasSchedule::MERCURY).unwrap();
+            assert_eq!(output.bytes, Bytes::from(B256::with_last_byte(1)));
+
+            let mut tampered = input.clone();
+            tampered[40] ^= 1;
+            let output = ed25519_verify(&tampered, 10_000, &SeismicGasSchedule::MERCURY).unwrap();
+            assert!(
+                output.bytes.is_empty(),
+                "Tampered signature must not verify"
+            );
+        }
+    }
+
+    #[test]
+    fn test_gas_grows_with_message() {
+        let (secret_key, ..) = VECTORS[0];
+        let input = [&secret_key[..], &[0u8; 64]].concat();
+        let output = ed25519_sign(&input, 10_000, &SeismicGasSchedule::MERCURY).unwrap();
+        assert_eq!(output.gas_used, BASE_GAS + 2 * 2 * SHA512_PER_WORD);
+
+        let input = [&[0u8; 96][..], &[0u8; 33]].concat();
+        let output = ed25519_verify(&input, 10_000, &SeismicGasSchedule::MERCURY).unwrap();
+        assert_eq!(output.gas_used, BASE_GAS + 2 * SHA512_PER_WORD);
+    }
+
+    #[test]
+    fn test_invalid_input_length() {
+        for (precompile, len) in [
+            (ed25519_sign as ScheduledPrecompileFn, 31),
+            (ed25519_verify, 95),
+        ] {
+            match precompile(&vec![0u8; len], 10_000, &SeismicGasSchedule::MERCURY).err() {
+                Some(PrecompileError::Other(msg)) => assert_eq!(msg, "Invalid input length"),
+                other => panic!("Expected invalid input length, got: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        let result = ed25519_sign(&[0u8; 32], BASE_GAS - 1, &SeismicGasSchedule::MERCURY);
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+        let result = ed25519_verify(&[0u8; 96], BASE_GAS - 1, &SeismicGasSchedule::MERCURY);
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+    }
+}
@@ -1,11 +1,13 @@
-use revm::precompile::{
-    calc_linear_cost_u32, u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult,
-    PrecompileWithAddress,
-};
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
 
 use hkdf::Hkdf;
 use sha2::Sha256;
 
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
+};
+
 /* --------------------------------------------------------------------------
 Precompile Wiring
 -------------------------------------------------------------------------- */
@@ -13,38 +15,12 @@ Precompile Wiring
 pub const HKDF_ADDRESS: u64 = 104;
 
 /// Returns the ecdh precompile with its address.
-pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
     [HKDF].into_iter()
 }
 
-pub const HKDF: PrecompileWithAddress =
-    PrecompileWithAddress(u64_to_address(HKDF_ADDRESS), hkdf_derive_symmetric_key);
-
-/* --------------------------------------------------------------------------
- Cost Constants
--------------------------------------------------------------------------- */
-
-/// Single SHA-256 precompile cost: 60 + 12 * (#words) as per the sha256 precompile.
-/// - 60 is a base cost
-/// - 12 is per 32-byte word
-const SHA256_BASE: u64 = 60;
-const SHA256_PER_WORD: u64 = 12;
-
-/// HMAC-SHA256 = 2 passes of SHA-256 (inner + outer):
-/// So each HMAC run costs about 2×(SHA256_BASE + SHA256_PER_WORD * (#words)).
-fn calc_hmac_sha256_cost(input_len: usize) -> u64 {
-    let cost_single_sha256 = calc_linear_cost_u32(input_len, SHA256_BASE, SHA256_PER_WORD);
-    2 * cost_single_sha256
-}
-
-/// For HKDF, we do:
-///  1) EXTRACT → HMAC-SHA256( salt, input ) with variable-sized `input`. TODO: If no salt, is that
-///     only one run SHA-256?
-///  2) EXPAND  → HMAC-SHA256( PRK, info )  with a short, fixed-length `info`.
-///
-/// We'll treat `Expand` as another HMAC with small input => we approximate with a
-/// constant cost derived from HMAC-SHA256 on a ~64-byte buffer, i.e. ~2 * single SHA-256 base.
-pub const EXPAND_FIXED_COST: u64 = 2 * SHA256_BASE;
+pub const HKDF: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(HKDF_ADDRESS), hkdf_derive_symmetric_key);
 
 /// This is the label used in the `expand(...)` step.
 const APPLICATION_INFO_BYTES: &[u8] = b"seismic_hkdf_105";
@@ -77,10 +53,10 @@ const APPLICATION_INFO_BYTES: &[u8] = b"seismic_hkdf_105";
 /// - **Extract** cost ~ 2 × SHA-256 (each pass processes `n` bytes).
 /// - **Expand** cost ~ 2 × SHA-256 (but for a short, fixed-size input).
 ///
-/// We approximate the Expand step as a constant `EXPAND_FIXED_COST`.  
-/// Overall:
-/// total_cost = HMAC_SHA256_EXTRACT(n) + EXPAND_FIXED_COST
-///            = 2 × (60 + 12 * (#words)) + ~120
+/// We approximate the Expand step as a constant `schedule.hkdf_expand_cost()`.  
+/// Overall, with the default schedule:
+/// total_cost = schedule.hmac_sha256_cost(n) + schedule.hkdf_expand_cost()
+///            = 2 × (60 + 12 * (#words)) + 120
 /// where `#words = ceil(n / 32)`.
 ///
 /// # Returns
@@ -92,9 +68,13 @@ const APPLICATION_INFO_BYTES: &[u8] = b"seismic_hkdf_105";
 /// - Returns `OutOfGas` if the total cost exceeds the available `gas_limit`.
 /// - Returns `HKDF expansion error` if anything goes wrong in the internal HKDF call
 ///   (rare in practice).
-pub fn hkdf_derive_symmetric_key(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    let extract_cost = calc_hmac_sha256_cost(input.len());
-    let total_cost = extract_cost + EXPAND_FIXED_COST;
+pub fn hkdf_derive_symmetric_key(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    let extract_cost = schedule.hmac_sha256_cost(input.len());
+    let total_cost = extract_cost + schedule.hkdf_expand_cost();
 
     if total_cost > gas_limit {
         return Err(PrecompileError::OutOfGas.into());
@@ -112,7 +92,7 @@ pub fn hkdf_derive_symmetric_key(input: &[u8], gas_limit: u64) -> PrecompileResu
 #[cfg(test)]
 mod tests {
     use super::*;
-    use revm::precompile::PrecompileError;
+    use revm::precompile::{calc_linear_cost_u32, PrecompileError};
     use revm::primitives::Bytes;
     use sha2::Sha256;
 
@@ -129,7 +109,8 @@ mod tests {
         // cf gas computation comments to understand the below line
         let gas_theoretically_spent = 2 * calc_linear_cost_u32(input.len(), 60, 12) + 2 * 60;
 
-        let result = hkdf_derive_symmetric_key(&Bytes::from(input), gas_limit);
+        let result =
+            hkdf_derive_symmetric_key(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_ok(), "Expected success on normal input");
         let output = result.unwrap();
 
@@ -156,7 +137,7 @@ mod tests {
         let input = Bytes::new(); // empty
         let gas_limit = 50_000;
 
-        let result = hkdf_derive_symmetric_key(&input, gas_limit);
+        let result = hkdf_derive_symmetric_key(&input, gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(
             result.is_ok(),
             "Empty input should still produce a valid key"
@@ -183,7 +164,11 @@ mod tests {
         let input = vec![0u8; 10_000];
         let small_gas_limit = 1_000; // artificially small
 
-        let result = hkdf_derive_symmetric_key(&Bytes::from(input), small_gas_limit);
+        let result = hkdf_derive_symmetric_key(
+            &Bytes::from(input),
+            small_gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err(), "Should fail due to out of gas");
         assert_eq!(
             result.err(),
@@ -207,7 +192,8 @@ mod tests {
         //   total ~ 144 + 120 = 264
         let exact_gas = 264;
 
-        let result = hkdf_derive_symmetric_key(&Bytes::from(input), exact_gas);
+        let result =
+            hkdf_derive_symmetric_key(&Bytes::from(input), exact_gas, &SeismicGasSchedule::MERCURY);
         assert!(result.is_ok(), "Should succeed exactly at the threshold");
         let output = result.unwrap();
         assert_eq!(output.gas_used, exact_gas, "Gas used should match exactly");
@@ -224,8 +210,12 @@ mod tests {
         let gas_limit = 10_000;
 
         // Precompile version
-        let precompile_res = hkdf_derive_symmetric_key(&Bytes::from(input.as_ref()), gas_limit)
-            .expect("Should succeed");
+        let precompile_res = hkdf_derive_symmetric_key(
+            &Bytes::from(input.as_ref()),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .expect("Should succeed");
         let precompile_okm = precompile_res.bytes.clone();
 
         // Direct library usage (mirror the same HKDF calls).
@@ -251,12 +241,17 @@ mod tests {
         let input = vec![123u8; 128];
         let gas_limit = 50_000;
 
-        let out1 = hkdf_derive_symmetric_key(&Bytes::from(input.clone()), gas_limit)
-            .unwrap()
-            .bytes;
-        let out2 = hkdf_derive_symmetric_key(&Bytes::from(input), gas_limit)
-            .unwrap()
-            .bytes;
+        let out1 = hkdf_derive_symmetric_key(
+            &Bytes::from(input.clone()),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
+        let out2 =
+            hkdf_derive_symmetric_key(&Bytes::from(input), gas_limit, &SeismicGasSchedule::MERCURY)
+                .unwrap()
+                .bytes;
         assert_eq!(
             out1, out2,
             "HKDF must produce the same key for identical input"
@@ -0,0 +1,607 @@
+//! HPKE seal and open precompiles.
+//!
+//! Single-shot [RFC 9180](https://www.rfc-editor.org/rfc/rfc9180) base mode encryption to a
+//! secp256k1 public key, so a contract can encrypt data to a user in one call instead of
+//! chaining the ECDH, HKDF and AES-GCM precompiles and managing nonces itself.
+//!
+//! The ciphersuite is DHKEM(secp256k1, HKDF-SHA256), HKDF-SHA256 and AES-256-GCM. The
+//! secp256k1 KEM is not registered with IANA, we use the identifier `0x0016` other
+//! implementations use for it. Public keys are serialized uncompressed, as RFC 9180 does for
+//! the NIST curves, and the Diffie-Hellman shared secret is the x-coordinate of the product.
+//!
+//! The ephemeral key of the sender is drawn from the RNG container, from a leaf RNG reserved
+//! to this precompile, so callers can neither pick nor reuse it.
+use aes_gcm::{
+    aead::{Aead, AeadInPlace, Payload},
+    Nonce,
+};
+use hkdf::Hkdf;
+use revm::{
+    context::ContextTr,
+    precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::{Address, Bytes},
+};
+use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey};
+use sha2::Sha256;
+
+use super::{
+    aes::common::aes_gcm_cipher,
+    scheduled_precompile::ScheduledPrecompileWithAddress,
+    secp256k1_keys::generate_secret_key,
+    secret::{Secret, SecretKeyGuard},
+    stateful_precompile::StatefulPrecompileWithAddress,
+};
+use crate::{api::exec::SeismicContextTr, gas_schedule::SeismicGasSchedule};
+use std::{string::String, vec::Vec};
+
+/* --------------------------------------------------------------------------
+Constants & Setup
+-------------------------------------------------------------------------- */
+
+/// Address of the HPKE seal precompile.
+pub const HPKE_SEAL_ADDRESS: u64 = 108;
+
+/// Address of the HPKE open precompile.
+pub const HPKE_OPEN_ADDRESS: u64 = 109;
+
+pub fn hpke_seal_precompile_iter<CTX: SeismicContextTr>(
+) -> impl Iterator<Item = StatefulPrecompileWithAddress<CTX>> {
+    [hpke_seal_precompile::<CTX>()].into_iter()
+}
+
+pub fn hpke_seal_precompile<CTX: SeismicContextTr>() -> StatefulPrecompileWithAddress<CTX> {
+    StatefulPrecompileWithAddress(u64_to_address(HPKE_SEAL_ADDRESS), seal::<CTX>)
+}
+
+/// Returns the HPKE open precompile with its address.
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
+    [HPKE_OPEN].into_iter()
+}
+
+pub const HPKE_OPEN: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(HPKE_OPEN_ADDRESS), open);
+
+/// Label of the leaf RNG the ephemeral keys are drawn from.
+const EPHEMERAL_KEY_LABEL: &[u8] = b"seismic hpke ephemeral key";
+
+const KEM_ID: u16 = 0x0016;
+const KDF_ID: u16 = 0x0001;
+const AEAD_ID: u16 = 0x0002;
+const MODE_BASE: u8 = 0x00;
+
+/// Length of a serialized, uncompressed, public key. Also the length of `enc`.
+pub const ENC_LENGTH: usize = 65;
+/// Length of a compressed public key.
+const COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
+const SECRET_KEY_LENGTH: usize = 32;
+/// Length of the AES-GCM tag appended to the ciphertext.
+const TAG_LENGTH: usize = 16;
+const NK: usize = 32;
+const NN: usize = 12;
+
+/* --------------------------------------------------------------------------
+ Cost Model
+-------------------------------------------------------------------------- */
+
+/// Both operations perform two secp256k1 scalar multiplications: seal derives the ephemeral
+/// public key and the shared point, open the recipient public key and the shared point. Each is
+/// priced like the ECDH precompile.
+fn kem_cost(schedule: &SeismicGasSchedule) -> u64 {
+    2 * schedule.shared_secret
+}
+
+/// The KEM and the key schedule run six HKDF steps on short inputs (`eae_prk`,
+/// `shared_secret`, `psk_id_hash`, `secret`, `key`, `base_nonce`), each priced like the
+/// expand step of the HKDF precompile. `info_hash` is priced on the length of `info`.
+fn key_schedule_cost(schedule: &SeismicGasSchedule, info_len: usize) -> u64 {
+    6 * schedule.hkdf_expand_cost() + schedule.hmac_sha256_cost(info_len)
+}
+
+/* --------------------------------------------------------------------------
+Precompile Logic
+-------------------------------------------------------------------------- */
+
+/// # HPKE Seal Precompile
+///
+/// **Input Layout**:
+/// ```text
+/// [0..33]   :  recipient public key, compressed secp256k1 point
+/// [33..37]  :  big-endian uint32 `info_len`
+/// [37..]    :  `info`, then big-endian uint32 `aad_len`, `aad`, and the plaintext
+/// ```
+///
+/// **Output Layout**:
+/// ```text
+/// [0..65]   :  `enc`, the uncompressed ephemeral public key
+/// [65..]    :  ciphertext followed by the 16-byte tag
+/// ```
+///
+/// **Gas Model**:
+/// Two scalar multiplications, the key schedule, AES-GCM over the plaintext and `aad` as in
+/// the AES-GCM with AAD precompile, and 32 bytes of the RNG precompile for the ephemeral key,
+/// including its init cost on the first seal of the transaction.
+fn seal<CTX: SeismicContextTr>(
+    evmctx: &mut CTX,
+    input: &Bytes,
+    gas_limit: u64,
+    _caller: Address,
+) -> PrecompileResult {
+    let (recipient, rest) = split(input, COMPRESSED_PUBLIC_KEY_LENGTH, "recipient public key")?;
+    let recipient = PublicKey::from_slice(recipient)
+        .map_err(|e| PrecompileError::Other(format!("public key deser err: {e}")))?;
+    let (info, rest) = parse_length_prefixed(rest, "info")?;
+    let (aad, plaintext) = parse_length_prefixed(rest, "aad")?;
+
+    let schedule = evmctx.gas_schedule();
+    let cost = kem_cost(&schedule)
+        + key_schedule_cost(&schedule, info.len())
+        + schedule.aes_gcm_aad_cost(plaintext.len(), aad.len())
+        + evmctx.chain().calculate_precompile_gas_cost(
+            EPHEMERAL_KEY_LABEL,
+            SECRET_KEY_LENGTH,
+            &schedule,
+        );
+    if cost > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let ephemeral = generate_secret_key(evmctx, EPHEMERAL_KEY_LABEL)?;
+    let output = seal_with_ephemeral(&recipient, &ephemeral, info, aad, plaintext)?;
+    Ok(PrecompileOutput::new(cost, output.into()))
+}
+
+/// # HPKE Open Precompile
+///
+/// **Input Layout**:
+/// ```text
+/// [0..32]   :  recipient secret key
+/// [32..97]  :  `enc`, as returned by the seal precompile
+/// [97..101] :  big-endian uint32 `info_len`
+/// [101..]   :  `info`, then big-endian uint32 `aad_len`, `aad`, and the ciphertext with its tag
+/// ```
+///
+/// Returns the plaintext, or fails if the tag doesn't authenticate the ciphertext, `info` and
+/// `aad` for this recipient.
+///
+/// **Gas Model**:
+/// Two scalar multiplications, the key schedule and AES-GCM over the ciphertext and `aad`.
+pub fn open(input: &[u8], gas_limit: u64, schedule: &SeismicGasSchedule) -> PrecompileResult {
+    let (recipient, rest) = split(input, SECRET_KEY_LENGTH, "recipient secret key")?;
+    let (enc, rest) = split(rest, ENC_LENGTH, "enc")?;
+    let (info, rest) = parse_length_prefixed(rest, "info")?;
+    let (aad, ciphertext) = parse_length_prefixed(rest, "aad")?;
+    if ciphertext.len() < TAG_LENGTH {
+        return Err(invalid_length(format!(
+            "expected a ciphertext of at least {TAG_LENGTH} bytes, got {}",
+            ciphertext.len()
+        )));
+    }
+
+    let cost = kem_cost(schedule)
+        + key_schedule_cost(schedule, info.len())
+        + schedule.aes_gcm_aad_cost(ciphertext.len() - TAG_LENGTH, aad.len());
+    if cost > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    let recipient = SecretKey::from_slice(recipient)
+        .map(SecretKeyGuard::new)
+        .map_err(|e| PrecompileError::Other(format!("secret key deser err: {e}")))?;
+    let ephemeral = PublicKey::from_slice(enc)
+        .map_err(|e| PrecompileError::Other(format!("public key deser err: {e}")))?;
+    let secp = Secp256k1::new();
+
+    let dh = diffie_hellman(&ephemeral, &recipient)?;
+    let kem_context = [
+        enc,
+        &recipient.public_key(&secp).serialize_uncompressed()[..],
+    ]
+    .concat();
+    let shared_secret = extract_and_expand(dh.as_slice(), &kem_context);
+    let (key, base_nonce) = key_schedule(shared_secret.as_slice(), info);
+
+    // The buffer holds the decrypted data even when the tag does not match.
+    let mut plaintext = Secret::new(ciphertext.to_vec());
+    aes_gcm_cipher(&key)
+        .decrypt_in_place(
+            Nonce::from_slice(base_nonce.as_slice()),
+            aad,
+            &mut *plaintext,
+        )
+        .map_err(|e| PrecompileError::Other(format!("Decryption failed: {e}")))?;
+
+    Ok(PrecompileOutput::new(
+        cost,
+        core::mem::take(&mut *plaintext).into(),
+    ))
+}
+
+/// Encap and seal with the given ephemeral key, returns `enc || ciphertext`.
+fn seal_with_ephemeral(
+    recipient: &PublicKey,
+    ephemeral: &SecretKey,
+    info: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, PrecompileError> {
+    let secp = Secp256k1::new();
+    let enc = ephemeral.public_key(&secp).serialize_uncompressed();
+
+    let dh = diffie_hellman(recipient, ephemeral)?;
+    let kem_context = [&enc[..], &recipient.serialize_uncompressed()[..]].concat();
+    let shared_secret = extract_and_expand(dh.as_slice(), &kem_context);
+    let (key, base_nonce) = key_schedule(shared_secret.as_slice(), info);
+
+    let ciphertext = aes_gcm_cipher(&key)
+        .encrypt(
+            Nonce::from_slice(base_nonce.as_slice()),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|e| PrecompileError::Other(format!("Encryption failed: {e}")))?;
+
+    Ok([&enc[..], &ciphertext].concat())
+}
+
+/// x-coordinate of `sk * pk`.
+fn diffie_hellman(pk: &PublicKey, sk: &SecretKey) -> Result<Secret<[u8; 32]>, PrecompileError> {
+    let point = pk
+        .mul_tweak(&Secp256k1::verification_only(), &Scalar::from(*sk))
+        .map_err(|e| PrecompileError::Other(format!("diffie-hellman failed: {e}")))?;
+    Ok(Secret::new(
+        point.serialize_uncompressed()[1..33]
+            .try_into()
+            .expect("must be 32 bytes"),
+    ))
+}
+
+/// `ExtractAndExpand` of DHKEM.
+fn extract_and_expand(dh: &[u8], kem_context: &[u8]) -> Secret<[u8; 32]> {
+    let suite_id = kem_suite_id();
+    let eae_prk = Secret::new(labeled_extract(&suite_id, &[], b"eae_prk", dh));
+    Secret::new(labeled_expand(
+        &suite_id,
+        eae_prk.as_slice(),
+        b"shared_secret",
+        kem_context,
+    ))
+}
+
+/// `KeySchedule` of the base mode, returns the key and base nonce.
+fn key_schedule(shared_secret: &[u8], info: &[u8]) -> (Secret<[u8; NK]>, Secret<[u8; NN]>) {
+    let suite_id = hpke_suite_id();
+    let psk_id_hash = labeled_extract(&suite_id, &[], b"psk_id_hash", &[]);
+    let info_hash = labeled_extract(&suite_id, &[], b"info_hash", info);
+    let context = [&[MODE_BASE][..], &psk_id_hash, &info_hash].concat();
+
+    let secret = Secret::new(labeled_extract(&suite_id, shared_secret, b"secret", &[]));
+    let key = labeled_expand(&suite_id, secret.as_slice(), b"key", &context);
+    let base_nonce = labeled_expand(&suite_id, secret.as_slice(), b"base_nonce", &context);
+    (Secret::new(key), Secret::new(base_nonce))
+}
+
+fn kem_suite_id() -> Vec<u8> {
+    [&b"KEM"[..], &KEM_ID.to_be_bytes()].concat()
+}
+
+fn hpke_suite_id() -> Vec<u8> {
+    [
+        &b"HPKE"[..],
+        &KEM_ID.to_be_bytes(),
+        &KDF_ID.to_be_bytes(),
+        &AEAD_ID.to_be_bytes(),
+    ]
+    .concat()
+}
+
+fn labeled_extract(suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> [u8; 32] {
+    let labeled_ikm = [&b"HPKE-v1"[..], suite_id, label, ikm].concat();
+    Hkdf::<Sha256>::extract(Some(salt), &labeled_ikm).0.into()
+}
+
+fn labeled_expand<const L: usize>(
+    suite_id: &[u8],
+    prk: &[u8],
+    label: &[u8],
+    info: &[u8],
+) -> [u8; L] {
+    let labeled_info = [
+        &(L as u16).to_be_bytes()[..],
+        b"HPKE-v1",
+        suite_id,
+        label,
+        info,
+    ]
+    .concat();
+    let mut okm = [0u8; L];
+    Hkdf::<Sha256>::from_prk(prk)
+        .expect("prk is one hash long")
+        .expand(&labeled_info, &mut okm)
+        .expect("length is valid for HKDF-SHA256");
+    okm
+}
+
+fn invalid_length(msg: String) -> PrecompileError {
+    PrecompileError::Other(format!("invalid input length: {msg}"))
+}
+
+fn split<'a>(
+    input: &'a [u8],
+    len: usize,
+    field: &str,
+) -> Result<(&'a [u8], &'a [u8]), PrecompileError> {
+    input.split_at_checked(len).ok_or_else(|| {
+        invalid_length(format!(
+            "expected {len} bytes of {field}, got {}",
+            input.len()
+        ))
+    })
+}
+
+/// Splits `input` into a field prefixed by its big-endian `uint32` length, and the rest.
+fn parse_length_prefixed<'a>(
+    input: &'a [u8],
+    field: &str,
+) -> Result<(&'a [u8], &'a [u8]), PrecompileError> {
+    let (prefix, rest) = split(input, 4, field)?;
+    let len = u32::from_be_bytes(prefix.try_into().expect("must be 4 bytes")) as usize;
+    split(rest, len, field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        transaction::abstraction::{RngMode, SeismicTransaction},
+        DefaultSeismic, SeismicContext,
+    };
+    use revm::{
+        database::EmptyDB,
+        primitives::{hex, B256},
+        Context,
+    };
+
+    const RECIPIENT_SK: [u8; 32] =
+        hex!("7e38022030c40773cc561c1cc9c0053e48b0be2cee33c13495f096942ea176ef");
+
+    fn recipient_pk() -> PublicKey {
+        SecretKey::from_slice(&RECIPIENT_SK)
+            .unwrap()
+            .public_key(&Secp256k1::new())
+    }
+
+    fn with_length(field: &[u8]) -> Vec<u8> {
+        [&(field.len() as u32).to_be_bytes()[..], field].concat()
+    }
+
+    fn seal_input(info: &[u8], aad: &[u8], plaintext: &[u8]) -> Bytes {
+        [
+            &recipient_pk().serialize()[..],
+            &with_length(info),
+            &with_length(aad),
+            plaintext,
+        ]
+        .concat()
+        .into()
+    }
+
+    fn open_input(sealed: &[u8], info: &[u8], aad: &[u8]) -> Vec<u8> {
+        [
+            &RECIPIENT_SK[..],
+            &sealed[..ENC_LENGTH],
+            &with_length(info),
+            &with_length(aad),
+            &sealed[ENC_LENGTH..],
+        ]
+        .concat()
+    }
+
+    fn context() -> SeismicContext<EmptyDB> {
+        let tx = SeismicTransaction::default().with_tx_hash(B256::from([0u8; 32]));
+        Context::seismic().with_tx(tx)
+    }
+
+    /// 1) Open recovers what seal encrypted, with the same `info` and `aad`.
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let mut context = context();
+        let plaintext = b"for your eyes only";
+        let sealed = seal(
+            &mut context,
+            &seal_input(b"info", b"aad", plaintext),
+            100_000,
+            Address::ZERO,
+        )
+        .unwrap();
+        assert_eq!(
+            sealed.bytes.len(),
+            ENC_LENGTH + plaintext.len() + TAG_LENGTH
+        );
+
+        let opened = open(
+            &open_input(&sealed.bytes, b"info", b"aad"),
+            100_000,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap();
+        assert_eq!(opened.bytes.as_ref(), plaintext);
+    }
+
+    /// 2) A wrong `info`, `aad` or recipient key fails authentication.
+    #[test]
+    fn test_open_rejects_mismatch() {
+        let mut context = context();
+        let sealed = seal(
+            &mut context,
+            &seal_input(b"info", b"aad", b"secret"),
+            100_000,
+            Address::ZERO,
+        )
+        .unwrap();
+
+        assert!(open(
+            &open_input(&sealed.bytes, b"other", b"aad"),
+            100_000,
+            &SeismicGasSchedule::MERCURY
+        )
+        .is_err());
+        assert!(open(
+            &open_input(&sealed.bytes, b"info", b"other"),
+            100_000,
+            &SeismicGasSchedule::MERCURY
+        )
+        .is_err());
+
+        let mut input = open_input(&sealed.bytes, b"info", b"aad");
+        input[31] ^= 1;
+        assert!(open(&input, 100_000, &SeismicGasSchedule::MERCURY).is_err());
+    }
+
+    /// 3) Every seal uses a fresh ephemeral key, and only the first pays the RNG init cost.
+    #[test]
+    fn test_seal_fresh_ephemeral_key() {
+        let mut context = context();
+        let input = seal_input(&[], &[], b"same plaintext");
+        let first = seal(&mut context, &input, 100_000, Address::ZERO).unwrap();
+        let second = seal(&mut context, &input, 100_000, Address::ZERO).unwrap();
+
+        assert_ne!(first.bytes[..ENC_LENGTH], second.bytes[..ENC_LENGTH]);
+        assert_ne!(first.bytes[ENC_LENGTH..], second.bytes[ENC_LENGTH..]);
+        assert!(second.gas_used < first.gas_used);
+    }
+
+    /// 4) The ephemeral key stream is out of reach of the RNG precompile.
+    #[test]
+    fn test_ephemeral_key_not_exposed_to_rng_precompile() {
+        use crate::precompiles::rng::domain_sep_rng::LeafKey;
+
+        let mut context = context();
+        let ephemeral = context
+            .chain()
+            .precompile_rng_bytes(EPHEMERAL_KEY_LABEL, 32, RngMode::Execution, &B256::ZERO)
+            .unwrap();
+
+        let mut context_2 = context();
+        let from_rng_precompile = context_2
+            .chain()
+            .process_rng(
+                &LeafKey::new(Bytes::from_static(EPHEMERAL_KEY_LABEL)),
+                32,
+                RngMode::Execution,
+                &B256::ZERO,
+            )
+            .unwrap();
+        assert_ne!(ephemeral, from_rng_precompile);
+    }
+
+    /// 5) Sealing is deterministic given the ephemeral key, and the key schedule yields
+    ///    different keys and nonces for different `info`.
+    #[test]
+    fn test_seal_with_ephemeral_deterministic() {
+        let ephemeral = SecretKey::from_slice(&[0x11; 32]).unwrap();
+        let a = seal_with_ephemeral(&recipient_pk(), &ephemeral, b"a", &[], b"msg").unwrap();
+        let a_again = seal_with_ephemeral(&recipient_pk(), &ephemeral, b"a", &[], b"msg").unwrap();
+        let b = seal_with_ephemeral(&recipient_pk(), &ephemeral, b"b", &[], b"msg").unwrap();
+        assert_eq!(a, a_again);
+        assert_eq!(a[..ENC_LENGTH], b[..ENC_LENGTH]);
+        assert_ne!(a[ENC_LENGTH..], b[ENC_LENGTH..]);
+    }
+
+    /// 6) Truncated length prefixes and short inputs are rejected.
+    #[test]
+    fn test_invalid_input_length() {
+        let mut context = context();
+        let mut input = seal_input(b"info", &[], &[]).to_vec();
+        input[33..37].copy_from_slice(&100u32.to_be_bytes());
+        match seal(&mut context, &input.into(), 100_000, Address::ZERO).err() {
+            Some(PrecompileError::Other(msg)) => {
+                assert!(msg.contains("invalid input length"), "{msg}")
+            }
+            other => panic!("Expected invalid length error, got {:?}", other),
+        }
+
+        let result = open(&[0u8; 40], 100_000, &SeismicGasSchedule::MERCURY);
+        assert!(
+            matches!(result, Err(PrecompileError::Other(msg)) if msg.contains("invalid input length"))
+        );
+    }
+
+    /// 7) Out of gas before any work is done.
+    #[test]
+    fn test_out_of_gas() {
+        let mut context = context();
+        let result = seal(
+            &mut context,
+            &seal_input(&[], &[], &[]),
+            kem_cost(&SeismicGasSchedule::MERCURY),
+            Address::ZERO,
+        );
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+        assert!(
+            context.chain().rng_container().precompile_rngs().is_empty(),
+            "Out of gas must not draw an ephemeral key"
+        );
+
+        let result = open(
+            &open_input(&[0u8; ENC_LENGTH + TAG_LENGTH], &[], &[]),
+            kem_cost(&SeismicGasSchedule::MERCURY),
+            &SeismicGasSchedule::MERCURY,
+        );
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+    }
+
+    /// 8) Known answer for DHKEM(secp256k1, HKDF-SHA256), HKDF-SHA256, AES-256-GCM in base mode.
+    ///
+    ///    Computed with an independent RFC 9180 implementation over OpenSSL, checked against
+    ///    the X25519 base mode vector of RFC 9180, appendix A.1.1. Inputs follow that vector.
+    #[test]
+    fn test_known_answer() {
+        let ephemeral = SecretKey::from_slice(&[0x11; 32]).unwrap();
+        let info = b"Ode on a Grecian Urn";
+        let aad = b"Count-0";
+        let plaintext = b"Beauty is truth, truth beauty";
+
+        let enc = ephemeral
+            .public_key(&Secp256k1::new())
+            .serialize_uncompressed();
+        assert_eq!(
+            enc,
+            hex!("044f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa385b6b1b8ead809ca67454d9683fcf2ba03456d6fe2c4abe2b07f0fbdbb2f1c1")
+        );
+        let dh = diffie_hellman(&recipient_pk(), &ephemeral).unwrap();
+        assert_eq!(
+            *dh,
+            hex!("f220ef33f97ce1b832281dec819f136f40fc3d99a4e8ccab5c7a2485f111acd7")
+        );
+        let kem_context = [&enc[..], &recipient_pk().serialize_uncompressed()[..]].concat();
+        let shared_secret = extract_and_expand(dh.as_slice(), &kem_context);
+        assert_eq!(
+            *shared_secret,
+            hex!("389f5224817bc3cf8fd3aff4134ffeef6779eaf86dad211e8e981e81896fcfb4")
+        );
+        let (key, base_nonce) = key_schedule(shared_secret.as_slice(), info);
+        assert_eq!(
+            *key,
+            hex!("143971f42cc1e40a26b8b149173ed2c80126bd31f8b0dc196be22185ae769aea")
+        );
+        assert_eq!(*base_nonce, hex!("eec70aab19e2a8ed09163262"));
+
+        let sealed =
+            seal_with_ephemeral(&recipient_pk(), &ephemeral, info, aad, plaintext).unwrap();
+        assert_eq!(sealed[..ENC_LENGTH], enc);
+        assert_eq!(
+            sealed[ENC_LENGTH..],
+            hex!("1759432662098097591a4cb7751e6f6109c07de9fbd5442484d7337fd81012854680e7921c181bdd1e54a99bbe")
+        );
+
+        let opened = open(
+            &open_input(&sealed, info, aad),
+            100_000,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap();
+        assert_eq!(opened.bytes.as_ref(), plaintext);
+    }
+}
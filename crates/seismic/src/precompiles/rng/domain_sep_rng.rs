@@ -4,18 +4,76 @@
 //! The Merlin transcript is initialized with a hash of the block environment.
 //! The Merlin transcript is then forked for each transaction
 //! The leaf RNG is then used to generate random bytes.
+//! From `VENUS`, a transaction holds one leaf RNG per [`LeafKey`], so requests made with
+//! different personalization bytes, or from different call frames, draw from independent
+//! streams. Before, every request of the transaction shares a single leaf RNG.
 //!
 //! This module is heavily inspired Oasis Network's RNG implementation.
+use core::cell::RefCell;
 use merlin::{Transcript, TranscriptRng};
-use rand_core::{CryptoRng, OsRng, RngCore};
-use revm::primitives::B256;
+use rand_core::{CryptoRng, RngCore};
+use revm::primitives::{Address, Bytes, B256};
 pub use schnorrkel::keys::Keypair as SchnorrkelKeypair;
 use seismic_enclave::get_unsecure_sample_schnorrkel_keypair;
-use std::{cell::RefCell, rc::Rc};
+use std::rc::Rc;
 
 /// RNG domain separation context.
 const RNG_CONTEXT: &[u8] = b"seismic rng context";
 
+/// Number of bytes a call frame adds to the transcript of its leaf RNG, caller and depth.
+const FRAME_DOMAIN_LEN: usize = 20 + 8;
+
+/// Identifies a leaf RNG within a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LeafKey {
+    /// Personalization bytes of the request.
+    pub pers: Bytes,
+    /// Caller address and call depth of the requesting frame, if streams are separated by frame.
+    pub frame: Option<(Address, usize)>,
+    /// Whether the leaf RNG is shared by every request of the transaction, whatever its
+    /// personalization, as before `VENUS`. It is forked with the pers bytes of the first request.
+    pub shared: bool,
+}
+
+impl LeafKey {
+    /// A key shared by every call frame of the transaction.
+    pub fn new(pers: Bytes) -> Self {
+        Self {
+            pers,
+            frame: None,
+            shared: false,
+        }
+    }
+
+    /// The single leaf RNG of the transaction, forked with `pers` if this is the first request.
+    pub fn shared(pers: Bytes) -> Self {
+        Self {
+            shared: true,
+            ..Self::new(pers)
+        }
+    }
+
+    /// Key the leaf RNG is kept under for the rest of the transaction.
+    pub fn slot(&self) -> Self {
+        if self.shared {
+            Self::shared(Bytes::new())
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Separates the key by the call frame making the request.
+    pub fn with_frame(mut self, caller: Address, depth: usize) -> Self {
+        self.frame = Some((caller, depth));
+        self
+    }
+
+    /// Number of bytes the key appends to the transcript when forking its leaf RNG.
+    pub fn transcript_len(&self) -> usize {
+        self.pers.len() + self.frame.map_or(0, |_| FRAME_DOMAIN_LEN)
+    }
+}
+
 /// A root RNG that can be used to derive domain-separated leaf RNGs.
 pub struct RootRng {
     inner: Rc<RefCell<Inner>>,
@@ -103,12 +161,9 @@ impl RootRng {
     /// # Non-determinism
     ///
     /// Using this method will result in the RNG being non-deterministic.
-    pub fn append_local_entropy(&self) {
-        let mut bytes = [0u8; 32];
-        OsRng.fill_bytes(&mut bytes);
-
+    pub fn append_local_entropy(&self, entropy: &[u8]) {
         let mut inner = self.inner.borrow_mut();
-        inner.transcript.append_message(b"local-rng", &bytes);
+        inner.transcript.append_message(b"local-rng", entropy);
     }
 
     /// Append an observed transaction hash to RNG transcript.
@@ -125,6 +180,18 @@ impl RootRng {
 
     /// Create an independent leaf RNG using this RNG as its parent.
     pub fn fork(&self, pers: &[u8]) -> LeafRng {
+        self.fork_with_label(b"fork", pers)
+    }
+
+    /// Create a leaf RNG for a precompile's own use, such as ephemeral keys.
+    ///
+    /// It is forked under a different transcript label than [`RootRng::fork`], so no
+    /// personalization a contract passes to the RNG precompile yields the same stream.
+    pub fn fork_precompile(&self, label: &[u8]) -> LeafRng {
+        self.fork_with_label(b"precompile-fork", label)
+    }
+
+    fn fork_with_label(&self, label: &'static [u8], pers: &[u8]) -> LeafRng {
         let mut inner = self.inner.borrow_mut();
 
         // Ensure the RNG is initialized and initialize it if not.
@@ -141,7 +208,7 @@ impl RootRng {
         }
 
         // Generate the leaf RNG.
-        inner.transcript.append_message(b"fork", pers);
+        inner.transcript.append_message(label, pers);
 
         let rng_builder = inner.transcript.build_rng();
         let parent_rng = inner.rng.as_mut().expect("rng must be initialized");
@@ -153,6 +220,22 @@ impl RootRng {
         LeafRng(rng)
     }
 
+    /// Create the leaf RNG identified by `key`.
+    ///
+    /// Without a call frame this is the same as [`RootRng::fork`] with the personalization bytes.
+    pub fn fork_leaf(&self, key: &LeafKey) -> LeafRng {
+        if let Some((caller, depth)) = key.frame {
+            let mut inner = self.inner.borrow_mut();
+            inner
+                .transcript
+                .append_message(b"caller", caller.as_slice());
+            inner
+                .transcript
+                .append_message(b"depth", &(depth as u64).to_be_bytes());
+        }
+        self.fork(&key.pers)
+    }
+
     #[cfg(test)]
     pub fn state_snapshot(&self) -> ([u8; 32], bool, bool, u64) {
         let inner = self.inner.borrow_mut();
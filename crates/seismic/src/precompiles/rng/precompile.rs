@@ -1,15 +1,18 @@
 use revm::{
-    context::ContextTr,
-    precompile::{
-        calc_linear_cost_u32, u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult,
-    },
-    primitives::Bytes,
+    context::{Cfg, ContextTr, JournalTr},
+    precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::{Address, Bytes},
 };
 
 use crate::{
-    api::exec::SeismicContextTr, precompiles::stateful_precompile::StatefulPrecompileWithAddress,
+    api::exec::SeismicContextTr,
+    precompiles::{
+        rng::domain_sep_rng::LeafKey, stateful_precompile::StatefulPrecompileWithAddress,
+    },
     transaction::abstraction::SeismicTxTr,
+    SeismicSpecId,
 };
+use std::string::ToString;
 
 /* --------------------------------------------------------------------------
 Constants & Setup
@@ -31,8 +34,6 @@ pub fn rng_precompile<CTX: SeismicContextTr>() -> StatefulPrecompileWithAddress<
 }
 
 const MIN_INPUT_LENGTH: usize = 2;
-const RNG_INIT_BASE: u64 = 3500;
-const STROBE128WORD: u64 = 5;
 
 /* --------------------------------------------------------------------------
 Precompile Logic
@@ -54,6 +55,12 @@ Precompile Logic
 /// we produce a leaf RNG that implements the `RngCore` interface and query
 /// it for bytes.
 ///
+/// Before the `VENUS` spec, the transaction has a single leaf RNG, forked with the `pers` of
+/// its first request, and later requests continue its stream whatever their `pers`. From
+/// `VENUS`, each distinct `pers` gets its own leaf RNG, kept for the rest of the transaction,
+/// also keyed by the caller address and the call depth of the request, so requests with
+/// different personalization or from different call frames draw from independent streams.
+///
 /// ## Gas Cost
 ///
 /// ### Pricing Fundamental Operations
@@ -95,6 +102,13 @@ Precompile Logic
 /// * The leaf RNG is initialized, which involves keying the RNG based on 32 random bytes  
 ///   from the parent RNG.
 ///
+/// * With frame separation (`VENUS`), the 20-byte caller address and the 8-byte call depth  
+///   are hashed along with the pers bytes, and priced as 28 extra pers bytes.
+///
+/// The init cost is charged on the first request for each leaf RNG of the transaction,
+/// later requests for the same leaf RNG only pay the fill cost. Before `VENUS`, that is only
+/// the first request of the transaction.
+///
 /// **Filling bytes** once the RNG is initialized:
 ///
 /// * Filling bytes occurs by squeezing the keccak sponge. As described above,  
@@ -109,19 +123,35 @@ Precompile Logic
 /// We add a 50% buffer to our gas calculations (which may be lowered in the future).
 ///
 /// ```text
-/// RNG_INIT_BASE = round(100 + 395 + 3000) = 3500
-/// fill_cost     = ceil(fill_len / 32) * 5
+/// rng_init_base = round(100 + 395 + 3000) = 3500
+/// rng_per_word  = 5
+/// fill_cost     = ceil(fill_len / 32) * rng_per_word
 /// ```
-fn rng<CTX: SeismicContextTr>(evmctx: &mut CTX, input: &Bytes, gas_limit: u64) -> PrecompileResult {
+///
+/// These are the defaults of the [`SeismicGasSchedule`](crate::SeismicGasSchedule).
+fn rng<CTX: SeismicContextTr>(
+    evmctx: &mut CTX,
+    input: &Bytes,
+    gas_limit: u64,
+    caller: Address,
+) -> PrecompileResult {
     // Validate input and extract parameters.
     validate_input_length(input.len(), MIN_INPUT_LENGTH)?;
     let (requested_output_len, pers) = parse_input(input)?;
     let requested_output_len = requested_output_len as usize;
 
+    // Select the leaf RNG of the request.
+    let leaf = if evmctx.cfg().spec().is_enabled_in(SeismicSpecId::VENUS) {
+        LeafKey::new(pers).with_frame(caller, evmctx.journal_ref().depth())
+    } else {
+        LeafKey::shared(pers)
+    };
+
     // Compute the gas cost.
+    let schedule = evmctx.gas_schedule();
     let gas_used = evmctx
         .chain()
-        .calculate_gas_cost(&pers, requested_output_len);
+        .calculate_gas_cost(&leaf, requested_output_len, &schedule);
     if gas_used > gas_limit {
         return Err(PrecompileError::OutOfGas); // Changed REVM_ERROR to PrecompileError
     }
@@ -133,20 +163,11 @@ fn rng<CTX: SeismicContextTr>(evmctx: &mut CTX, input: &Bytes, gas_limit: u64) -
     // Let the container update its state and produce the random bytes.
     let output = evmctx
         .chain()
-        .process_rng(&pers, requested_output_len, kernel_mode, &tx_hash)
-        .map_err(|e| PrecompileError::Other(e.to_string()))?; // Changed PCError to PrecompileError
+        .process_rng(&leaf, requested_output_len, kernel_mode, &tx_hash)?;
 
     Ok(PrecompileOutput::new(gas_used, output))
 }
 
-pub(crate) fn calculate_init_cost(pers_len: usize) -> u64 {
-    calc_linear_cost_u32(pers_len, RNG_INIT_BASE, STROBE128WORD)
-}
-
-pub(crate) fn calculate_fill_cost(fill_len: usize) -> u64 {
-    calc_linear_cost_u32(fill_len, 0, STROBE128WORD)
-}
-
 pub(crate) fn parse_input(input: &Bytes) -> Result<(u32, Bytes), PrecompileError> {
     if input.len() < 4 {
         return Err(PrecompileError::Other(
@@ -180,10 +201,12 @@ pub(crate) fn validate_input_length(
 #[cfg(test)]
 mod tests {
     use crate::transaction::abstraction::SeismicTransaction;
-    use crate::{DefaultSeismic, SeismicContext};
+    use crate::{DefaultSeismic, SeismicContext, SeismicGasSchedule};
     use std::vec;
 
     use super::*;
+    use crate::precompiles::rng::domain_sep_rng::RootRng;
+    use rand_core::RngCore;
     use revm::database::EmptyDB;
     use revm::precompile::PrecompileError;
     use revm::primitives::{Bytes, B256};
@@ -224,7 +247,7 @@ mod tests {
     fn test_rng_init_no_pers() {
         let (gas_limit, input, mut context, precompile) = setup_rng_test(32, None);
 
-        let result = precompile.1(&mut context, &input.into(), gas_limit);
+        let result = precompile.1(&mut context, &input.into(), gas_limit, Address::ZERO);
         assert!(
             result.is_ok(),
             "Should succeed without default personalization"
@@ -242,8 +265,12 @@ mod tests {
         let (gas_limit, input_with_pers, mut context_with_pers, precompile) =
             setup_rng_test(32, Some(empty_pers));
 
-        let result_with_pers =
-            precompile.1(&mut context_with_pers, &input_with_pers.into(), gas_limit);
+        let result_with_pers = precompile.1(
+            &mut context_with_pers,
+            &input_with_pers.into(),
+            gas_limit,
+            Address::ZERO,
+        );
         assert!(
             result_with_pers.is_ok(),
             "Should succeed with default personalization"
@@ -262,7 +289,12 @@ mod tests {
         // Test without personalization
         let (gas_limit, input_no_pers, mut context_no_pers, precompile) = setup_rng_test(32, None);
 
-        let result_no_pers = precompile.1(&mut context_no_pers, &input_no_pers.into(), gas_limit);
+        let result_no_pers = precompile.1(
+            &mut context_no_pers,
+            &input_no_pers.into(),
+            gas_limit,
+            Address::ZERO,
+        );
         assert!(
             result_no_pers.is_ok(),
             "Should succeed without default personalization"
@@ -284,7 +316,7 @@ mod tests {
         let personalization = vec![1, 2, 3, 4]; // use 4 pers bytes, gets rounded up to one word
         let (gas_limit, input, mut context, precompile) = setup_rng_test(32, Some(personalization));
 
-        let result = precompile.1(&mut context, &input.into(), gas_limit);
+        let result = precompile.1(&mut context, &input.into(), gas_limit, Address::ZERO);
         assert!(result.is_ok(), "Should succeed with personalization");
 
         let output = result.unwrap();
@@ -298,11 +330,16 @@ mod tests {
         let (_, input, mut context, precompile) = setup_rng_test(32, Some(empty_pers));
 
         // Call once to initialize the RNG
-        let _ = precompile.1(&mut context, &input.clone().into(), 6000);
+        let _ = precompile.1(&mut context, &input.clone().into(), 6000, Address::ZERO);
 
         // Make a second call with the leaf rng already initialized
         let reduced_gas_limit = 500;
-        let result = precompile.1(&mut context, &input.into(), reduced_gas_limit);
+        let result = precompile.1(
+            &mut context,
+            &input.into(),
+            reduced_gas_limit,
+            Address::ZERO,
+        );
         assert!(result.is_ok(), "Should succeed with initialized RNG");
 
         let output = result.unwrap();
@@ -310,13 +347,96 @@ mod tests {
         assert!(output.bytes.len() == 32, "RNG output should be 32 bytes");
     }
 
+    #[test]
+    fn test_rng_distinct_pers_share_the_stream_before_venus() {
+        let (gas_limit, input_a, mut context, precompile) = setup_rng_test(32, Some(vec![1]));
+        let input_b = Bytes::from([&32u32.to_be_bytes()[..], &[2, 2, 2]].concat());
+
+        let first = precompile.1(&mut context, &input_a, gas_limit, Address::ZERO).unwrap();
+        let second = precompile.1(&mut context, &input_b, gas_limit, Address::ZERO).unwrap();
+        assert_eq!(first.gas_used, 3510);
+        assert_eq!(second.gas_used, 5, "Only the first request initializes");
+
+        // Both requests read the leaf RNG forked with the first pers.
+        let root_rng = RootRng::test_default();
+        root_rng.append_tx(&B256::ZERO);
+        let mut leaf_rng = root_rng.fork(&[1]);
+        for output in [first.bytes, second.bytes] {
+            let mut expected = [0u8; 32];
+            leaf_rng.fill_bytes(&mut expected);
+            assert_eq!(output.as_ref(), expected);
+        }
+    }
+
+    #[test]
+    fn test_rng_distinct_pers_use_distinct_streams() {
+        let (gas_limit, input_a, context, precompile) = setup_rng_test(32, Some(vec![1]));
+        let mut context = context.modify_cfg_chained(|cfg| cfg.spec = SeismicSpecId::VENUS);
+        let input_b = Bytes::from([&32u32.to_be_bytes()[..], &[2]].concat());
+
+        let first_a = precompile.1(&mut context, &input_a, gas_limit, Address::ZERO).unwrap();
+        let first_b = precompile.1(&mut context, &input_b, gas_limit, Address::ZERO).unwrap();
+        let schedule = SeismicGasSchedule::default();
+        assert_eq!(
+            first_b.gas_used,
+            schedule.rng_init_cost(1 + 28) + schedule.rng_fill_cost(32),
+            "New pers should pay the init cost"
+        );
+
+        let second_a = precompile.1(&mut context, &input_a, gas_limit, Address::ZERO).unwrap();
+        let second_b = precompile.1(&mut context, &input_b, gas_limit, Address::ZERO).unwrap();
+        assert_eq!(
+            second_a.gas_used, 5,
+            "Known pers should only pay the fill cost"
+        );
+        assert_eq!(
+            second_b.gas_used, 5,
+            "Known pers should only pay the fill cost"
+        );
+
+        let outputs = [first_a.bytes, first_b.bytes, second_a.bytes, second_b.bytes];
+        for (i, output) in outputs.iter().enumerate() {
+            assert!(
+                !outputs[..i].contains(output),
+                "Streams of different pers should be independent"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rng_frame_separation() {
+        let caller_a = Address::repeat_byte(0xaa);
+        let caller_b = Address::repeat_byte(0xbb);
+
+        // Before VENUS, every caller shares the leaf RNG of the transaction.
+        let (gas_limit, input, mut context, precompile) = setup_rng_test(32, Some(vec![1]));
+        let first = precompile.1(&mut context, &input, gas_limit, caller_a).unwrap();
+        let second = precompile.1(&mut context, &input, gas_limit, caller_b).unwrap();
+        assert_eq!(first.gas_used, 3510);
+        assert_eq!(second.gas_used, 5);
+
+        // From VENUS, each caller gets its own leaf RNG, priced with the frame domain.
+        let (gas_limit, input, context, precompile) = setup_rng_test(32, Some(vec![1]));
+        let mut context = context.modify_cfg_chained(|cfg| cfg.spec = SeismicSpecId::VENUS);
+        let first_a = precompile.1(&mut context, &input, gas_limit, caller_a).unwrap();
+        let first_b = precompile.1(&mut context, &input, gas_limit, caller_b).unwrap();
+        let second_a = precompile.1(&mut context, &input, gas_limit, caller_a).unwrap();
+        let schedule = SeismicGasSchedule::default();
+        let expected_init = schedule.rng_init_cost(1 + 28) + schedule.rng_fill_cost(32);
+        assert_eq!(first_a.gas_used, expected_init);
+        assert_eq!(first_b.gas_used, expected_init);
+        assert_eq!(second_a.gas_used, 5);
+        assert_ne!(first_a.bytes, first_b.bytes);
+        assert_ne!(first_a.bytes, second_a.bytes);
+    }
+
     #[test]
     fn test_rng_out_of_gas_on_init() {
         let empty_pers = vec![0, 0, 0, 0]; // U32::ZERO.to_be_bytes_vec()
         let (_, input, mut context, precompile) = setup_rng_test(16, Some(empty_pers));
 
         let insufficient_gas = 2500; // less than the init cost
-        let result = precompile.1(&mut context, &input.into(), insufficient_gas);
+        let result = precompile.1(&mut context, &input.into(), insufficient_gas, Address::ZERO);
         assert!(result.is_err());
 
         match result.err() {
@@ -331,11 +451,11 @@ mod tests {
         let (_, input, mut context, precompile) = setup_rng_test(6000, Some(empty_pers));
 
         // Call once to initialize the RNG
-        let _ = precompile.1(&mut context, &input.clone().into(), 6000);
+        let _ = precompile.1(&mut context, &input.clone().into(), 6000, Address::ZERO);
 
         // Make a second call with the leaf rng already initialized
         let insufficient_gas = 100;
-        let result = precompile.1(&mut context, &input.into(), insufficient_gas);
+        let result = precompile.1(&mut context, &input.into(), insufficient_gas, Address::ZERO);
         assert!(result.is_err());
 
         match result.err() {
@@ -354,7 +474,7 @@ mod tests {
         // We can use dummy values for bytes_requested and personalization since we'll override the input
         let (gas_limit, _, mut context, precompile) = setup_rng_test(0, None);
 
-        let result = precompile.1(&mut context, &input.into(), gas_limit);
+        let result = precompile.1(&mut context, &input.into(), gas_limit, Address::ZERO);
         assert!(result.is_err());
 
         // We expect a PCError::Other complaining about input length
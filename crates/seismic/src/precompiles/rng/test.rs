@@ -1,10 +1,17 @@
 use super::*;
+use crate::chain::entropy_source::{EntropySource, OsEntropySource};
 use domain_sep_rng::RootRng;
 use rand_core::RngCore;
 use revm::primitives::B256;
 use schnorrkel::{keys::Keypair as SchnorrkelKeypair, ExpansionMode};
 use std::str::FromStr;
 
+fn local_entropy() -> [u8; 32] {
+    let mut entropy = [0u8; 32];
+    OsEntropySource.fill_bytes(&mut entropy);
+    entropy
+}
+
 fn hex_to_hash_bytes(input: &str) -> B256 {
     B256::from_str(input).unwrap()
 }
@@ -130,7 +137,7 @@ fn test_rng_local_entropy() {
 
     // Create second root RNG using the same context, but mix in local entropy.
     let root_rng = RootRng::test_default();
-    root_rng.append_local_entropy();
+    root_rng.append_local_entropy(&local_entropy());
 
     let mut leaf_rng = root_rng.fork(&[]);
     let mut bytes2 = [0u8; 32];
@@ -232,9 +239,9 @@ fn test_clone_after_local_entropy() {
 
     // simulate some initial transactions with local entropy
     let _ = root_rng.fork(&[]);
-    root_rng.append_local_entropy();
+    root_rng.append_local_entropy(&local_entropy());
     let _ = root_rng.fork(&[]);
-    root_rng.append_local_entropy();
+    root_rng.append_local_entropy(&local_entropy());
 
     // clone and test rng is same
     let root_rng_2 = root_rng.clone();
@@ -247,3 +254,44 @@ fn test_clone_after_local_entropy() {
     let mut bytes2 = [0u8; 32];
     leaf_rng_2.fill_bytes(&mut bytes2);
 }
+
+/// Entropy source returning a fixed byte, to observe it through the RNG output.
+#[derive(Debug)]
+struct FixedEntropySource(u8);
+
+impl EntropySource for FixedEntropySource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        dest.fill(self.0);
+    }
+}
+
+#[test]
+fn test_simulation_uses_injected_entropy_source() {
+    use crate::{chain::rng_container::RngContainer, transaction::abstraction::RngMode};
+    use domain_sep_rng::LeafKey;
+    use revm::primitives::Bytes;
+    use std::sync::Arc;
+
+    let leaf = LeafKey::new(Bytes::from_static(b"pers"));
+    let rng_bytes = |entropy: u8, mode: RngMode| {
+        let mut container = RngContainer::default();
+        container.set_entropy_source(Arc::new(FixedEntropySource(entropy)));
+        container.process_rng(&leaf, 32, mode, &B256::ZERO).unwrap()
+    };
+
+    assert_eq!(
+        rng_bytes(1, RngMode::Simulation),
+        rng_bytes(1, RngMode::Simulation),
+        "same entropy should give the same output"
+    );
+    assert_ne!(
+        rng_bytes(1, RngMode::Simulation),
+        rng_bytes(2, RngMode::Simulation),
+        "simulations should mix in the injected entropy"
+    );
+    assert_eq!(
+        rng_bytes(1, RngMode::Execution),
+        rng_bytes(2, RngMode::Execution),
+        "executions should ignore the entropy source"
+    );
+}
@@ -0,0 +1,71 @@
+use revm::precompile::PrecompileResult;
+use revm::primitives::{Address, HashMap};
+
+use crate::gas_schedule::SeismicGasSchedule;
+
+/// A stateless precompile priced with the gas schedule, called with the input, gas limit and
+/// the schedule of the current spec.
+pub type ScheduledPrecompileFn = fn(&[u8], u64, &SeismicGasSchedule) -> PrecompileResult;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ScheduledPrecompileWithAddress(pub Address, pub ScheduledPrecompileFn);
+
+/// Set of stateless Seismic precompiles. Unlike [`revm::precompile::Precompiles`], their gas is
+/// read from the [`SeismicGasSchedule`] at call time, so it can be re-tuned per chain.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduledPrecompiles {
+    inner: HashMap<Address, ScheduledPrecompileFn>,
+}
+
+impl ScheduledPrecompiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn extend<I: IntoIterator<Item = ScheduledPrecompileWithAddress>>(&mut self, iter: I) {
+        self.inner.extend(
+            iter.into_iter()
+                .map(|precompile| (precompile.0, precompile.1)),
+        );
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&ScheduledPrecompileFn> {
+        self.inner.get(address)
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        self.inner.contains_key(address)
+    }
+
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> {
+        self.inner.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the addresses of the precompiles that are not in `other`.
+    pub fn difference(&self, other: &Self) -> impl Iterator<Item = &Address> {
+        self.addresses()
+            .filter(move |address| !other.contains(address))
+    }
+}
+
+impl ScheduledPrecompileWithAddress {
+    /// Returns reference of address.
+    #[inline]
+    pub fn address(&self) -> &Address {
+        &self.0
+    }
+
+    /// Returns reference of precompile.
+    #[inline]
+    pub fn precompile(&self) -> &ScheduledPrecompileFn {
+        &self.1
+    }
+}
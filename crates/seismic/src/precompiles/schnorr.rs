@@ -0,0 +1,207 @@
+use k256::schnorr::{signature::hazmat::PrehashVerifier, Signature, SigningKey, VerifyingKey};
+use revm::{
+    precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::{Bytes, B256},
+};
+
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
+};
+use std::string::ToString;
+
+/* --------------------------------------------------------------------------
+Precompile Wiring
+-------------------------------------------------------------------------- */
+/// Address of BIP-340 Schnorr sign precompile.
+pub const SCHNORR_SIGN_ADDRESS: u64 = 110;
+
+/// Address of BIP-340 Schnorr verify precompile.
+pub const SCHNORR_VERIFY_ADDRESS: u64 = 111;
+
+/// Returns the Schnorr precompiles with their addresses.
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
+    [SCHNORR_SIGN, SCHNORR_VERIFY].into_iter()
+}
+
+pub const SCHNORR_SIGN: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(SCHNORR_SIGN_ADDRESS), schnorr_sign);
+
+pub const SCHNORR_VERIFY: ScheduledPrecompileWithAddress =
+    ScheduledPrecompileWithAddress(u64_to_address(SCHNORR_VERIFY_ADDRESS), schnorr_verify);
+
+/// Input length of the sign precompile: secret key, message and auxiliary randomness.
+const SIGN_INPUT_LENGTH: usize = 96;
+
+/// Input length of the verify precompile: x-only public key, message and signature.
+const VERIFY_INPUT_LENGTH: usize = 128;
+
+/* --------------------------------------------------------------------------
+Precompile Logic and Gas Calculation
+-------------------------------------------------------------------------- */
+/// # BIP-340 Schnorr Sign
+///
+/// Input is `secret_key (32) || message (32) || aux_rand (32)`, output is the 64-byte
+/// signature. The auxiliary randomness is taken from the input so that signing stays
+/// deterministic; callers wanting fresh randomness can get it from the RNG precompile.
+///
+/// ## Gas
+/// Signing takes two fixed-base scalar multiplications, for the nonce point and the x-only
+/// public key, and three tagged SHA-256 hashes of short inputs. Fixed-base multiplications are
+/// several times cheaper than the variable-base ones of ecrecover, so we charge the same
+/// `signature_base` as ECDSA signing, which stays conservative.
+pub fn schnorr_sign(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    let gas_used = schedule.signature_base;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    if input.len() != SIGN_INPUT_LENGTH {
+        return Err(PrecompileError::Other("Invalid input length".to_string()));
+    }
+    let signing_key = SigningKey::from_bytes(&input[0..32])
+        .map_err(|e| PrecompileError::Other(format!("Invalid secret key: {e}")))?;
+    let message: &[u8; 32] = input[32..64].try_into().unwrap();
+    let aux_rand: &[u8; 32] = input[64..96].try_into().unwrap();
+
+    let signature = signing_key
+        .sign_prehash_with_aux_rand(message, aux_rand)
+        .map_err(|e| PrecompileError::Other(format!("Signing failed: {e}")))?;
+
+    Ok(PrecompileOutput::new(
+        gas_used,
+        signature.to_bytes().to_vec().into(),
+    ))
+}
+
+/// # BIP-340 Schnorr Verify
+///
+/// Input is `x_only_public_key (32) || message (32) || signature (64)`. Like `P256VERIFY`, the
+/// output is the 32-byte word `1` if the signature is valid, and empty otherwise.
+///
+/// ## Gas
+/// Verification is one double scalar multiplication and a square root to lift the public
+/// key, the same work ecrecover does, so it is charged `signature_base`.
+pub fn schnorr_verify(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    let gas_used = schedule.signature_base;
+    if gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas);
+    }
+
+    if input.len() != VERIFY_INPUT_LENGTH {
+        return Err(PrecompileError::Other("Invalid input length".to_string()));
+    }
+    let valid = VerifyingKey::from_bytes(&input[0..32])
+        .ok()
+        .zip(Signature::try_from(&input[64..128]).ok())
+        .is_some_and(|(key, signature)| key.verify_prehash(&input[32..64], &signature).is_ok());
+
+    let output = if valid {
+        B256::with_last_byte(1).into()
+    } else {
+        Bytes::new()
+    };
+    Ok(PrecompileOutput::new(gas_used, output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::hex;
+
+    const BASE_GAS: u64 = SeismicGasSchedule::MERCURY.signature_base;
+
+    /// Test vectors 0 and 1 of BIP-340: secret key, public key, aux_rand, message, signature.
+    const VECTORS: [([u8; 32], [u8; 32], [u8; 32], [u8; 32], [u8; 64]); 2] = [
+        (
+            hex!("0000000000000000000000000000000000000000000000000000000000000003"),
+            hex!("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0"),
+        ),
+        (
+            hex!("B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF"),
+            hex!("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+            hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+            hex!("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89"),
+            hex!("6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A"),
+        ),
+    ];
+
+    #[test]
+    fn test_sign_bip340_vectors() {
+        for (secret_key, _, aux_rand, message, signature) in VECTORS {
+            let input = [&secret_key[..], &message, &aux_rand].concat();
+            let output = schnorr_sign(&input, BASE_GAS, &SeismicGasSchedule::MERCURY).unwrap();
+            assert_eq!(output.gas_used, BASE_GAS);
+            assert_eq!(output.bytes.as_ref(), signature);
+        }
+    }
+
+    #[test]
+    fn test_verify_bip340_vectors() {
+        for (_, public_key, _, message, signature) in VECTORS {
+            let input = [&public_key[..], &message, &signature].concat();
+            let output = schnorr_verify(&input, BASE_GAS, &SeismicGasSchedule::MERCURY).unwrap();
+            assert_eq!(output.bytes, Bytes::from(B256::with_last_byte(1)));
+
+            let mut tampered = input.clone();
+            tampered[40] ^= 1;
+            let output = schnorr_verify(&tampered, BASE_GAS, &SeismicGasSchedule::MERCURY).unwrap();
+            assert!(output.bytes.is_empty(), "Tampered message must not verify");
+        }
+    }
+
+    #[test]
+    fn test_verify_invalid_public_key() {
+        // Not the x-coordinate of a curve point (BIP-340 test vector 5).
+        let (_, _, _, message, signature) = VECTORS[1];
+        let public_key = hex!("EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34");
+        let input = [&public_key[..], &message, &signature].concat();
+        let output = schnorr_verify(&input, BASE_GAS, &SeismicGasSchedule::MERCURY).unwrap();
+        assert!(output.bytes.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_input_length() {
+        for precompile in [schnorr_sign, schnorr_verify] {
+            match precompile(&[0u8; 64], BASE_GAS, &SeismicGasSchedule::MERCURY).err() {
+                Some(PrecompileError::Other(msg)) => assert_eq!(msg, "Invalid input length"),
+                other => panic!("Expected invalid input length, got: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_secret_key() {
+        let result = schnorr_sign(
+            &[0u8; SIGN_INPUT_LENGTH],
+            BASE_GAS,
+            &SeismicGasSchedule::MERCURY,
+        );
+        assert!(
+            matches!(result, Err(PrecompileError::Other(msg)) if msg.starts_with("Invalid secret key"))
+        );
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        for precompile in [schnorr_sign, schnorr_verify] {
+            let result = precompile(
+                &[0u8; VERIFY_INPUT_LENGTH],
+                BASE_GAS - 1,
+                &SeismicGasSchedule::MERCURY,
+            );
+            assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+        }
+    }
+}
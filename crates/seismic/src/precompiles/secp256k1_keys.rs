@@ -0,0 +1,245 @@
+use revm::{
+    context::ContextTr,
+    precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::{Address, Bytes},
+};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+use super::{secret::SecretKeyGuard, stateful_precompile::StatefulPrecompileWithAddress};
+use crate::{
+    api::exec::SeismicContextTr, gas_schedule::SeismicGasSchedule,
+    transaction::abstraction::SeismicTxTr,
+};
+use std::{string::ToString, vec::Vec};
+
+/* --------------------------------------------------------------------------
+Precompile Wiring
+-------------------------------------------------------------------------- */
+/// Address of SECP256K1 key utility precompile.
+pub const SECP256K1_KEYS_ADDRESS: u64 = 114;
+
+pub fn secp256k1_keys_precompile_iter<CTX: SeismicContextTr>(
+) -> impl Iterator<Item = StatefulPrecompileWithAddress<CTX>> {
+    [secp256k1_keys_precompile::<CTX>()].into_iter()
+}
+
+pub fn secp256k1_keys_precompile<CTX: SeismicContextTr>() -> StatefulPrecompileWithAddress<CTX> {
+    StatefulPrecompileWithAddress(
+        u64_to_address(SECP256K1_KEYS_ADDRESS),
+        secp256k1_keys::<CTX>,
+    )
+}
+
+/// Derives the public keys and address of the secret key that follows.
+pub const OP_DERIVE: u8 = 0x00;
+/// Generates a fresh keypair.
+pub const OP_GENERATE: u8 = 0x01;
+
+/// Label of the leaf RNG generated secret keys are drawn from.
+const KEYGEN_LABEL: &[u8] = b"seismic secp256k1 keygen";
+
+const SECRET_KEY_LENGTH: usize = 32;
+
+/// Keccak-256 of the 64-byte public key, as for the `KECCAK256` opcode: 30 + 6 per word.
+const ADDRESS_HASH_COST: u64 = 30 + 6 * 2;
+
+/// Deriving the public key is one fixed-base scalar multiplication, cheaper than signing, which
+/// we still price at the signing `signature_base` to stay conservative.
+fn derive_cost(schedule: &SeismicGasSchedule) -> u64 {
+    schedule.signature_base + ADDRESS_HASH_COST
+}
+
+/* --------------------------------------------------------------------------
+Precompile Logic and Gas Calculation
+-------------------------------------------------------------------------- */
+/// # SECP256K1 Key Utility
+///
+/// The first input byte selects the operation:
+///
+/// | Input                              | Output                                                   |
+/// | ---------------------------------- | -------------------------------------------------------- |
+/// | `0x00 \|\| secret_key (32)`          | `compressed (33) \|\| uncompressed (65) \|\| address (20)`   |
+/// | `0x01`                             | `secret_key (32) \|\| compressed (33) \|\| uncompressed (65) \|\| address (20)` |
+///
+/// The uncompressed public key keeps its `0x04` prefix, and the address is the last 20 bytes of
+/// the Keccak-256 hash of the public key, as for Ethereum accounts.
+///
+/// Generated secret keys are drawn from the RNG container, from a leaf RNG reserved to this
+/// precompile, so no other contract of the transaction can predict them through the RNG
+/// precompile.
+///
+/// ## Gas
+/// Deriving costs `signature_base` plus the Keccak-256 of the address. Generating adds 32 bytes of the
+/// RNG precompile, including its init cost on the first generation of the transaction.
+fn secp256k1_keys<CTX: SeismicContextTr>(
+    evmctx: &mut CTX,
+    input: &Bytes,
+    gas_limit: u64,
+    _caller: Address,
+) -> PrecompileResult {
+    let schedule = evmctx.gas_schedule();
+    match (input.first(), input.len()) {
+        (Some(&OP_DERIVE), 33) => {
+            let cost = derive_cost(&schedule);
+            if cost > gas_limit {
+                return Err(PrecompileError::OutOfGas);
+            }
+            let secret_key = SecretKey::from_slice(&input[1..])
+                .map(SecretKeyGuard::new)
+                .map_err(|e| PrecompileError::Other(format!("Invalid secret key: {e}")))?;
+            Ok(PrecompileOutput::new(
+                cost,
+                public_key_encodings(&secret_key).into(),
+            ))
+        }
+        (Some(&OP_GENERATE), 1) => {
+            let cost = derive_cost(&schedule)
+                + evmctx.chain().calculate_precompile_gas_cost(
+                    KEYGEN_LABEL,
+                    SECRET_KEY_LENGTH,
+                    &schedule,
+                );
+            if cost > gas_limit {
+                return Err(PrecompileError::OutOfGas);
+            }
+            let secret_key = generate_secret_key(evmctx, KEYGEN_LABEL)?;
+            let output = [
+                &secret_key.secret_bytes()[..],
+                &public_key_encodings(&secret_key),
+            ]
+            .concat();
+            Ok(PrecompileOutput::new(cost, output.into()))
+        }
+        _ => Err(PrecompileError::Other("Invalid input".to_string())),
+    }
+}
+
+/// Draws a secret key from the leaf RNG reserved to the precompile using `label`.
+pub(crate) fn generate_secret_key<CTX: SeismicContextTr>(
+    evmctx: &mut CTX,
+    label: &'static [u8],
+) -> Result<SecretKeyGuard, PrecompileError> {
+    let kernel_mode = evmctx.tx().rng_mode();
+    let tx_hash = evmctx.tx().tx_hash();
+    loop {
+        let bytes =
+            evmctx
+                .chain()
+                .precompile_rng_bytes(label, SECRET_KEY_LENGTH, kernel_mode, &tx_hash)?;
+        // Rejection sampling, a draw outside the curve order is negligibly likely.
+        let key = SecretKey::from_slice(&bytes);
+        #[cfg(feature = "zeroize_memory")]
+        revm::interpreter::interpreter::zeroize_bytes(bytes);
+        if let Ok(key) = key {
+            return Ok(SecretKeyGuard::new(key));
+        }
+    }
+}
+
+/// `compressed || uncompressed || address` of the public key of `secret_key`.
+fn public_key_encodings(secret_key: &SecretKey) -> Vec<u8> {
+    let public_key = PublicKey::from_secret_key(&Secp256k1::signing_only(), secret_key);
+    let uncompressed = public_key.serialize_uncompressed();
+    let address = Address::from_raw_public_key(&uncompressed[1..]);
+    [
+        &public_key.serialize()[..],
+        &uncompressed,
+        address.as_slice(),
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transaction::abstraction::SeismicTransaction, DefaultSeismic, SeismicContext};
+    use revm::{
+        database::EmptyDB,
+        primitives::{address, hex, B256},
+        Context,
+    };
+
+    fn context() -> SeismicContext<EmptyDB> {
+        let tx = SeismicTransaction::default().with_tx_hash(B256::from([0u8; 32]));
+        Context::seismic().with_tx(tx)
+    }
+
+    fn run(context: &mut SeismicContext<EmptyDB>, input: &[u8]) -> PrecompileResult {
+        secp256k1_keys(
+            context,
+            &Bytes::copy_from_slice(input),
+            100_000,
+            Address::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_derive_known_key() {
+        // The secret key 1 is the generator, whose address is well known.
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+        let output = run(&mut context(), &[&[OP_DERIVE][..], &secret_key].concat()).unwrap();
+
+        assert_eq!(output.gas_used, derive_cost(&SeismicGasSchedule::MERCURY));
+        assert_eq!(output.bytes.len(), 33 + 65 + 20);
+        assert_eq!(
+            output.bytes[..33],
+            hex!("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798")
+        );
+        assert_eq!(output.bytes[33], 0x04);
+        assert_eq!(output.bytes[34..66], output.bytes[1..33]);
+        assert_eq!(
+            Address::from_slice(&output.bytes[98..]),
+            address!("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+        );
+    }
+
+    #[test]
+    fn test_generate_keypair() {
+        let mut context = context();
+        let first = run(&mut context, &[OP_GENERATE]).unwrap();
+        let second = run(&mut context, &[OP_GENERATE]).unwrap();
+        assert_eq!(first.bytes.len(), 32 + 33 + 65 + 20);
+        assert_ne!(first.bytes, second.bytes);
+        assert!(
+            second.gas_used < first.gas_used,
+            "Only the first generation pays the RNG init cost"
+        );
+
+        // The public keys and address match the generated secret key.
+        let derived = run(
+            &mut context,
+            &[&[OP_DERIVE][..], &first.bytes[..32]].concat(),
+        )
+        .unwrap();
+        assert_eq!(derived.bytes, first.bytes[32..]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut context = context();
+        for input in [&[][..], &[OP_DERIVE; 32], &[OP_GENERATE, 0], &[0x02]] {
+            match run(&mut context, input).err() {
+                Some(PrecompileError::Other(msg)) => assert_eq!(msg, "Invalid input"),
+                other => panic!("Expected invalid input, got: {other:?}"),
+            }
+        }
+
+        let result = run(&mut context, &[OP_DERIVE; 33]);
+        assert!(
+            matches!(result, Err(PrecompileError::Other(msg)) if msg.starts_with("Invalid secret key"))
+        );
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        let mut context = context();
+        let result = secp256k1_keys(
+            &mut context,
+            &Bytes::from_static(&[OP_GENERATE]),
+            derive_cost(&SeismicGasSchedule::MERCURY),
+            Address::ZERO,
+        );
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
+    }
+}
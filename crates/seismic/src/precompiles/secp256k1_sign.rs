@@ -1,9 +1,13 @@
-use revm::precompile::{
-    u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
-};
+use revm::precompile::{u64_to_address, PrecompileError, PrecompileOutput, PrecompileResult};
 
 use secp256k1::Secp256k1;
 
+use crate::{
+    gas_schedule::SeismicGasSchedule,
+    precompiles::scheduled_precompile::ScheduledPrecompileWithAddress,
+};
+use std::string::ToString;
+
 /* --------------------------------------------------------------------------
 Precompile Wiring
 -------------------------------------------------------------------------- */
@@ -11,30 +15,32 @@ Precompile Wiring
 pub const SECP256K1_SIGN_ADDRESS: u64 = 105;
 
 /// Returns the ecdh precompile with its address.
-pub fn precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+pub fn precompiles() -> impl Iterator<Item = ScheduledPrecompileWithAddress> {
     [SECP256K1_SIGN].into_iter()
 }
 
-pub const SECP256K1_SIGN: PrecompileWithAddress = PrecompileWithAddress(
+pub const SECP256K1_SIGN: ScheduledPrecompileWithAddress = ScheduledPrecompileWithAddress(
     u64_to_address(SECP256K1_SIGN_ADDRESS),
     secp256k1_sign_ecdsa_recoverable,
 );
 
-const BASE_GAS: u64 = 3000;
-
 /* --------------------------------------------------------------------------
 Precompile Logic and Gas Calculation
 -------------------------------------------------------------------------- */
 /// We give signing the same gas cost as Ethereum's ecrecover precompile,
-/// which we expect is slightly conservative.
+/// which we expect is slightly conservative, charging `schedule.signature_base`.
 ///
 /// Recovering a public key from a signature requires one scalar inverse, an ecmult, a field square root,
 /// two scalar mul, and some other operations that take very little time relatively. Ecrecover must
 /// recover the public key, plus do some keccak hashing to recover the address.
 /// In comparision, signing is one scalar inverse, an ecmult, two scalar mul, and some other operations
 /// Notably, it does not require any square roots or keccak hashing, so it should be slight less gas-expensive.
-pub fn secp256k1_sign_ecdsa_recoverable(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    let gas_used = BASE_GAS;
+pub fn secp256k1_sign_ecdsa_recoverable(
+    input: &[u8],
+    gas_limit: u64,
+    schedule: &SeismicGasSchedule,
+) -> PrecompileResult {
+    let gas_used = schedule.signature_base;
     if gas_used > gas_limit {
         return Err(PrecompileError::OutOfGas.into());
     }
@@ -82,9 +88,13 @@ mod tests {
         let mut input = sk_bytes.to_vec();
         input.extend_from_slice(&message);
         let gas_limit = 4000;
-        let output = secp256k1_sign_ecdsa_recoverable(&Bytes::from(input), gas_limit)
-            .unwrap()
-            .bytes;
+        let output = secp256k1_sign_ecdsa_recoverable(
+            &Bytes::from(input),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
 
         let sig: [u8; 64] = output[0..64].try_into().unwrap();
         let secp = Secp256k1::verification_only();
@@ -118,9 +128,13 @@ mod tests {
         let mut input = sk_bytes.to_vec();
         input.extend_from_slice(&message);
         let gas_limit = 4000;
-        let output = secp256k1_sign_ecdsa_recoverable(&Bytes::from(input), gas_limit)
-            .unwrap()
-            .bytes;
+        let output = secp256k1_sign_ecdsa_recoverable(
+            &Bytes::from(input),
+            gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        )
+        .unwrap()
+        .bytes;
 
         let sig: B512 = output[0..64].try_into().unwrap();
         let recid: u8 = output[64];
@@ -133,7 +147,8 @@ mod tests {
     fn test_invalid_input_length() {
         let input = Bytes::from("short_bytes");
         let gas_limit = 4000;
-        let result = secp256k1_sign_ecdsa_recoverable(&input, gas_limit);
+        let result =
+            secp256k1_sign_ecdsa_recoverable(&input, gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_err());
         match result.err() {
             Some(PrecompileError::Other(msg)) => {
@@ -149,7 +164,8 @@ mod tests {
     fn test_invalid_secret_key() {
         let input = Bytes::from([0u8; 64]);
         let gas_limit = 4000;
-        let result = secp256k1_sign_ecdsa_recoverable(&input, gas_limit);
+        let result =
+            secp256k1_sign_ecdsa_recoverable(&input, gas_limit, &SeismicGasSchedule::MERCURY);
         assert!(result.is_err());
         match result.err() {
             Some(PrecompileError::Other(msg)) => {
@@ -169,7 +185,11 @@ mod tests {
         input.extend_from_slice(&message);
         let small_gas_limit = 500; // well below 1180
 
-        let result = secp256k1_sign_ecdsa_recoverable(&Bytes::from(input), small_gas_limit);
+        let result = secp256k1_sign_ecdsa_recoverable(
+            &Bytes::from(input),
+            small_gas_limit,
+            &SeismicGasSchedule::MERCURY,
+        );
         assert!(result.is_err());
 
         match result.err() {
@@ -0,0 +1,71 @@
+//! Key material and plaintexts held by precompiles while they run.
+//!
+//! With the `zeroize_memory` feature, [`Secret`] and [`SecretKeyGuard`] wipe what they hold when
+//! dropped, like the interpreter buffers. Without it they are plain wrappers.
+use core::ops::{Deref, DerefMut};
+use secp256k1::SecretKey;
+
+#[cfg(feature = "zeroize_memory")]
+pub(crate) use zeroize::Zeroizing as Secret;
+
+/// Value wiped on drop with `zeroize_memory`.
+#[cfg(not(feature = "zeroize_memory"))]
+#[derive(Debug, Default)]
+pub(crate) struct Secret<T>(T);
+
+#[cfg(not(feature = "zeroize_memory"))]
+impl<T> Secret<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(not(feature = "zeroize_memory"))]
+impl<T> Deref for Secret<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "zeroize_memory"))]
+impl<T> DerefMut for Secret<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// secp256k1 secret key erased on drop with `zeroize_memory`.
+///
+/// [`SecretKey`] does not implement `Zeroize`, it is erased with
+/// [`SecretKey::non_secure_erase`] instead.
+#[derive(Debug)]
+pub(crate) struct SecretKeyGuard(SecretKey);
+
+impl SecretKeyGuard {
+    pub(crate) fn new(key: SecretKey) -> Self {
+        Self(key)
+    }
+}
+
+impl Deref for SecretKeyGuard {
+    type Target = SecretKey;
+
+    fn deref(&self) -> &SecretKey {
+        &self.0
+    }
+}
+
+impl DerefMut for SecretKeyGuard {
+    fn deref_mut(&mut self) -> &mut SecretKey {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "zeroize_memory")]
+impl Drop for SecretKeyGuard {
+    fn drop(&mut self) {
+        self.0.non_secure_erase();
+    }
+}
@@ -1,8 +1,8 @@
 use revm::precompile::PrecompileResult;
-use revm::primitives::{Address, Bytes};
-use std::collections::{HashMap, HashSet};
+use revm::primitives::{Address, Bytes, HashMap, HashSet};
 
-pub type StatefulPrecompileFn<CTX> = fn(&mut CTX, &Bytes, u64) -> PrecompileResult;
+/// A precompile with access to the context, called with the input, gas limit and caller.
+pub type StatefulPrecompileFn<CTX> = fn(&mut CTX, &Bytes, u64, Address) -> PrecompileResult;
 
 #[derive(Clone, Debug)]
 pub struct StatefulPrecompileWithAddress<CTX>(pub Address, pub StatefulPrecompileFn<CTX>);
@@ -16,8 +16,8 @@ pub struct StatefulPrecompiles<CTX> {
 impl<CTX> StatefulPrecompiles<CTX> {
     pub fn new() -> Self {
         Self {
-            inner: HashMap::new(),
-            addresses: HashSet::new(),
+            inner: HashMap::default(),
+            addresses: HashSet::default(),
         }
     }
 
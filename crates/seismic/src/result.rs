@@ -1,5 +1,6 @@
+use core::convert::Infallible;
 use core::fmt;
-use std::convert::Infallible;
+use std::string::ToString;
 
 use revm::context_interface::{context::ContextError, result::HaltReason};
 
@@ -8,13 +8,15 @@ use revm::primitives::hardfork::{SpecId, UnknownHardfork};
 pub enum SeismicSpecId {
     #[default]
     MERCURY = 100,
+    /// Separates the RNG precompile streams of different call frames.
+    VENUS = 101,
 }
 
 impl SeismicSpecId {
     /// Converts the [`SeismicSpecId`] into a [`SpecId`].
     pub const fn into_eth_spec(self) -> SpecId {
         match self {
-            Self::MERCURY => SpecId::PRAGUE,
+            Self::MERCURY | Self::VENUS => SpecId::PRAGUE,
         }
     }
 
@@ -35,6 +37,7 @@ impl FromStr for SeismicSpecId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             name::MERCURY => Ok(SeismicSpecId::MERCURY),
+            name::VENUS => Ok(SeismicSpecId::VENUS),
             _ => Err(UnknownHardfork),
         }
     }
@@ -44,6 +47,7 @@ impl From<SeismicSpecId> for &'static str {
     fn from(spec_id: SeismicSpecId) -> Self {
         match spec_id {
             SeismicSpecId::MERCURY => name::MERCURY,
+            SeismicSpecId::VENUS => name::VENUS,
         }
     }
 }
@@ -57,6 +61,7 @@ impl From<SpecId> for SeismicSpecId {
 /// String identifiers for Optimism hardforks
 pub mod name {
     pub const MERCURY: &str = "Mercury";
+    pub const VENUS: &str = "Venus";
 }
 
 #[cfg(test)]
@@ -67,16 +72,26 @@ mod tests {
     #[test]
     fn test_seismic_spec_id_eth_spec_compatibility() {
         // Define test cases: (SeismicSpecId, enabled in ETH specs, enabled in Seismic specs)
-        let test_cases = [(
-            SeismicSpecId::MERCURY,
-            vec![
-                (SpecId::MERGE, true),
-                (SpecId::SHANGHAI, true),
-                (SpecId::CANCUN, true),
-                (SpecId::OSAKA, false),
-            ],
-            vec![(SeismicSpecId::MERCURY, true)],
-        )];
+        let test_cases = [
+            (
+                SeismicSpecId::MERCURY,
+                vec![
+                    (SpecId::MERGE, true),
+                    (SpecId::SHANGHAI, true),
+                    (SpecId::CANCUN, true),
+                    (SpecId::OSAKA, false),
+                ],
+                vec![
+                    (SeismicSpecId::MERCURY, true),
+                    (SeismicSpecId::VENUS, false),
+                ],
+            ),
+            (
+                SeismicSpecId::VENUS,
+                vec![(SpecId::PRAGUE, true), (SpecId::OSAKA, false)],
+                vec![(SeismicSpecId::MERCURY, true), (SeismicSpecId::VENUS, true)],
+            ),
+        ];
 
         for (seismic_spec, eth_tests, seismic_tests) in test_cases {
             // Test ETH spec compatibility
@@ -53,14 +53,14 @@ impl<T: Transaction> SeismicTransaction<T> {
     }
 }
 
-impl<T: Transaction> std::ops::Deref for SeismicTransaction<T> {
+impl<T: Transaction> core::ops::Deref for SeismicTransaction<T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         &self.base
     }
 }
 
-impl<T: Transaction> std::ops::DerefMut for SeismicTransaction<T> {
+impl<T: Transaction> core::ops::DerefMut for SeismicTransaction<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.base
     }